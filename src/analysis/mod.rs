@@ -0,0 +1,8 @@
+//! Structural analysis of a single LP problem.
+//!
+//! - `network`: Recognising network-flow structure in the constraints.
+//! - `symmetry`: Detecting interchangeable variables and constraints.
+//!
+
+pub mod network;
+pub mod symmetry;
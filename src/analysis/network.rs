@@ -0,0 +1,285 @@
+//! Recognising network-flow structure.
+//!
+//! A constraint matrix is a node-arc incidence matrix when every column has at
+//! most one `+1` and at most one `-1`: each constraint is then the flow balance
+//! of a node and each variable the flow along an arc. Network simplex and
+//! other specialised algorithms solve such problems much faster than a general
+//! LP solver, and the structure is often hidden in a larger model.
+//!
+//! `find_network` looks for the largest such submatrix it can find greedily.
+//! Constraints may be negated to fit, so `-x + y <= 0` and `x - y >= 0` are
+//! recognised as the same node.
+//!
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{diff::coefficient_map, model::Constraint, problem::LpProblem};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+/// A variable viewed as the flow along an arc between two constraints.
+pub struct Arc {
+    /// The name of the variable.
+    pub variable: String,
+    /// The node the flow leaves, or `None` if it enters the network from outside.
+    pub from: Option<String>,
+    /// The node the flow enters, or `None` if it leaves the network.
+    pub to: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// A flow network embedded in the constraints of a problem.
+pub struct Network {
+    /// The constraints acting as flow balance nodes, sorted by name.
+    pub nodes: Vec<String>,
+    /// The nodes whose constraint was negated to fit the incidence structure.
+    pub reversed: Vec<String>,
+    /// The variables acting as arcs, sorted by name.
+    pub arcs: Vec<Arc>,
+    /// The constraints outside of the network, sorted by name.
+    pub other_constraints: Vec<String>,
+}
+
+impl Network {
+    #[must_use]
+    #[inline]
+    /// Returns `true` if no network structure was found.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    #[must_use]
+    #[inline]
+    /// Returns `true` if every constraint of the problem is a node of the network.
+    pub fn is_pure(&self) -> bool {
+        !self.is_empty() && self.other_constraints.is_empty()
+    }
+}
+
+impl std::fmt::Display for Network {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let total = self.nodes.len() + self.other_constraints.len();
+        writeln!(
+            f,
+            "Network with {} nodes and {} arcs covering {} of {total} constraints",
+            self.nodes.len(),
+            self.arcs.len(),
+            self.nodes.len()
+        )?;
+        for Arc { variable, from, to } in &self.arcs {
+            let (from, to) = (from.as_deref().unwrap_or("(outside)"), to.as_deref().unwrap_or("(outside)"));
+            writeln!(f, "  {variable}: {from} -> {to}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A union-find over rows that tracks whether each row is negated relative to its representative.
+struct ParityUnionFind {
+    parent: Vec<usize>,
+    /// Whether a row is negated relative to its parent.
+    parity: Vec<bool>,
+    rank: Vec<u8>,
+}
+
+impl ParityUnionFind {
+    fn new(size: usize) -> Self {
+        Self { parent: (0..size).collect(), parity: vec![false; size], rank: vec![0; size] }
+    }
+
+    /// Returns the representative of `row` and whether `row` is negated relative to it.
+    fn find(&mut self, row: usize) -> (usize, bool) {
+        let (mut root, mut negated) = (row, false);
+        while self.parent[root] != root {
+            negated ^= self.parity[root];
+            root = self.parent[root];
+        }
+        // Point every row on the path straight at the root
+        let (mut node, mut node_negated) = (row, negated);
+        while self.parent[node] != root && self.parent[node] != node {
+            let (next, next_negated) = (self.parent[node], node_negated ^ self.parity[node]);
+            self.parent[node] = root;
+            self.parity[node] = node_negated;
+            (node, node_negated) = (next, next_negated);
+        }
+        (root, negated)
+    }
+
+    /// Records that exactly one of `a` and `b` is negated if `odd`, returning `false` if that contradicts earlier records.
+    fn union(&mut self, a: usize, b: usize, odd: bool) -> bool {
+        let ((root_a, negated_a), (root_b, negated_b)) = (self.find(a), self.find(b));
+        if root_a == root_b {
+            return negated_a ^ negated_b == odd;
+        }
+        let (child, root) = if self.rank[root_a] > self.rank[root_b] { (root_b, root_a) } else { (root_a, root_b) };
+        self.parent[child] = root;
+        self.parity[child] = negated_a ^ negated_b ^ odd;
+        if self.rank[child] == self.rank[root] {
+            self.rank[root] += 1;
+        }
+        true
+    }
+}
+
+/// Chooses whether to negate each row so every column shared by two rows has opposite signs.
+///
+/// Returns the negated rows, or the row to drop when the signs cannot be reconciled.
+fn orient(rows: &BTreeMap<usize, BTreeMap<&str, f64>>, size: usize) -> Result<BTreeSet<usize>, usize> {
+    let mut columns: BTreeMap<&str, Vec<(usize, f64)>> = BTreeMap::new();
+    for (row, coefficients) in rows {
+        for (variable, coefficient) in coefficients {
+            columns.entry(variable).or_default().push((*row, *coefficient));
+        }
+    }
+
+    let mut rows_found = ParityUnionFind::new(size);
+    for entries in columns.values() {
+        // Equal signs need exactly one of the two rows negated.
+        if let [(a, sign_a), (b, sign_b)] = entries[..] {
+            if !rows_found.union(a, b, sign_a == sign_b) {
+                return Err(a.max(b));
+            }
+        }
+    }
+    Ok(rows.keys().copied().filter(|row| rows_found.find(*row).1).collect())
+}
+
+/// Drops rows sharing a variable with two or more other rows, most crowded rows first.
+///
+/// The number of rows per variable and of crowded variables per row are
+/// updated as rows are dropped, rather than recounted after every drop.
+fn drop_crowded(rows: &mut BTreeMap<usize, BTreeMap<&str, f64>>) {
+    let mut columns: BTreeMap<&str, BTreeSet<usize>> = BTreeMap::new();
+    for (row, coefficients) in rows.iter() {
+        for variable in coefficients.keys() {
+            columns.entry(variable).or_default().insert(*row);
+        }
+    }
+
+    let mut crowded_counts: BTreeMap<usize, usize> = BTreeMap::new();
+    for (row, coefficients) in rows.iter() {
+        let count = coefficients.keys().filter(|variable| columns[*variable].len() > 2).count();
+        if count > 0 {
+            crowded_counts.insert(*row, count);
+        }
+    }
+    let mut crowded: BTreeSet<(usize, usize)> = crowded_counts.iter().map(|(row, count)| (*count, *row)).collect();
+
+    while let Some(&(count, row)) = crowded.iter().next_back() {
+        crowded.remove(&(count, row));
+        crowded_counts.remove(&row);
+        let coefficients = match rows.remove(&row) {
+            Some(coefficients) => coefficients,
+            None => continue,
+        };
+        for variable in coefficients.keys() {
+            let members = match columns.get_mut(variable) {
+                Some(members) => members,
+                None => continue,
+            };
+            members.remove(&row);
+            // The variable just stopped being crowded
+            if members.len() == 2 {
+                for other in members.iter() {
+                    if let Some(count) = crowded_counts.get_mut(other) {
+                        crowded.remove(&(*count, *other));
+                        *count -= 1;
+                        if *count > 0 {
+                            crowded.insert((*count, *other));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[must_use]
+/// Finds a set of constraints whose matrix is, up to negating rows, a node-arc incidence matrix.
+///
+/// Candidate rows are the standard constraints whose coefficients are all
+/// `1` or `-1`. Rows are dropped greedily, first from variables that appear
+/// in more than two candidates and then to resolve conflicting signs, so the
+/// result is large but not guaranteed to be the largest possible network.
+///
+/// # Arguments
+///
+/// * `problem` - The problem to analyse.
+///
+pub fn find_network(problem: &LpProblem) -> Network {
+    let names: Vec<&str> = problem.constraints.keys().map(AsRef::as_ref).collect::<BTreeSet<_>>().into_iter().collect();
+    let mut rows: BTreeMap<usize, BTreeMap<&str, f64>> = BTreeMap::new();
+    for (index, name) in names.iter().enumerate() {
+        if let Constraint::Standard { coefficients, .. } = &problem.constraints[*name] {
+            let coefficients: BTreeMap<&str, f64> = coefficient_map(coefficients).into_iter().filter(|(_, c)| *c != 0.0).collect();
+            if !coefficients.is_empty() && coefficients.values().all(|c| c.abs() == 1.0) {
+                rows.insert(index, coefficients);
+            }
+        }
+    }
+
+    // Dropping rows only lowers the number of rows per variable, so no row becomes crowded again
+    drop_crowded(&mut rows);
+    let reversed = loop {
+        match orient(&rows, names.len()) {
+            Ok(reversed) => break reversed,
+            Err(row) => {
+                rows.remove(&row);
+            }
+        }
+    };
+
+    let mut columns: BTreeMap<&str, Arc> = BTreeMap::new();
+    for (row, coefficients) in &rows {
+        let node = names[*row].to_string();
+        for (variable, coefficient) in coefficients {
+            let arc = columns.entry(variable).or_insert_with(|| Arc { variable: variable.to_string(), from: None, to: None });
+            if (*coefficient > 0.0) != reversed.contains(row) {
+                arc.to = Some(node.clone());
+            } else {
+                arc.from = Some(node.clone());
+            }
+        }
+    }
+
+    Network {
+        nodes: rows.keys().map(|row| names[*row].to_string()).collect(),
+        reversed: reversed.iter().map(|row| names[*row].to_string()).collect(),
+        arcs: columns.into_values().collect(),
+        other_constraints: names
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !rows.contains_key(index))
+            .map(|(_, name)| name.to_string())
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{analysis::network::find_network, problem::LpProblem};
+
+    #[test]
+    fn test_find_network() {
+        let problem = LpProblem::parse(
+            "minimize\nobj: 2xab + 3xac + xbc + 4xbd + 2xcd\nsubject to\na: - xab - xac = -5\nb: xab - xbc - xbd = 0\nc: xac + xbc - xcd >= 0\nd: xbd + xcd = 5\ncap: xab + xac <= 6\nend",
+        )
+        .expect("test case not to fail");
+
+        let network = find_network(&problem);
+        assert_eq!(network.nodes, ["a", "b", "c", "d"]);
+        assert_eq!(network.other_constraints, ["cap"]);
+        assert!(network.reversed.is_empty());
+        assert!(!network.is_pure());
+        assert_eq!(
+            network.to_string(),
+            "Network with 4 nodes and 5 arcs covering 4 of 5 constraints\n  xab: a -> b\n  xac: a -> c\n  xbc: b -> c\n  xbd: b -> d\n  xcd: c -> d\n"
+        );
+
+        let reversed = LpProblem::parse("minimize\nobj: x + y\nsubject to\ns: x + y <= 4\nt: x >= 1\nend").expect("test case not to fail");
+        let network = find_network(&reversed);
+        assert!(network.is_pure());
+        assert_eq!(network.reversed, ["s"]);
+        assert_eq!(network.to_string(), "Network with 2 nodes and 2 arcs covering 2 of 2 constraints\n  x: s -> t\n  y: s -> (outside)\n");
+    }
+}
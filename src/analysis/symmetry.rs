@@ -0,0 +1,164 @@
+//! Detecting interchangeable variables and constraints.
+//!
+//! A symmetry of a problem is a permutation of its variables and constraints
+//! that maps the problem onto itself. Symmetric MIPs are slow to solve because
+//! branch and bound explores every equivalent copy of a solution, so knowing
+//! which variables are interchangeable helps to add symmetry-breaking
+//! constraints or to reformulate.
+//!
+//! `find_symmetry` reuses the graph and colour refinement behind
+//! `diff::structure::structural_match`: refinement splits the nodes into cells
+//! that no automorphism can mix, and a search for an automorphism mapping one
+//! member of a cell onto another confirms which members form an orbit.
+//! Coefficients, bounds and right-hand sides are compared exactly. Each search
+//! is capped by a budget, so on highly regular models an orbit may be reported
+//! as several smaller groups.
+//!
+
+use std::collections::BTreeMap;
+
+use crate::{
+    diff::structure::{refine, search, Graph, NodeKind, Search, SEARCH_BUDGET},
+    problem::LpProblem,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// The orbits of the automorphism group of a problem, restricted to orbits with more than one member.
+pub struct Symmetry {
+    /// Groups of interchangeable variables, each sorted by name.
+    pub variables: Vec<Vec<String>>,
+    /// Groups of interchangeable constraints, each sorted by name.
+    pub constraints: Vec<Vec<String>>,
+    /// `true` if a search ran out of budget, so some groups may be split into smaller ones.
+    pub incomplete: bool,
+}
+
+impl Symmetry {
+    #[must_use]
+    #[inline]
+    /// Returns `true` if no two variables or constraints are interchangeable.
+    pub fn is_empty(&self) -> bool {
+        self.variables.is_empty() && self.constraints.is_empty()
+    }
+}
+
+impl std::fmt::Display for Symmetry {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for orbit in &self.variables {
+            writeln!(f, "Symmetric variables: {}", orbit.join(", "))?;
+        }
+        for orbit in &self.constraints {
+            writeln!(f, "Symmetric constraints: {}", orbit.join(", "))?;
+        }
+        if self.incomplete {
+            writeln!(f, "Search budget exhausted, some groups may be incomplete")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the representative of the orbit containing `node`.
+fn find(parent: &mut [usize], mut node: usize) -> usize {
+    while parent[node] != node {
+        parent[node] = parent[parent[node]];
+        node = parent[node];
+    }
+    node
+}
+
+#[must_use]
+/// Finds the groups of variables and constraints that some symmetry of `problem` interchanges.
+///
+/// Two members of a group need not be swappable on their own; the group is
+/// an orbit, so for any two members some symmetry maps one onto the other.
+/// Each new orbit requires an automorphism search, which is fast on typical
+/// models but may backtrack on highly regular ones. A search that exhausts
+/// its budget is treated as failed and `Symmetry::incomplete` is set.
+///
+/// # Arguments
+///
+/// * `problem` - The problem to analyse.
+///
+pub fn find_symmetry(problem: &LpProblem) -> Symmetry {
+    let graph = Graph::new(problem);
+    let (colours, _) = refine(&graph, &graph, graph.colours.clone(), graph.colours.clone());
+
+    let mut cells: BTreeMap<u64, Vec<usize>> = BTreeMap::new();
+    for (node, colour) in colours.iter().enumerate() {
+        cells.entry(*colour).or_default().push(node);
+    }
+
+    let mut parent: Vec<usize> = (0..graph.nodes.len()).collect();
+    let mut incomplete = false;
+    for nodes in cells.values().filter(|nodes| nodes.len() > 1) {
+        let mut representatives: Vec<usize> = Vec::new();
+        for &node in nodes {
+            if representatives.iter().any(|&representative| find(&mut parent, representative) == find(&mut parent, node)) {
+                continue;
+            }
+            let automorphism = representatives.iter().find_map(|&representative| {
+                match search(&graph, &graph, &colours, &colours, &[(representative, node)], SEARCH_BUDGET) {
+                    Search::Found(mapping) => Some(mapping),
+                    Search::Different => None,
+                    Search::Unknown => {
+                        incomplete = true;
+                        None
+                    }
+                }
+            });
+            match automorphism {
+                Some(mapping) => {
+                    for (node, image) in mapping.into_iter().enumerate() {
+                        let (a, b) = (find(&mut parent, node), find(&mut parent, image));
+                        parent[a] = b;
+                    }
+                }
+                None => representatives.push(node),
+            }
+        }
+    }
+
+    let mut orbits: BTreeMap<usize, Vec<(NodeKind, &str)>> = BTreeMap::new();
+    for (node, entry) in graph.nodes.iter().enumerate() {
+        orbits.entry(find(&mut parent, node)).or_default().push(*entry);
+    }
+    let mut symmetry = Symmetry { incomplete, ..Symmetry::default() };
+    for members in orbits.into_values().filter(|members| members.len() > 1) {
+        let mut names: Vec<String> = members.iter().map(|(_, name)| name.to_string()).collect();
+        names.sort_unstable();
+        match members[0].0 {
+            NodeKind::Variable => symmetry.variables.push(names),
+            NodeKind::Constraint => symmetry.constraints.push(names),
+            NodeKind::Objective => {}
+        }
+    }
+    symmetry.variables.sort_unstable();
+    symmetry.constraints.sort_unstable();
+    symmetry
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{analysis::symmetry::find_symmetry, problem::LpProblem};
+
+    #[test]
+    fn test_find_symmetry() {
+        let problem = LpProblem::parse(
+            "maximize\nobj: a + b + c + 2d\nsubject to\nc1: a + b <= 1\nc2: b + c <= 1\nc3: a + c <= 1\nc4: d + a <= 2\nc5: d + b <= 2\nc6: d + c <= 2\nend",
+        )
+        .expect("test case not to fail");
+
+        let symmetry = find_symmetry(&problem);
+        assert!(!symmetry.incomplete);
+        assert_eq!(symmetry.variables, [["a", "b", "c"]]);
+        assert_eq!(symmetry.constraints, [["c1", "c2", "c3"], ["c4", "c5", "c6"]]);
+        assert_eq!(
+            symmetry.to_string(),
+            "Symmetric variables: a, b, c\nSymmetric constraints: c1, c2, c3\nSymmetric constraints: c4, c5, c6\n"
+        );
+
+        let asymmetric = LpProblem::parse("maximize\nobj: x + 2y\nsubject to\nc1: x + y <= 4\nend").expect("test case not to fail");
+        assert!(find_symmetry(&asymmetric).is_empty());
+    }
+}
@@ -2,11 +2,11 @@ use std::{env, error::Error, path::PathBuf};
 
 use lp_parser_rs::{parser::parse_file, problem::LpProblem};
 
-fn dissemble_single_file(path: &str) -> Result<(), Box<dyn Error>> {
+fn dissemble_single_file(path: &str, tighten_bounds: bool) -> Result<(), Box<dyn Error>> {
     let path = PathBuf::from(path);
     let input = parse_file(&path)?;
 
-    let problem = LpProblem::parse(&input).unwrap();
+    let mut problem = LpProblem::parse(&input).unwrap();
 
     // Print the parsed LP problem
     println!("Parsed LP Problem:");
@@ -18,14 +18,20 @@ fn dissemble_single_file(path: &str) -> Result<(), Box<dyn Error>> {
     println!("Constraint count={}", problem.constraint_count());
     println!("Variables count={}", problem.variable_count());
 
+    if tighten_bounds {
+        let changes = problem.tighten_bounds();
+        println!("Tightened bounds count={}", changes.len());
+        for change in changes {
+            println!("{change}");
+        }
+    }
+
     Ok(())
 }
 
-#[cfg(feature = "diff")]
 fn compare_lp_files(p1: &str, p2: &str) -> Result<(), Box<dyn Error>> {
     println!("Attempting to compare {p1} to {p2}");
-    use diff::Diff;
-    use lp_parser_rs::problem::LpProblemDiff;
+    use lp_parser_rs::diff::{build_diff_report, DiffOptions};
 
     let path = PathBuf::from(p1);
     let input1 = parse_file(&path)?;
@@ -35,36 +41,221 @@ fn compare_lp_files(p1: &str, p2: &str) -> Result<(), Box<dyn Error>> {
     let input2 = parse_file(&path)?;
     let problem2 = LpProblem::parse(&input2).unwrap();
 
-    let difference: LpProblemDiff = problem1.diff(&problem2);
+    let report = build_diff_report(&problem1, &problem2, &DiffOptions::default().with_rename_detection(true));
+    if report.is_empty() {
+        println!("No differences found");
+    } else {
+        print!("{report}");
+    }
+
+    Ok(())
+}
+
+fn lint_lp_file(path: &str, context: &mut lp_parser_rs::validation::ValidationContext, fix: bool) -> Result<(), Box<dyn Error>> {
+    use lp_parser_rs::syntax::edit::SourceEditor;
 
-    // Variables altered
-    difference.variables.altered.iter().for_each(|(k, v)| {
-        if let Some(v_name) = problem2.variables.get(k) {
-            println!("Variable {k} changed from {v:?} to {v_name:?}");
+    let input = parse_file(&PathBuf::from(path))?;
+    let problem = LpProblem::parse(&input).unwrap();
+
+    context.validate_source(&problem, &input);
+    if fix {
+        let mut editor = SourceEditor::new(&input);
+        for fix in context.diagnostics().iter().filter_map(|diagnostic| diagnostic.fix.as_ref()) {
+            match fix.edit(&mut editor) {
+                Ok(true) => println!("fixed: {fix}"),
+                Ok(false) => {}
+                Err(error) => println!("not fixed: {fix} ({error})"),
+            }
+        }
+        if !editor.edits().is_empty() {
+            let fixed = editor.apply();
+            let problem = LpProblem::parse(&fixed).map_err(|error| format!("the fixed {path} does not parse: {error}"))?;
+            std::fs::write(path, &fixed)?;
+            context.validate_source(&problem, &fixed);
         }
-    });
+    }
+    for diagnostic in context.diagnostics() {
+        println!("{diagnostic}");
+        if let Some(fix) = &diagnostic.fix {
+            println!("  fix: {fix}");
+        }
+    }
+    if context.has_errors() {
+        return Err(format!("{path} failed validation").into());
+    }
+
+    Ok(())
+}
+
+fn print_symmetry(path: &str) -> Result<(), Box<dyn Error>> {
+    use lp_parser_rs::analysis::symmetry::find_symmetry;
+
+    let input = parse_file(&PathBuf::from(path))?;
+    let problem = LpProblem::parse(&input).unwrap();
+
+    let symmetry = find_symmetry(&problem);
+    if symmetry.is_empty() && !symmetry.incomplete {
+        println!("No symmetry found");
+    } else {
+        print!("{symmetry}");
+    }
 
-    // Variables removed
-    difference.variables.removed.iter().for_each(|k| {
-        println!("Variable {k} removed");
-    });
+    Ok(())
+}
+
+fn print_network(path: &str) -> Result<(), Box<dyn Error>> {
+    use lp_parser_rs::analysis::network::find_network;
+
+    let input = parse_file(&PathBuf::from(path))?;
+    let problem = LpProblem::parse(&input).unwrap();
+
+    let network = find_network(&problem);
+    if network.is_empty() {
+        println!("No network structure found");
+    } else {
+        print!("{network}");
+    }
+
+    Ok(())
+}
+
+fn query_lp_file(path: &str, query: &str) -> Result<(), Box<dyn Error>> {
+    let input = parse_file(&PathBuf::from(path))?;
+    let problem = LpProblem::parse(&input).unwrap();
 
-    // Constraints altered
-    difference.constraints.altered.iter().for_each(|(k, v)| {
-        if let Some(c_name) = problem2.constraints.get(k) {
-            println!("Constraint {k} changed from {v:?} to {c_name:?}");
+    println!("{}", problem.query(query)?);
+
+    Ok(())
+}
+
+#[cfg(feature = "solver-pure")]
+/// Prints the status, objective value, best bound and relative gap of `solution`.
+fn print_solution_summary(solution: &lp_parser_rs::model::Solution) {
+    println!("Status: {}", solution.status);
+    if let Some(objective) = solution.objective {
+        println!("Objective value: {objective}");
+    }
+    if let Some(best_bound) = solution.best_bound {
+        println!("Best bound: {best_bound}");
+    }
+    if let Some(gap) = solution.relative_gap() {
+        println!("Relative gap: {:.4}%", gap * 100.0);
+    }
+}
+
+#[cfg(feature = "solver-pure")]
+fn solve_lp_files(paths: &[String], options: &lp_parser_rs::compat::SolveOptions) -> Result<(), Box<dyn Error>> {
+    use lp_parser_rs::compat::{pure::capability_warnings, solve_many, SolverBackend};
+
+    let inputs = paths.iter().map(|path| parse_file(&PathBuf::from(path))).collect::<Result<Vec<_>, _>>()?;
+    let problems: Vec<LpProblem<'_>> = inputs.iter().map(|input| LpProblem::parse(input).unwrap()).collect();
+
+    let results = solve_many(&problems, SolverBackend::default(), options);
+    for ((path, problem), result) in paths.iter().zip(&problems).zip(results) {
+        if paths.len() > 1 {
+            println!("== {path}");
+        }
+        for warning in capability_warnings(problem) {
+            eprintln!("Warning: {warning}");
         }
-    });
 
-    // Constraints removed
-    difference.constraints.removed.iter().for_each(|k| {
-        println!("Constraint {k} removed");
-    });
+        let solution = result?;
+        print_solution_summary(&solution);
+
+        let mut values: Vec<_> = solution.values.into_iter().collect();
+        values.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        for (name, value) in values {
+            println!("{name} = {value}");
+        }
+    }
 
     Ok(())
 }
 
-/// Parses and prints details of a single LP file or compares two LP files if the "diff" feature is enabled.
+#[cfg(all(feature = "solver-pure", feature = "json"))]
+#[derive(serde::Serialize)]
+/// One side of the `compare-solve --json` report.
+struct SolveSummary<'a> {
+    path: &'a str,
+    status: lp_parser_rs::model::SolutionStatus,
+    objective: Option<f64>,
+    best_bound: Option<f64>,
+    relative_gap: Option<f64>,
+}
+
+#[cfg(all(feature = "solver-pure", feature = "json"))]
+impl<'a> SolveSummary<'a> {
+    fn new(path: &'a str, solution: &lp_parser_rs::model::Solution) -> Self {
+        Self {
+            path,
+            status: solution.status,
+            objective: solution.objective,
+            best_bound: solution.best_bound,
+            relative_gap: solution.relative_gap(),
+        }
+    }
+}
+
+#[cfg(all(feature = "solver-pure", feature = "json"))]
+#[derive(serde::Serialize)]
+/// The report printed by `compare-solve --json`.
+struct CompareSolveReport<'a> {
+    left: SolveSummary<'a>,
+    right: SolveSummary<'a>,
+    tolerance: f64,
+    differences: &'a [lp_parser_rs::model::SolutionDifference],
+}
+
+#[cfg(feature = "solver-pure")]
+fn compare_solve_lp_files(
+    p1: &str,
+    p2: &str,
+    backend: lp_parser_rs::compat::SolverBackend,
+    tolerance: f64,
+    json: bool,
+) -> Result<(), Box<dyn Error>> {
+    use lp_parser_rs::compat::{solve_many, SolveOptions};
+
+    let (input1, input2) = (parse_file(&PathBuf::from(p1))?, parse_file(&PathBuf::from(p2))?);
+    let problems = [LpProblem::parse(&input1).unwrap(), LpProblem::parse(&input2).unwrap()];
+
+    let mut results = solve_many(&problems, backend, &SolveOptions::default()).into_iter();
+    let (solution1, solution2) = (results.next().ok_or("missing solution")??, results.next().ok_or("missing solution")??);
+    let differences = problems[0].compare_solutions(&solution1, &problems[1], &solution2, tolerance);
+
+    if json {
+        #[cfg(feature = "json")]
+        {
+            let report = CompareSolveReport {
+                left: SolveSummary::new(p1, &solution1),
+                right: SolveSummary::new(p2, &solution2),
+                tolerance,
+                differences: &differences,
+            };
+            println!("{}", serde_json::to_string(&report)?);
+        }
+    } else {
+        for (path, solution) in [(p1, &solution1), (p2, &solution2)] {
+            println!("== {path}");
+            print_solution_summary(solution);
+        }
+        println!("== differences");
+        if differences.is_empty() {
+            println!("No differences found within tolerance {tolerance}");
+        }
+        for difference in &differences {
+            println!("{difference}");
+        }
+    }
+
+    if differences.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("{} solution differences beyond tolerance {tolerance}", differences.len()).into())
+    }
+}
+
+/// Parses and prints details of a single LP file or compares two LP files.
 ///
 /// # Arguments
 ///
@@ -76,18 +267,129 @@ fn compare_lp_files(p1: &str, p2: &str) -> Result<(), Box<dyn Error>> {
 ///
 /// # Features
 ///
-/// * If the "diff" feature is enabled, it can compare two LP files and print the differences in variables and constraints.
+/// * `lp_parser --tighten-bounds <PATH_TO_FILE>` also propagates the constraints and prints the variable bounds they tighten.
+/// * Given two paths, it compares the LP files and prints the coefficient-level differences in objectives, constraints and variables.
+/// * `lp_parser symmetry <PATH_TO_FILE>` prints the groups of interchangeable variables and constraints.
+/// * `lp_parser network <PATH_TO_FILE>` prints the flow network found in the constraints, if any.
+/// * `lp_parser query <PATH_TO_FILE> <QUERY>` prints the results of a selector such as `constraints[rhs>100].name` as JSON.
+/// * `lp_parser lint [--fix] [--dialect NAME] [--strict] [--disable RULE]... [--param RULE.NAME=VALUE]... <PATH_TO_FILE>` runs the lint rules and prints their findings.
+///   With `--fix`, the available fixes are written back to the file, keeping its comments and layout, and only the remaining findings are printed.
+///   `--dialect` selects the dialect the file is checked against, `cplex` by default, and `--strict` reports warnings as errors.
+/// * If the "solver-pure" feature is enabled, `lp_parser solve [--timeout SECONDS] [--sos-big-m M] [--semi-big-m M] <PATH_TO_FILE>...` solves one or more problems with a pure-Rust solver.
+///   `--sos-big-m` and `--semi-big-m` reformulate SOS constraints and semi-continuous variables with binaries instead of relaxing them.
+/// * If the "solver-pure" feature is enabled, `lp_parser compare-solve [--solver NAME] [--tol TOLERANCE] [--json] <PATH_A> <PATH_B>` solves both problems
+///   and prints the status, objective, best bound and gap of each, then the objective values, variable values and constraint activities that differ,
+///   exiting with an error if any do.
+///   `--json`, which needs the "json" feature, prints both solutions' status, objective, best bound and gap alongside the differences.
 ///
 fn main() -> Result<(), Box<dyn Error>> {
     let mut args = env::args();
     args.next();
     let path = args.next().ok_or("Usage: nom_lp_parser <PATH_TO_FILE>")?;
 
+    if path == "symmetry" {
+        return print_symmetry(&args.next().ok_or("Usage: lp_parser symmetry <PATH_TO_FILE>")?);
+    }
+
+    if path == "network" {
+        return print_network(&args.next().ok_or("Usage: lp_parser network <PATH_TO_FILE>")?);
+    }
+
+    if path == "query" {
+        let usage = "Usage: lp_parser query <PATH_TO_FILE> <QUERY>";
+        let file = args.next().ok_or(usage)?;
+        return query_lp_file(&file, &args.next().ok_or(usage)?);
+    }
+
+    if path == "lint" {
+        use lp_parser_rs::validation::{Strictness, ValidationContext};
+
+        let usage =
+            "Usage: lp_parser lint [--fix] [--dialect NAME] [--strict] [--disable RULE]... [--param RULE.NAME=VALUE]... <PATH_TO_FILE>";
+        let mut context = ValidationContext::new();
+        let (mut file, mut fix) = (None, false);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--fix" => fix = true,
+                "--dialect" => context = context.with_dialect(args.next().ok_or(usage)?.parse()?),
+                "--strict" => context = context.with_strictness(Strictness::Strict),
+                "--disable" => context.rule_config_mut(&args.next().ok_or(usage)?).enabled = false,
+                "--param" => {
+                    let param = args.next().ok_or(usage)?;
+                    let (key, value) = param.split_once('=').ok_or(usage)?;
+                    let (rule, name) = key.split_once('.').ok_or(usage)?;
+                    context.rule_config_mut(rule).params.insert(name.to_string(), value.to_string());
+                }
+                flag if flag.starts_with("--") => return Err(format!("unknown option {flag}. {usage}").into()),
+                _ => file = Some(arg),
+            }
+        }
+        let file = file.ok_or(usage)?;
+        return lint_lp_file(&file, &mut context, fix);
+    }
+
+    #[cfg(feature = "solver-pure")]
+    if path == "solve" {
+        use lp_parser_rs::compat::{
+            good_lp::{GoodLpOptions, SemiContinuousStrategy, SosStrategy},
+            CancellationToken, SolveOptions,
+        };
+
+        let mut paths: Vec<String> = args.collect();
+        let mut options = SolveOptions::default();
+        if let Some(index) = paths.iter().position(|arg| arg == "--timeout") {
+            let seconds: f64 = paths.get(index + 1).ok_or("--timeout requires a value in seconds")?.parse()?;
+            if !(0.0..=f64::from(u32::MAX)).contains(&seconds) {
+                return Err(format!("--timeout requires a non-negative number of seconds, got {seconds}").into());
+            }
+            options = options.with_cancellation(CancellationToken::new().with_timeout(std::time::Duration::from_secs_f64(seconds)));
+            paths.drain(index..=index + 1);
+        }
+        let mut conversion = GoodLpOptions::default();
+        if let Some(index) = paths.iter().position(|arg| arg == "--sos-big-m") {
+            let big_m: f64 = paths.get(index + 1).ok_or("--sos-big-m requires a value")?.parse()?;
+            conversion = conversion.with_sos(SosStrategy::BigM(big_m));
+            paths.drain(index..=index + 1);
+        }
+        if let Some(index) = paths.iter().position(|arg| arg == "--semi-big-m") {
+            let big_m: f64 = paths.get(index + 1).ok_or("--semi-big-m requires a value")?.parse()?;
+            conversion = conversion.with_semi_continuous(SemiContinuousStrategy::BigM(big_m));
+            paths.drain(index..=index + 1);
+        }
+        options = options.with_conversion(conversion);
+        if paths.is_empty() {
+            return Err("Usage: lp_parser solve [--timeout SECONDS] [--sos-big-m M] [--semi-big-m M] <PATH_TO_FILE>...".into());
+        }
+        return solve_lp_files(&paths, &options);
+    }
+
+    #[cfg(feature = "solver-pure")]
+    if path == "compare-solve" {
+        use lp_parser_rs::compat::SolverBackend;
+
+        let usage = "Usage: lp_parser compare-solve [--solver NAME] [--tol TOLERANCE] [--json] <PATH_A> <PATH_B>";
+        let (mut backend, mut tolerance, mut json, mut paths) = (SolverBackend::default(), 1e-6, false, Vec::new());
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--solver" => backend = args.next().ok_or(usage)?.parse()?,
+                "--tol" => tolerance = args.next().ok_or(usage)?.parse()?,
+                "--json" if cfg!(feature = "json") => json = true,
+                "--json" => return Err("--json requires the json feature".into()),
+                _ => paths.push(arg),
+            }
+        }
+        if paths.len() != 2 {
+            return Err(usage.into());
+        }
+        return compare_solve_lp_files(&paths[0], &paths[1], backend, tolerance, json);
+    }
+
+    if path == "--tighten-bounds" {
+        return dissemble_single_file(&args.next().ok_or("Usage: lp_parser --tighten-bounds <PATH_TO_FILE>")?, true);
+    }
+
     match (path, args.next()) {
-        (p1, None) => dissemble_single_file(&p1),
-        #[cfg(feature = "diff")]
+        (p1, None) => dissemble_single_file(&p1, false),
         (p1, Some(p2)) => compare_lp_files(&p1, &p2),
-        #[cfg(not(feature = "diff"))]
-        (_, Some(_)) => Err("Diff feature not enabled".into()),
     }
 }
@@ -0,0 +1,419 @@
+//! Conversion of an `LpProblem` into a `good_lp` problem definition.
+//!
+//! The LP format treats variables without a declaration in the `Bounds`
+//! section as non-negative. The parser records those variables as
+//! `VariableType::Free`, so the bridge applies the LP default bounds
+//! `[0, +inf)` to them, and only leaves variables declared `x free`
+//! unbounded, following `Variable::effective_bounds`.
+//!
+//! `good_lp` models a single objective and has no native support for
+//! semi-continuous variables or SOS constraints. By default these are
+//! relaxed and a warning is logged; [`GoodLpOptions`] selects a faithful
+//! big-M reformulation using binary variables instead, for SOS1 and SOS2
+//! constraints and for semi-continuous variables, whose declared bounds
+//! give the range of the non-zero values.
+//!
+
+use std::collections::HashMap;
+
+use good_lp::{
+    constraint, solvers::ObjectiveDirection, variable, Constraint as GoodLpConstraint, Expression, IntoAffineExpression as _,
+    ProblemVariables, Solution, Solver, SolverModel, Variable as GoodLpVariable, VariableDefinition,
+};
+
+use crate::{
+    model::{Coefficient, ComparisonOp, Constraint, Objective, SOSType, Sense, Variable, VariableType},
+    problem::LpProblem,
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+/// How SOS constraints are translated into `good_lp`.
+pub enum SosStrategy {
+    #[default]
+    /// Drop the constraint, relaxing the problem.
+    Drop,
+    /// Encode the constraint with one binary per member (SOS1) or segment (SOS2).
+    ///
+    /// Members without a finite bound use the given big-M value in its place.
+    BigM(f64),
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+/// How semi-continuous variables are translated into `good_lp`.
+pub enum SemiContinuousStrategy {
+    #[default]
+    /// Relax the variable to a continuous variable between zero and its bounds.
+    Relax,
+    /// Encode `lower * z <= x <= upper * z` with a binary `z`, using the declared bounds.
+    ///
+    /// Variables without a finite upper bound use the given big-M value in its place.
+    BigM(f64),
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+/// Options controlling how an `LpProblem` is translated into `good_lp`.
+pub struct GoodLpOptions {
+    /// The strategy used for SOS constraints.
+    pub sos: SosStrategy,
+    /// The strategy used for semi-continuous variables.
+    pub semi_continuous: SemiContinuousStrategy,
+}
+
+impl GoodLpOptions {
+    #[must_use]
+    #[inline]
+    /// Builder method for setting the SOS strategy.
+    pub const fn with_sos(self, sos: SosStrategy) -> Self {
+        Self { sos, ..self }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Builder method for setting the semi-continuous strategy.
+    pub const fn with_semi_continuous(self, semi_continuous: SemiContinuousStrategy) -> Self {
+        Self { semi_continuous, ..self }
+    }
+}
+
+/// An `LpProblem` translated into `good_lp` types, ready to be handed to a solver.
+pub struct GoodLpProblem<'a> {
+    /// The variable definitions, including bounds and integrality.
+    pub variables: ProblemVariables,
+    /// The objective expression.
+    pub objective: Expression,
+    /// Whether the objective is minimised or maximised.
+    pub direction: ObjectiveDirection,
+    /// The linear constraints of the problem.
+    pub constraints: Vec<GoodLpConstraint>,
+    /// A lookup from the LP variable name to the `good_lp` variable.
+    pub variable_map: HashMap<&'a str, GoodLpVariable>,
+}
+
+impl<'a> GoodLpProblem<'a> {
+    #[must_use]
+    #[inline]
+    /// Returns the `good_lp` variable associated with the LP variable `name`.
+    pub fn variable(&self, name: &str) -> Option<GoodLpVariable> {
+        self.variable_map.get(name).copied()
+    }
+
+    #[inline]
+    /// Consumes `Self`, creating a solver model containing the objective and all constraints.
+    pub fn using<S: Solver>(self, solver: S) -> S::Model {
+        let mut model = self.variables.optimise(self.direction, self.objective).using(solver);
+        for constraint in self.constraints {
+            model.add_constraint(constraint);
+        }
+        model
+    }
+
+    #[must_use]
+    #[inline]
+    /// Maps the values of a `good_lp` solution back onto the LP variable names.
+    pub fn values<S: Solution>(&self, solution: &S) -> HashMap<&'a str, f64> {
+        self.variable_map.iter().map(|(&name, &var)| (name, solution.value(var))).collect()
+    }
+}
+
+#[inline]
+/// Builds the `good_lp` definition of a variable from its type and effective bounds.
+fn variable_definition(var: &Variable<'_>, semi_continuous: SemiContinuousStrategy) -> VariableDefinition {
+    let (name, var_type) = (var.name, &var.var_type);
+    let relaxed = match var_type {
+        VariableType::SemiContinuous => semi_continuous == SemiContinuousStrategy::Relax,
+        VariableType::SOS => true,
+        _ => false,
+    };
+    if relaxed {
+        log::warn!("Variable `{name}` of type {var_type} is not supported by good_lp, relaxing to a continuous variable");
+    }
+
+    let (lower, upper) = var.effective_bounds();
+    // The relaxation of a semi-continuous variable must still admit zero
+    let lower = if *var_type == VariableType::SemiContinuous { lower.min(0.0) } else { lower };
+    let definition = variable().name(name).min(lower).max(upper);
+    if var_type.is_integer() {
+        definition.integer()
+    } else {
+        definition
+    }
+}
+
+#[inline]
+fn build_expression(coefficients: &[Coefficient<'_>], variable_map: &HashMap<&str, GoodLpVariable>) -> Expression {
+    let mut expression = Expression::with_capacity(coefficients.len());
+    for coeff in coefficients {
+        if let Some(&var) = variable_map.get(coeff.var_name) {
+            expression.add_mul(coeff.coefficient, var);
+        }
+    }
+    expression
+}
+
+#[inline]
+/// Selects the objective to optimise, falling back to the first by name when there are several.
+fn select_objective<'b, 'a>(problem: &'b LpProblem<'a>) -> Option<&'b Objective<'a>> {
+    if problem.objectives.len() > 1 {
+        log::warn!("good_lp supports a single objective, only the first objective (by name) will be used");
+    }
+    problem.objectives.values().min_by(|a, b| a.name.cmp(&b.name))
+}
+
+/// Appends the binary encoding of an SOS constraint over `members`, ordered by weight.
+fn encode_sos(
+    name: &str,
+    sos_type: SOSType,
+    members: &[(GoodLpVariable, (f64, f64))],
+    big_m: f64,
+    variables: &mut ProblemVariables,
+    constraints: &mut Vec<GoodLpConstraint>,
+) {
+    // SOS1 uses one binary per member, SOS2 one binary per pair of adjacent members
+    let indicator_count = match sos_type {
+        SOSType::S1 => members.len(),
+        SOSType::S2 => members.len().saturating_sub(1),
+    };
+    if indicator_count == 0 {
+        return;
+    }
+    let indicators: Vec<GoodLpVariable> =
+        (0..indicator_count).map(|i| variables.add(variable().name(format!("{name}_sos_z{i}")).binary())).collect();
+
+    for (i, &(var, (lower, upper))) in members.iter().enumerate() {
+        let active: Vec<GoodLpVariable> = match sos_type {
+            SOSType::S1 => vec![indicators[i]],
+            SOSType::S2 => [i.checked_sub(1), (i < indicator_count).then_some(i)].into_iter().flatten().map(|j| indicators[j]).collect(),
+        };
+        let upper = if upper.is_finite() { upper } else { big_m };
+        let lower = if lower.is_finite() { lower } else { -big_m };
+
+        let mut ub_expression = Expression::from(var);
+        let mut lb_expression = Expression::from(var);
+        for &z in &active {
+            ub_expression.add_mul(-upper, z);
+            lb_expression.add_mul(-lower, z);
+        }
+        constraints.push(constraint::leq(ub_expression, 0.0).set_name(format!("{name}_sos_ub{i}")));
+        if lower != 0.0 {
+            constraints.push(constraint::geq(lb_expression, 0.0).set_name(format!("{name}_sos_lb{i}")));
+        }
+    }
+
+    let mut cardinality = Expression::with_capacity(indicators.len());
+    for z in indicators {
+        cardinality.add_mul(1.0, z);
+    }
+    constraints.push(constraint::leq(cardinality, 1.0).set_name(format!("{name}_sos_card")));
+}
+
+/// Appends the binary encoding `lower * z <= x <= upper * z` of a semi-continuous variable.
+fn encode_semi_continuous(
+    name: &str,
+    var: GoodLpVariable,
+    (lower, upper): (f64, f64),
+    big_m: f64,
+    variables: &mut ProblemVariables,
+    constraints: &mut Vec<GoodLpConstraint>,
+) {
+    let indicator = variables.add(variable().name(format!("{name}_semi_z")).binary());
+    let upper = if upper.is_finite() { upper } else { big_m };
+
+    let mut ub_expression = Expression::from(var);
+    ub_expression.add_mul(-upper, indicator);
+    constraints.push(constraint::leq(ub_expression, 0.0).set_name(format!("{name}_semi_ub")));
+    if lower > 0.0 {
+        let mut lb_expression = Expression::from(var);
+        lb_expression.add_mul(-lower, indicator);
+        constraints.push(constraint::geq(lb_expression, 0.0).set_name(format!("{name}_semi_lb")));
+    }
+}
+
+impl<'a> GoodLpProblem<'a> {
+    #[must_use]
+    /// Translates `problem` into `good_lp` types using `options`.
+    pub fn new(problem: &LpProblem<'a>, options: &GoodLpOptions) -> Self {
+        let mut variables = ProblemVariables::new();
+
+        let mut names: Vec<&'a str> = problem.variables.keys().copied().collect();
+        names.sort_unstable();
+        let variable_map: HashMap<&'a str, GoodLpVariable> = names
+            .iter()
+            .map(|&name| {
+                let definition = variable_definition(&problem.variables[name], options.semi_continuous);
+                (name, variables.add(definition))
+            })
+            .collect();
+
+        let objective = select_objective(problem)
+            .map_or_else(Expression::default, |objective| build_expression(&objective.coefficients, &variable_map));
+        let direction = match problem.sense {
+            Sense::Minimize => ObjectiveDirection::Minimisation,
+            Sense::Maximize => ObjectiveDirection::Maximisation,
+        };
+
+        let mut sorted_constraints: Vec<&Constraint<'a>> = problem.constraints.values().collect();
+        sorted_constraints.sort_unstable_by(|a, b| a.name().cmp(&b.name()));
+
+        let mut constraints = Vec::with_capacity(problem.constraints.len());
+        if let SemiContinuousStrategy::BigM(big_m) = options.semi_continuous {
+            for name in names.iter().filter(|&&name| problem.variables[name].var_type == VariableType::SemiContinuous) {
+                let bounds = problem.variables[name].effective_bounds();
+                encode_semi_continuous(name, variable_map[name], bounds, big_m, &mut variables, &mut constraints);
+            }
+        }
+        for constraint in sorted_constraints {
+            match constraint {
+                Constraint::Standard { name, coefficients, operator, rhs, .. } => {
+                    let expression = build_expression(coefficients, &variable_map);
+                    let converted = match operator {
+                        ComparisonOp::LT | ComparisonOp::LTE => constraint::leq(expression, *rhs),
+                        ComparisonOp::GT | ComparisonOp::GTE => constraint::geq(expression, *rhs),
+                        ComparisonOp::EQ => constraint::eq(expression, *rhs),
+                    };
+                    constraints.push(converted.set_name(name.to_string()));
+                }
+                Constraint::SOS { name, sos_type, weights, .. } => match options.sos {
+                    SosStrategy::Drop => {
+                        log::warn!("SOS constraint `{name}` is not supported by good_lp and has been dropped");
+                    }
+                    SosStrategy::BigM(big_m) => {
+                        let mut ordered: Vec<&Coefficient<'a>> = weights.iter().collect();
+                        ordered.sort_by(|a, b| a.coefficient.total_cmp(&b.coefficient));
+                        let members: Vec<(GoodLpVariable, (f64, f64))> = ordered
+                            .into_iter()
+                            .filter_map(|w| Some((*variable_map.get(w.var_name)?, problem.variables[w.var_name].effective_bounds())))
+                            .collect();
+                        encode_sos(name, *sos_type, &members, big_m, &mut variables, &mut constraints);
+                    }
+                },
+            }
+        }
+
+        Self { variables, objective, direction, constraints, variable_map }
+    }
+}
+
+impl<'a> From<&LpProblem<'a>> for GoodLpProblem<'a> {
+    #[inline]
+    fn from(problem: &LpProblem<'a>) -> Self {
+        Self::new(problem, &GoodLpOptions::default())
+    }
+}
+
+impl<'a> LpProblem<'a> {
+    #[must_use]
+    #[inline]
+    /// Converts `Self` into a `good_lp` problem definition.
+    pub fn to_good_lp(&self) -> GoodLpProblem<'a> {
+        GoodLpProblem::from(self)
+    }
+
+    #[must_use]
+    #[inline]
+    /// Converts `Self` into a `good_lp` problem definition using `options`.
+    pub fn to_good_lp_with_options(&self, options: &GoodLpOptions) -> GoodLpProblem<'a> {
+        GoodLpProblem::new(self, options)
+    }
+}
+
+#[must_use]
+#[inline]
+/// Recovers the LP coefficients of a `good_lp` expression using the variable names of `problem`.
+///
+/// Terms whose variable is not part of `problem` are skipped. The constant term of the
+/// expression is returned separately as it has no representation in an LP objective.
+pub fn coefficients_from_expression<'a>(problem: &GoodLpProblem<'a>, expression: &Expression) -> (Vec<Coefficient<'a>>, f64) {
+    let names: HashMap<GoodLpVariable, &'a str> = problem.variable_map.iter().map(|(&name, &var)| (var, name)).collect();
+    let mut coefficients: Vec<Coefficient<'a>> = expression
+        .linear_coefficients()
+        .filter_map(|(var, coefficient)| names.get(&var).map(|&var_name| Coefficient { var_name, coefficient }))
+        .collect();
+    coefficients.sort_unstable_by(|a, b| a.var_name.cmp(b.var_name));
+    (coefficients, expression.constant())
+}
+
+#[cfg(test)]
+mod test {
+    use good_lp::solvers::ObjectiveDirection;
+
+    use crate::{
+        compat::good_lp::{coefficients_from_expression, GoodLpOptions, SemiContinuousStrategy, SosStrategy},
+        problem::LpProblem,
+    };
+
+    const INPUT: &str = "Maximize
+ obj: 3 x + 2 y
+subject to
+c1: x + y <= 4
+c2: x + 3 y <= 6
+bounds
+x <= 3
+End";
+
+    #[test]
+    fn test_to_good_lp() {
+        let problem = LpProblem::parse(INPUT).expect("test case not to fail");
+        let converted = problem.to_good_lp();
+
+        assert!(converted.direction == ObjectiveDirection::Maximisation);
+        assert_eq!(converted.variable_map.len(), 2);
+        assert_eq!(converted.constraints.len(), 2);
+        assert!(converted.variable("x").is_some());
+        assert!(converted.variable("z").is_none());
+
+        let (coefficients, constant) = coefficients_from_expression(&converted, &converted.objective);
+        assert_eq!(constant, 0.0);
+        assert_eq!(coefficients.len(), 2);
+        assert_eq!(coefficients[0].var_name, "x");
+        assert_eq!(coefficients[0].coefficient, 3.0);
+    }
+
+    #[test]
+    fn test_sos_big_m() {
+        let input = "Maximize
+ obj: x1 + 2 x2 + 3 x3
+subject to
+c1: x1 + x2 + x3 <= 10
+c2: x1 <= 4
+c3: x2 <= 4
+c4: x3 <= 4
+Integers
+Generals
+Binary
+Semi-Continuous
+SOS
+s1: S1:: x1:1 x2:2 x3:3
+End";
+        let problem = LpProblem::parse(input).expect("test case not to fail");
+        assert_eq!(problem.to_good_lp().constraints.len(), 4);
+
+        let converted = problem.to_good_lp_with_options(&GoodLpOptions::default().with_sos(SosStrategy::BigM(100.0)));
+        // One upper bound link per member plus the cardinality row
+        assert_eq!(converted.constraints.len(), 4 + 3 + 1);
+        assert_eq!(converted.variables.len(), problem.variable_count() + 3);
+    }
+
+    #[test]
+    fn test_semi_continuous_big_m() {
+        let input = "Minimize
+ obj: s + t
+subject to
+c1: s + t >= 1
+bounds
+2 <= s <= 5
+Integers
+Generals
+Binary
+Semi-Continuous
+s t
+End";
+        let problem = LpProblem::parse(input).expect("test case not to fail");
+        assert_eq!(problem.to_good_lp().constraints.len(), 1);
+
+        let options = GoodLpOptions::default().with_semi_continuous(SemiContinuousStrategy::BigM(100.0));
+        let converted = problem.to_good_lp_with_options(&options);
+        // Both variables link to an indicator from above, only `s` has a positive lower bound
+        assert_eq!(converted.constraints.len(), 1 + 2 + 1);
+        assert_eq!(converted.variables.len(), problem.variable_count() + 2);
+    }
+}
@@ -0,0 +1,261 @@
+//! Bridges between `LpProblem` and third-party optimisation crates.
+//!
+//! Each bridge lives in its own sub module and is gated behind a
+//! feature of the same name, so the core parser does not pull in any
+//! solver dependencies unless they are requested.
+//!
+//! - `good_lp`: Conversion into a [`good_lp`](https://docs.rs/good_lp) problem definition
+//! - `pure`: Solving with the pure-Rust `microlp` solver (`solver-pure` feature)
+//!
+//! With a solver backend enabled, [`solve_many`] solves a batch of problems
+//! (for example perturbed copies of a base model) across threads. A
+//! [`CancellationToken`] aborts the batch early: solves that have not started
+//! are skipped, and solves in flight are abandoned. `microlp` cannot be
+//! interrupted, so an abandoned solve finishes on a detached thread.
+//!
+
+#[cfg(feature = "good_lp")]
+pub mod good_lp;
+#[cfg(feature = "solver-pure")]
+pub mod pure;
+
+#[cfg(feature = "solver-pure")]
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "solver-pure")]
+use ::good_lp::ResolutionError;
+
+#[cfg(feature = "solver-pure")]
+use crate::{compat::good_lp::GoodLpOptions, model::Solution, problem::LpProblem};
+
+#[cfg(feature = "solver-pure")]
+#[derive(Debug, Clone, Default)]
+/// A shared flag, with an optional deadline, used to abort solves.
+///
+/// Clones share the same flag, so a token can be handed to a solve while
+/// another thread (for example a signal handler) calls [`CancellationToken::cancel`].
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+#[cfg(feature = "solver-pure")]
+impl CancellationToken {
+    #[must_use]
+    #[inline]
+    /// Initialise a new `CancellationToken` without a deadline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    #[inline]
+    /// Builder method for cancelling automatically once `deadline` has passed.
+    pub fn with_deadline(self, deadline: Instant) -> Self {
+        Self { deadline: Some(deadline), ..self }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Builder method for cancelling automatically once `timeout` has elapsed from now.
+    ///
+    /// A timeout too large to be represented leaves the token without a deadline.
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.with_deadline(deadline),
+            None => self,
+        }
+    }
+
+    #[inline]
+    /// Requests cancellation of every solve sharing this token.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    #[inline]
+    /// Returns `true` if cancellation was requested or the deadline has passed.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed) || self.deadline.map_or(false, |deadline| Instant::now() >= deadline)
+    }
+}
+
+#[cfg(feature = "solver-pure")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// The solver used to solve a problem.
+pub enum SolverBackend {
+    #[default]
+    /// The pure-Rust `microlp` solver.
+    Microlp,
+}
+
+#[cfg(feature = "solver-pure")]
+impl SolverBackend {
+    #[inline]
+    /// Solves `problem` with the selected backend.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ResolutionError` if the backend fails to solve the problem.
+    pub fn solve(&self, problem: &LpProblem<'_>) -> Result<Solution, ResolutionError> {
+        match self {
+            Self::Microlp => pure::solve(problem),
+        }
+    }
+
+    #[inline]
+    /// Solves `problem` using `options`. If the cancellation token fires before or
+    /// during the solve, the returned `Solution` has the status `SolutionStatus::Interrupted`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ResolutionError` if the backend fails to solve the problem.
+    pub fn solve_with_options(&self, problem: &LpProblem<'_>, options: &SolveOptions) -> Result<Solution, ResolutionError> {
+        match self {
+            Self::Microlp => pure::solve_cancellable(problem, &options.conversion, &options.cancellation),
+        }
+    }
+}
+
+#[cfg(feature = "solver-pure")]
+impl std::str::FromStr for SolverBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "microlp" | "minilp" | "pure" => Ok(Self::Microlp),
+            _ => Err(format!("unsupported solver {s}, only microlp is available")),
+        }
+    }
+}
+
+#[cfg(feature = "solver-pure")]
+#[derive(Debug, Default, Clone)]
+/// Options controlling how a batch of problems is solved.
+pub struct SolveOptions {
+    /// The number of worker threads, defaults to the available parallelism.
+    pub threads: Option<usize>,
+    /// A token used to abort the unfinished solves of the batch.
+    pub cancellation: CancellationToken,
+    /// Options controlling how each problem is translated for the backend.
+    pub conversion: GoodLpOptions,
+}
+
+#[cfg(feature = "solver-pure")]
+impl SolveOptions {
+    #[must_use]
+    #[inline]
+    /// Builder method for setting the number of worker threads.
+    pub fn with_threads(self, threads: usize) -> Self {
+        Self { threads: Some(threads), ..self }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Builder method for setting the cancellation token.
+    pub fn with_cancellation(self, cancellation: CancellationToken) -> Self {
+        Self { cancellation, ..self }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Builder method for setting the translation options.
+    pub fn with_conversion(self, conversion: GoodLpOptions) -> Self {
+        Self { conversion, ..self }
+    }
+}
+
+#[cfg(feature = "solver-pure")]
+#[must_use]
+/// Solves each problem in `problems` with `backend`, distributing the work across threads.
+///
+/// The results are returned in the same order as `problems`. Workers pull the next
+/// unsolved problem as they finish, so scenarios of uneven difficulty are balanced.
+/// Problems that had not finished when the cancellation token fired are reported
+/// with the status `SolutionStatus::Interrupted`.
+pub fn solve_many(problems: &[LpProblem<'_>], backend: SolverBackend, options: &SolveOptions) -> Vec<Result<Solution, ResolutionError>> {
+    let threads = options
+        .threads
+        .or_else(|| thread::available_parallelism().ok().map(NonZeroUsize::get))
+        .unwrap_or(1)
+        .clamp(1, problems.len().max(1));
+    let next = AtomicUsize::new(0);
+
+    let mut results: Vec<(usize, Result<Solution, ResolutionError>)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut solved = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        match problems.get(index) {
+                            Some(problem) => solved.push((index, backend.solve_with_options(problem, options))),
+                            None => break,
+                        }
+                    }
+                    solved
+                })
+            })
+            .collect();
+        workers.into_iter().flat_map(|worker| worker.join().expect("solver thread panicked")).collect()
+    });
+
+    results.sort_unstable_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(all(test, feature = "solver-pure"))]
+mod test {
+    use float_eq::assert_float_eq;
+
+    use std::time::Duration;
+
+    use crate::{
+        compat::{solve_many, CancellationToken, SolveOptions, SolverBackend},
+        model::SolutionStatus,
+        problem::LpProblem,
+    };
+
+    #[test]
+    fn test_solve_many() {
+        let inputs: Vec<String> = (1..=6).map(|cap| format!("Maximize\n obj: 3 x + 2 y\nsubject to\nc1: x + y <= {cap}\nEnd")).collect();
+        let problems: Vec<LpProblem<'_>> = inputs.iter().map(|input| LpProblem::parse(input).expect("test case not to fail")).collect();
+
+        let results = solve_many(&problems, SolverBackend::Microlp, &SolveOptions::default().with_threads(3));
+        assert_eq!(results.len(), 6);
+        for (cap, result) in (1..=6).zip(results) {
+            let solution = result.expect("test case not to fail");
+            assert_float_eq!(solution.objective.expect("test case not to fail"), 3.0 * f64::from(cap), abs <= 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_solve_many_cancelled() {
+        let problems = vec![LpProblem::parse("Minimize\n obj: x\nsubject to\nc1: x >= 1\nEnd").expect("test case not to fail")];
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let results = solve_many(&problems, SolverBackend::Microlp, &SolveOptions::default().with_cancellation(token));
+        assert_eq!(results[0].as_ref().expect("test case not to fail").status, SolutionStatus::Interrupted);
+
+        let token = CancellationToken::new().with_timeout(Duration::ZERO);
+        assert!(token.is_cancelled());
+    }
+
+    #[test]
+    fn test_backend_from_str() {
+        assert_eq!("MicroLP".parse::<SolverBackend>(), Ok(SolverBackend::Microlp));
+        assert_eq!("minilp".parse::<SolverBackend>(), Ok(SolverBackend::Microlp));
+        assert_eq!("pure".parse::<SolverBackend>(), Ok(SolverBackend::Microlp));
+        assert!("cbc".parse::<SolverBackend>().is_err());
+    }
+}
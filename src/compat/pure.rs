@@ -0,0 +1,293 @@
+//! Pure-Rust solving of an `LpProblem` using `microlp` via the `good_lp` bridge.
+//!
+//! This backend requires no external solver binaries, which makes it a
+//! useful fallback where CBC or GLPK cannot be installed. `microlp` is an
+//! LP solver: integer variables are only handled through Gomory cuts and
+//! may fail to solve, while semi-continuous variables and SOS constraints
+//! are relaxed unless `GoodLpOptions` selects their big-M reformulation.
+//! Use [`capability_warnings`] to surface these limitations before solving.
+//!
+
+use std::{
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+use good_lp::{microlp, ResolutionError, Solution as _, SolverModel as _, Variable as GoodLpVariable};
+
+use crate::{
+    compat::{
+        good_lp::{GoodLpOptions, GoodLpProblem},
+        CancellationToken,
+    },
+    model::{Constraint, Solution, SolutionStatus, VariableType},
+    problem::LpProblem,
+};
+
+/// How often `solve_cancellable` checks the cancellation token while `microlp` runs.
+const CANCELLATION_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+#[must_use]
+/// Lists the features of `problem` that the pure-Rust backend cannot represent faithfully.
+pub fn capability_warnings(problem: &LpProblem<'_>) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    let integer_count = problem
+        .variables
+        .values()
+        .filter(|v| matches!(v.var_type, VariableType::Binary | VariableType::Integer | VariableType::General))
+        .count();
+    if integer_count > 0 {
+        warnings.push(format!(
+            "{integer_count} integer variable(s) found: microlp is an LP solver with limited MIP support, the solve may fail or be slow"
+        ));
+    }
+
+    let semi_count = problem.variables.values().filter(|v| v.var_type == VariableType::SemiContinuous).count();
+    if semi_count > 0 {
+        warnings.push(format!(
+            "{semi_count} semi-continuous variable(s) will be relaxed unless reformulated with `SemiContinuousStrategy::BigM`"
+        ));
+    }
+
+    let sos_count = problem.constraints.values().filter(|c| matches!(c, Constraint::SOS { .. })).count();
+    if sos_count > 0 {
+        warnings.push(format!("{sos_count} SOS constraint(s) will be dropped unless reformulated with `SosStrategy::BigM`"));
+    }
+
+    if problem.objectives.len() > 1 {
+        warnings.push(format!("{} objectives found: only the first objective (by name) will be optimised", problem.objectives.len()));
+    }
+
+    warnings
+}
+
+/// Solves `problem` with the pure-Rust `microlp` solver.
+///
+/// Infeasible and unbounded problems are reported through the status of the
+/// returned `Solution`. `microlp` only returns proven optima, so the best bound
+/// equals the objective value. It does not expose duals or reduced costs.
+///
+/// # Errors
+///
+/// Returns a `ResolutionError` if the problem contains integer variables that
+/// `microlp` is unable to handle.
+pub fn solve(problem: &LpProblem<'_>) -> Result<Solution, ResolutionError> {
+    solve_with_options(problem, &GoodLpOptions::default())
+}
+
+/// Solves `problem` with the pure-Rust `microlp` solver, translating it using `options`.
+///
+/// # Errors
+///
+/// Returns a `ResolutionError` if the problem contains integer variables that
+/// `microlp` is unable to handle.
+pub fn solve_with_options(problem: &LpProblem<'_>, options: &GoodLpOptions) -> Result<Solution, ResolutionError> {
+    prepare(problem, options)()
+}
+
+/// Solves `problem` like [`solve_with_options`], giving up once `cancellation` fires.
+///
+/// `microlp` cannot be interrupted, so the solve runs on a detached worker thread
+/// while the token is polled. When the token fires, a `Solution` with the status
+/// `SolutionStatus::Interrupted` is returned straight away and the abandoned
+/// worker keeps running in the background until `microlp` finishes.
+///
+/// # Errors
+///
+/// Returns a `ResolutionError` if the problem contains integer variables that
+/// `microlp` is unable to handle, or if the worker thread panics.
+pub fn solve_cancellable(
+    problem: &LpProblem<'_>,
+    options: &GoodLpOptions,
+    cancellation: &CancellationToken,
+) -> Result<Solution, ResolutionError> {
+    if cancellation.is_cancelled() {
+        return Ok(Solution::new(SolutionStatus::Interrupted));
+    }
+
+    let solve = prepare(problem, options);
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        // The receiver is gone if the solve was abandoned
+        let _ = sender.send(solve());
+    });
+    loop {
+        match receiver.recv_timeout(CANCELLATION_POLL_INTERVAL) {
+            Ok(result) => return result,
+            Err(RecvTimeoutError::Timeout) if cancellation.is_cancelled() => return Ok(Solution::new(SolutionStatus::Interrupted)),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return Err(ResolutionError::Other("microlp worker thread panicked")),
+        }
+    }
+}
+
+/// Translates `problem` and returns the owned `microlp` solve, which can be moved to another thread.
+fn prepare(problem: &LpProblem<'_>, options: &GoodLpOptions) -> impl FnOnce() -> Result<Solution, ResolutionError> + Send + 'static {
+    for warning in capability_warnings(problem) {
+        log::warn!("{warning}");
+    }
+
+    let converted = GoodLpProblem::new(problem, options);
+    let objective = converted.objective.clone();
+    let variable_map: Vec<(String, GoodLpVariable)> =
+        converted.variable_map.iter().map(|(name, var)| ((*name).to_string(), *var)).collect();
+    let model = converted.using(microlp);
+
+    move || match model.solve() {
+        Ok(solution) => {
+            let values = variable_map.into_iter().map(|(name, var)| (name, solution.value(var))).collect();
+            let objective_value = solution.eval(&objective);
+            Ok(Solution::new(SolutionStatus::Optimal).with_objective(objective_value).with_best_bound(objective_value).with_values(values))
+        }
+        Err(ResolutionError::Infeasible) => Ok(Solution::new(SolutionStatus::Infeasible)),
+        Err(ResolutionError::Unbounded) => Ok(Solution::new(SolutionStatus::Unbounded)),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use float_eq::assert_float_eq;
+
+    use crate::{
+        compat::{
+            good_lp::{GoodLpOptions, SemiContinuousStrategy},
+            pure::{capability_warnings, solve, solve_cancellable, solve_with_options},
+            CancellationToken,
+        },
+        model::{Solution, SolutionStatus},
+        problem::LpProblem,
+    };
+
+    #[test]
+    fn test_solve_lp() {
+        let input = "Maximize
+ obj: 3 x + 2 y
+subject to
+c1: x + y <= 4
+c2: x + 3 y <= 6
+bounds
+x <= 3
+End";
+        let problem = LpProblem::parse(input).expect("test case not to fail");
+        assert!(capability_warnings(&problem).is_empty());
+
+        let solution = solve(&problem).expect("test case not to fail");
+        assert_eq!(solution.status, SolutionStatus::Optimal);
+        assert_float_eq!(solution.objective.expect("test case not to fail"), 11.0, abs <= 1e-9);
+        assert_eq!(solution.relative_gap(), Some(0.0));
+        assert_float_eq!(solution.value("x"), 3.0, abs <= 1e-9);
+        assert_float_eq!(solution.value("y"), 1.0, abs <= 1e-9);
+        assert!(problem.check_solution(&solution).is_empty());
+    }
+
+    #[test]
+    fn test_relative_gap() {
+        let gap = |objective: f64, best_bound: f64| {
+            Solution::new(SolutionStatus::Feasible).with_objective(objective).with_best_bound(best_bound).relative_gap()
+        };
+        assert_float_eq!(gap(100.0, 90.0).expect("test case not to fail"), 0.1, abs <= 1e-12);
+        assert_float_eq!(gap(-50.0, -55.0).expect("test case not to fail"), 0.1, abs <= 1e-12);
+        // Near-zero objectives are measured against 1e-10 rather than dividing by zero.
+        assert_float_eq!(gap(0.0, 1e-12).expect("test case not to fail"), 0.01, abs <= 1e-12);
+        assert_eq!(gap(0.0, 0.0), Some(0.0));
+        assert!(gap(0.0, 1.0).expect("test case not to fail").is_finite());
+        assert_eq!(Solution::new(SolutionStatus::Feasible).with_objective(1.0).relative_gap(), None);
+    }
+
+    #[test]
+    fn test_solve_infeasible() {
+        let input = "Minimize
+ obj: x
+subject to
+c1: x >= 4
+c2: x <= 2
+End";
+        let problem = LpProblem::parse(input).expect("test case not to fail");
+        assert_eq!(solve(&problem).expect("test case not to fail").status, SolutionStatus::Infeasible);
+    }
+
+    #[test]
+    fn test_solve_declared_integer_bounds() {
+        let input = "Maximize
+ obj: x
+subject to
+c1: x >= 0
+bounds
+0 <= x <= 10
+Integers
+x
+End";
+        let problem = LpProblem::parse(input).expect("test case not to fail");
+        let solution = solve(&problem).expect("test case not to fail");
+        assert_eq!(solution.status, SolutionStatus::Optimal);
+        assert_float_eq!(solution.value("x"), 10.0, abs <= 1e-9);
+    }
+
+    #[test]
+    fn test_solve_free_variable() {
+        let input = "Minimize
+ obj: x + y
+subject to
+c1: x >= -3
+c2: y >= -3
+bounds
+x free
+End";
+        let problem = LpProblem::parse(input).expect("test case not to fail");
+        let solution = solve(&problem).expect("test case not to fail");
+        assert_eq!(solution.status, SolutionStatus::Optimal);
+        assert_float_eq!(solution.value("x"), -3.0, abs <= 1e-9);
+        assert_float_eq!(solution.value("y"), 0.0, abs <= 1e-9);
+    }
+
+    #[test]
+    fn test_solve_semi_continuous_big_m() {
+        let input = "Minimize
+ obj: s
+subject to
+c1: s >= 1
+bounds
+2 <= s <= 5
+Integers
+Generals
+Binary
+Semi-Continuous
+s
+End";
+        let problem = LpProblem::parse(input).expect("test case not to fail");
+        assert_float_eq!(solve(&problem).expect("test case not to fail").value("s"), 1.0, abs <= 1e-9);
+
+        let options = GoodLpOptions::default().with_semi_continuous(SemiContinuousStrategy::BigM(100.0));
+        let solution = solve_with_options(&problem, &options).expect("test case not to fail");
+        assert_eq!(solution.status, SolutionStatus::Optimal);
+        assert_float_eq!(solution.value("s"), 2.0, abs <= 1e-9);
+    }
+
+    #[test]
+    fn test_solve_cancellable() {
+        let problem = LpProblem::parse("Maximize\n obj: x\nsubject to\nc1: x <= 4\nEnd").expect("test case not to fail");
+        let solution = solve_cancellable(&problem, &GoodLpOptions::default(), &CancellationToken::new()).expect("test case not to fail");
+        assert_float_eq!(solution.value("x"), 4.0, abs <= 1e-9);
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let solution = solve_cancellable(&problem, &GoodLpOptions::default(), &token).expect("test case not to fail");
+        assert_eq!(solution.status, SolutionStatus::Interrupted);
+    }
+
+    #[test]
+    fn test_capability_warnings() {
+        let input = "Minimize
+ obj: x + y
+subject to
+c1: x + y >= 1
+Binary
+ x
+End";
+        let problem = LpProblem::parse(input).expect("test case not to fail");
+        assert_eq!(capability_warnings(&problem).len(), 1);
+    }
+}
@@ -0,0 +1,184 @@
+//! CSV export of a `DiffReport`.
+//!
+//! Reports are written as two long-format tables that pivot well in a
+//! spreadsheet:
+//!
+//! - `write_coefficients_csv`: one row per changed coefficient, with the
+//!   columns `section,name,kind,variable,old,new`.
+//! - `write_fields_csv`: one row per other changed field (sense, operator,
+//!   right-hand side, SOS type, variable type, bounds and attributes), with the
+//!   columns `section,name,kind,field,old,new`. Attributes are written with the
+//!   field `attribute:KEY`.
+//!
+//! Values that are absent on one side are written as empty cells.
+//!
+
+use std::{borrow::Cow, fmt::Display, io::Write};
+
+use crate::diff::{AttributeChange, Change, CoefficientChange, DiffKind, DiffReport};
+
+/// Quotes a CSV cell if it contains a delimiter, quote or line break.
+fn escape(cell: &str) -> Cow<'_, str> {
+    if cell.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", cell.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(cell)
+    }
+}
+
+fn write_row<W: Write>(writer: &mut W, cells: &[&str]) -> std::io::Result<()> {
+    let row: Vec<Cow<str>> = cells.iter().map(|cell| escape(cell)).collect();
+    writeln!(writer, "{}", row.join(","))
+}
+
+#[inline]
+fn cell<T: Display>(value: Option<&T>) -> String {
+    value.map_or_else(String::new, ToString::to_string)
+}
+
+fn write_change<W: Write, T: Display>(
+    writer: &mut W,
+    section: &str,
+    name: &str,
+    kind: DiffKind,
+    field: &str,
+    change: Option<&Change<T>>,
+) -> std::io::Result<()> {
+    match change {
+        Some(change) => {
+            write_row(writer, &[section, name, &kind.to_string(), field, &cell(change.old.as_ref()), &cell(change.new.as_ref())])
+        }
+        None => Ok(()),
+    }
+}
+
+fn write_coefficient_rows<W: Write>(
+    writer: &mut W,
+    section: &str,
+    name: &str,
+    kind: DiffKind,
+    coefficients: &[CoefficientChange],
+) -> std::io::Result<()> {
+    let kind = kind.to_string();
+    for coefficient in coefficients {
+        let (old, new) = (cell(coefficient.change.old.as_ref()), cell(coefficient.change.new.as_ref()));
+        write_row(writer, &[section, name, &kind, &coefficient.variable, &old, &new])?;
+    }
+    Ok(())
+}
+
+fn write_attribute_rows<W: Write>(
+    writer: &mut W,
+    section: &str,
+    name: &str,
+    kind: DiffKind,
+    attributes: &[AttributeChange],
+) -> std::io::Result<()> {
+    for attribute in attributes {
+        write_change(writer, section, name, kind, &format!("attribute:{}", attribute.key), Some(&attribute.change))?;
+    }
+    Ok(())
+}
+
+/// Writes one row per changed objective coefficient, constraint coefficient and SOS weight.
+///
+/// # Errors
+///
+/// Returns an error if writing to `writer` fails.
+///
+pub fn write_coefficients_csv<W: Write>(report: &DiffReport, mut writer: W) -> std::io::Result<()> {
+    write_row(&mut writer, &["section", "name", "kind", "variable", "old", "new"])?;
+    for objective in &report.objectives {
+        write_coefficient_rows(&mut writer, "objective", &objective.name, objective.kind, &objective.coefficients)?;
+    }
+    for constraint in &report.constraints {
+        write_coefficient_rows(&mut writer, "constraint", &constraint.name, constraint.kind, &constraint.coefficients)?;
+    }
+    Ok(())
+}
+
+/// Writes one row per changed field other than coefficients.
+///
+/// Renamed constraints are written with a `renamed_from` field holding the
+/// previous name.
+///
+/// # Errors
+///
+/// Returns an error if writing to `writer` fails.
+///
+pub fn write_fields_csv<W: Write>(report: &DiffReport, mut writer: W) -> std::io::Result<()> {
+    write_row(&mut writer, &["section", "name", "kind", "field", "old", "new"])?;
+    write_change(&mut writer, "problem", "", DiffKind::Modified, "sense", report.sense.as_ref())?;
+    for objective in &report.objectives {
+        write_attribute_rows(&mut writer, "objective", &objective.name, objective.kind, &objective.attributes)?;
+    }
+    for constraint in &report.constraints {
+        let (name, kind) = (constraint.name.as_str(), constraint.kind);
+        if let Some(previous) = &constraint.renamed_from {
+            write_row(&mut writer, &["constraint", name, &kind.to_string(), "renamed_from", previous, name])?;
+        }
+        write_change(&mut writer, "constraint", name, kind, "operator", constraint.operator.as_ref())?;
+        write_change(&mut writer, "constraint", name, kind, "rhs", constraint.rhs.as_ref())?;
+        write_change(&mut writer, "constraint", name, kind, "sos_type", constraint.sos_type.as_ref())?;
+        write_attribute_rows(&mut writer, "constraint", name, kind, &constraint.attributes)?;
+    }
+    for variable in &report.variables {
+        let (name, kind) = (variable.name.as_str(), variable.kind);
+        if variable.type_changed() {
+            write_change(&mut writer, "variable", name, kind, "type", Some(&variable.var_type))?;
+        }
+        write_change(&mut writer, "variable", name, kind, "lower", variable.lower.as_ref())?;
+        write_change(&mut writer, "variable", name, kind, "upper", variable.upper.as_ref())?;
+        write_attribute_rows(&mut writer, "variable", name, kind, &variable.attributes)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        diff::{
+            build_diff_report,
+            csv::{escape, write_coefficients_csv, write_fields_csv},
+            DiffOptions,
+        },
+        problem::LpProblem,
+    };
+
+    #[test]
+    fn test_write_csv() {
+        let old = LpProblem::parse("maximize\nobj: 3x + 2y\nsubject to\nc1: x + y <= 4\nc2: x + 3y <= 6\nbounds\nx <= 3\nend")
+            .expect("test case not to fail");
+        let mut new = LpProblem::parse("minimize\nobj: 3x + 2y\nsubject to\nc1: x + y <= 4\nc2: x + 2y >= 6\nbounds\nx <= 5\nend")
+            .expect("test case not to fail");
+        if let Some(c1) = new.constraints.get_mut("c1") {
+            c1.attributes_mut().insert("owner".to_string(), "ops, planning".into());
+        }
+        let report = build_diff_report(&old, &new, &DiffOptions::default());
+
+        let mut coefficients = Vec::new();
+        write_coefficients_csv(&report, &mut coefficients).expect("test case not to fail");
+        assert_eq!(
+            String::from_utf8(coefficients).expect("test case not to fail"),
+            "section,name,kind,variable,old,new\nconstraint,c2,modified,y,3,2\n"
+        );
+
+        let mut fields = Vec::new();
+        write_fields_csv(&report, &mut fields).expect("test case not to fail");
+        assert_eq!(
+            String::from_utf8(fields).expect("test case not to fail"),
+            "section,name,kind,field,old,new\n\
+             problem,,modified,sense,Maximize,Minimize\n\
+             constraint,c1,modified,attribute:owner,,\"ops, planning\"\n\
+             constraint,c2,modified,operator,<=,>=\n\
+             variable,x,modified,upper,3,5\n"
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("plain"), "plain");
+        assert_eq!(escape("a,b"), "\"a,b\"");
+        assert_eq!(escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
@@ -0,0 +1,997 @@
+//! Coefficient-level comparison of two LP problems.
+//!
+//! Unlike the `diff` feature, which derives a structural diff of the raw
+//! `LpProblem` fields, this module compares problems entity by entity and
+//! reports exactly which coefficients, operators, right-hand sides and
+//! variable types changed. It has no optional dependencies.
+//!
+//! - `csv`: Exporting a `DiffReport` as CSV tables.
+//! - `patch`: Applying the changes in a `DiffReport` to another problem.
+//! - `structure`: Name-agnostic comparison of two problems.
+//! - `summary`: Fast per-section change counts.
+//!
+//! - `DiffOptions`: Options controlling how problems are compared, including
+//!   tolerances and which sections and entities to compare.
+//! - `DiffReport`: The result of comparing two problems.
+//! - `build_diff_report`: Compares two problems and builds a `DiffReport`.
+//!
+//! With the `serde` feature the report serializes with a stable schema:
+//! every field is always present, absent values are `null` and `kind` is one
+//! of `added`, `removed`, `modified` or `renamed`.
+//!
+
+pub mod csv;
+pub mod patch;
+pub mod structure;
+pub mod summary;
+
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, BTreeSet, HashMap, HashSet},
+    hash::{Hash, Hasher},
+    mem::discriminant,
+    sync::Arc,
+};
+
+use crate::{
+    model::{AttrValue, Attributes, Coefficient, ComparisonOp, Constraint, SOSType, Sense, Variable, VariableType},
+    problem::LpProblem,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// The parts of a problem that can be compared.
+pub enum DiffSection {
+    /// The optimization sense.
+    Sense,
+    /// The objectives.
+    Objectives,
+    /// The constraints.
+    Constraints,
+    /// The variables.
+    Variables,
+}
+
+type NamePredicate = dyn Fn(DiffSection, &str) -> bool + Send + Sync;
+
+#[derive(Clone)]
+/// A predicate deciding whether a named entity in a section is compared.
+pub struct NameFilter(Arc<NamePredicate>);
+
+impl NameFilter {
+    #[must_use]
+    #[inline]
+    /// Initialise a new `NameFilter` from a predicate.
+    pub fn new(predicate: impl Fn(DiffSection, &str) -> bool + Send + Sync + 'static) -> Self {
+        Self(Arc::new(predicate))
+    }
+}
+
+impl std::fmt::Debug for NameFilter {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("NameFilter")
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+/// Options controlling how two problems are compared by `build_diff_report`.
+///
+/// By default values are compared exactly. Two values are considered equal
+/// when they differ by no more than `absolute_tolerance`, or by no more than
+/// `relative_tolerance` times the larger of their magnitudes.
+///
+/// The comparison can be restricted to some `sections`, and to entities whose
+/// names match one of `name_globs` and are accepted by `name_filter`. Entities
+/// that are filtered out are skipped before any of their details are compared.
+pub struct DiffOptions {
+    /// The absolute tolerance used when comparing numeric values.
+    pub absolute_tolerance: f64,
+    /// The relative tolerance used when comparing numeric values.
+    pub relative_tolerance: f64,
+    /// Whether a removed constraint that matches an added one is reported as a rename.
+    pub detect_renames: bool,
+    /// The sections to compare, or every section if empty.
+    pub sections: Vec<DiffSection>,
+    /// Glob patterns (`*` and `?`) of the entity names to compare, or every name if empty.
+    pub name_globs: Vec<String>,
+    /// A predicate restricting which entities are compared.
+    pub name_filter: Option<NameFilter>,
+}
+
+impl DiffOptions {
+    #[must_use]
+    #[inline]
+    /// Builder method for setting the absolute tolerance.
+    pub fn with_absolute_tolerance(self, absolute_tolerance: f64) -> Self {
+        Self { absolute_tolerance, ..self }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Builder method for setting the relative tolerance.
+    pub fn with_relative_tolerance(self, relative_tolerance: f64) -> Self {
+        Self { relative_tolerance, ..self }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Builder method for enabling rename detection.
+    ///
+    /// A removed constraint is reported as renamed when an added constraint has
+    /// the same operator, right-hand side and coefficients (or SOS type and
+    /// weights) within the configured tolerances.
+    pub fn with_rename_detection(self, detect_renames: bool) -> Self {
+        Self { detect_renames, ..self }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Builder method for restricting the comparison to the given sections.
+    pub fn with_sections(self, sections: &[DiffSection]) -> Self {
+        Self { sections: sections.to_vec(), ..self }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Builder method for adding a glob pattern of entity names to compare.
+    pub fn with_name_glob(mut self, pattern: &str) -> Self {
+        self.name_globs.push(pattern.to_string());
+        self
+    }
+
+    #[must_use]
+    #[inline]
+    /// Builder method for setting a predicate restricting which entities are compared.
+    pub fn with_name_filter(self, predicate: impl Fn(DiffSection, &str) -> bool + Send + Sync + 'static) -> Self {
+        Self { name_filter: Some(NameFilter::new(predicate)), ..self }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Returns `true` if `section` is compared.
+    pub fn includes_section(&self, section: DiffSection) -> bool {
+        self.sections.is_empty() || self.sections.contains(&section)
+    }
+
+    #[must_use]
+    #[inline]
+    /// Returns `true` if the entity `name` in `section` is compared.
+    pub fn includes(&self, section: DiffSection, name: &str) -> bool {
+        self.includes_section(section)
+            && (self.name_globs.is_empty() || self.name_globs.iter().any(|pattern| glob_match(pattern, name)))
+            && self.name_filter.as_ref().map_or(true, |filter| (filter.0)(section, name))
+    }
+
+    #[must_use]
+    #[inline]
+    /// Returns `true` if two values are equal within the configured tolerances.
+    pub fn values_equal(&self, a: f64, b: f64) -> bool {
+        #[allow(clippy::float_cmp)]
+        let exact = a == b;
+        exact || (a - b).abs() <= self.absolute_tolerance.max(self.relative_tolerance * a.abs().max(b.abs()))
+    }
+
+    #[must_use]
+    /// Returns `true` if two variable types are the same kind and their bounds are equal within the configured tolerances.
+    pub fn var_types_equal(&self, a: &VariableType, b: &VariableType) -> bool {
+        let ((a_lower, a_upper), (b_lower, b_upper)) = (a.bounds(), b.bounds());
+        discriminant(a) == discriminant(b) && self.values_equal(a_lower, b_lower) && self.values_equal(a_upper, b_upper)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Describes how an entity differs between the two problems.
+pub enum DiffKind {
+    /// The entity only exists in the new problem.
+    Added,
+    /// The entity only exists in the old problem.
+    Removed,
+    /// The entity exists in both problems but differs.
+    Modified,
+    /// The entity was removed and re-added under a new name.
+    Renamed,
+}
+
+impl std::fmt::Display for DiffKind {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Added => write!(f, "added"),
+            Self::Removed => write!(f, "removed"),
+            Self::Modified => write!(f, "modified"),
+            Self::Renamed => write!(f, "renamed"),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// A value that differs between the two problems, `None` where it is absent.
+pub struct Change<T> {
+    /// The value in the old problem.
+    pub old: Option<T>,
+    /// The value in the new problem.
+    pub new: Option<T>,
+}
+
+impl<T: PartialEq> Change<T> {
+    #[inline]
+    fn between(old: Option<T>, new: Option<T>) -> Option<Self> {
+        Self::between_by(old, new, |a, b| a == b)
+    }
+}
+
+impl<T> Change<T> {
+    #[inline]
+    fn between_by(old: Option<T>, new: Option<T>, eq: impl Fn(&T, &T) -> bool) -> Option<Self> {
+        let equal = match (&old, &new) {
+            (Some(a), Some(b)) => eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        (!equal).then_some(Self { old, new })
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Change<T> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.old {
+            Some(old) => write!(f, "{old}")?,
+            None => write!(f, "-")?,
+        }
+        write!(f, " -> ")?;
+        match &self.new {
+            Some(new) => write!(f, "{new}"),
+            None => write!(f, "-"),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// A change to the coefficient of a single variable.
+pub struct CoefficientChange {
+    /// The name of the variable.
+    pub variable: String,
+    /// The old and new coefficient, `None` where the variable does not appear.
+    pub change: Change<f64>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// A change to a single attribute.
+pub struct AttributeChange {
+    /// The key of the attribute.
+    pub key: String,
+    /// The old and new value, `None` where the attribute is not set.
+    pub change: Change<AttrValue>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// The differences in a single objective.
+pub struct ObjectiveDiff {
+    /// The name of the objective.
+    pub name: String,
+    /// How the objective differs.
+    pub kind: DiffKind,
+    /// The coefficients that differ, sorted by variable name.
+    pub coefficients: Vec<CoefficientChange>,
+    /// The attributes that differ, sorted by key.
+    pub attributes: Vec<AttributeChange>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// The differences in a single constraint.
+///
+/// Standard constraints report `operator` and `rhs` changes, SOS constraints
+/// report `sos_type` changes and their weights as `coefficients`.
+pub struct ConstraintDiff {
+    /// The name of the constraint.
+    pub name: String,
+    /// How the constraint differs.
+    pub kind: DiffKind,
+    /// The coefficients (or SOS weights) that differ, sorted by variable name.
+    pub coefficients: Vec<CoefficientChange>,
+    /// The comparison operator, if it differs.
+    pub operator: Option<Change<ComparisonOp>>,
+    /// The right-hand side, if it differs.
+    pub rhs: Option<Change<f64>>,
+    /// The SOS type, if it differs.
+    pub sos_type: Option<Change<SOSType>>,
+    /// The name in the old problem, if the constraint was renamed.
+    pub renamed_from: Option<String>,
+    /// The attributes that differ, sorted by key.
+    pub attributes: Vec<AttributeChange>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// The differences in a single variable.
+///
+/// Alongside the old and new `VariableType`, the effective bounds are
+/// reported separately so a change can be described as `ub 10 -> 12` rather
+/// than as a change of type.
+pub struct VariableDiff {
+    /// The name of the variable.
+    pub name: String,
+    /// How the variable differs.
+    pub kind: DiffKind,
+    /// The old and new variable type.
+    pub var_type: Change<VariableType>,
+    /// The effective lower bound, if it differs.
+    #[cfg_attr(feature = "serde", serde(with = "bound_change"))]
+    pub lower: Option<Change<f64>>,
+    /// The effective upper bound, if it differs.
+    #[cfg_attr(feature = "serde", serde(with = "bound_change"))]
+    pub upper: Option<Change<f64>>,
+    /// The attributes that differ, sorted by key.
+    pub attributes: Vec<AttributeChange>,
+}
+
+#[cfg(feature = "serde")]
+/// Serializes infinite bounds as `"inf"` and `"-inf"`, as JSON has no representation for them.
+mod bound_change {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{
+        diff::Change,
+        model::infinite::{to_f64, Bound},
+    };
+
+    pub fn serialize<S: Serializer>(value: &Option<Change<f64>>, serializer: S) -> Result<S::Ok, S::Error> {
+        value.as_ref().map(|change| Change { old: change.old.map(Bound::from), new: change.new.map(Bound::from) }).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Change<f64>>, D::Error> {
+        Option::<Change<Bound>>::deserialize(deserializer)?
+            .map(|change| Ok(Change { old: change.old.map(to_f64).transpose()?, new: change.new.map(to_f64).transpose()? }))
+            .transpose()
+    }
+}
+
+impl VariableDiff {
+    #[must_use]
+    #[inline]
+    /// Returns `true` if the kind of variable changed, rather than only its bounds.
+    pub fn type_changed(&self) -> bool {
+        match (&self.var_type.old, &self.var_type.new) {
+            (Some(old), Some(new)) => discriminant(old) != discriminant(new),
+            _ => true,
+        }
+    }
+}
+
+impl std::fmt::Display for VariableDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Variable {} {}", self.name, self.kind)?;
+        if self.kind != DiffKind::Modified {
+            return write!(f, ": {}", self.var_type);
+        }
+        let mut details = Vec::new();
+        if self.type_changed() {
+            details.push(format!("type {}", self.var_type));
+        }
+        if let Some(lower) = &self.lower {
+            details.push(format!("lb {lower}"));
+        }
+        if let Some(upper) = &self.upper {
+            details.push(format!("ub {upper}"));
+        }
+        details.extend(self.attributes.iter().map(|attribute| format!("attribute {} {}", attribute.key, attribute.change)));
+        write!(f, ": {}", details.join(", "))
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default, PartialEq)]
+/// A coefficient-level report of the differences between two problems.
+///
+/// Every list is sorted by name so reports are deterministic.
+pub struct DiffReport {
+    /// The optimization sense, if it differs.
+    pub sense: Option<Change<Sense>>,
+    /// The objectives that differ.
+    pub objectives: Vec<ObjectiveDiff>,
+    /// The constraints that differ.
+    pub constraints: Vec<ConstraintDiff>,
+    /// The variables that differ.
+    pub variables: Vec<VariableDiff>,
+}
+
+impl DiffReport {
+    #[must_use]
+    #[inline]
+    /// Returns `true` if the two problems compared were equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.sense.is_none() && self.objectives.is_empty() && self.constraints.is_empty() && self.variables.is_empty()
+    }
+}
+
+impl std::fmt::Display for DiffReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(sense) = &self.sense {
+            writeln!(f, "Sense changed: {sense}")?;
+        }
+        for objective in &self.objectives {
+            writeln!(f, "Objective {} {}", objective.name, objective.kind)?;
+            write_coefficients(f, &objective.coefficients)?;
+            write_attributes(f, &objective.attributes)?;
+        }
+        for constraint in &self.constraints {
+            match &constraint.renamed_from {
+                Some(previous) => writeln!(f, "Constraint {} {} from {previous}", constraint.name, constraint.kind)?,
+                None => writeln!(f, "Constraint {} {}", constraint.name, constraint.kind)?,
+            }
+            if let Some(sos_type) = &constraint.sos_type {
+                writeln!(f, "  type: {sos_type}")?;
+            }
+            write_coefficients(f, &constraint.coefficients)?;
+            if let Some(operator) = &constraint.operator {
+                writeln!(f, "  operator: {operator}")?;
+            }
+            if let Some(rhs) = &constraint.rhs {
+                writeln!(f, "  rhs: {rhs}")?;
+            }
+            write_attributes(f, &constraint.attributes)?;
+        }
+        for variable in &self.variables {
+            writeln!(f, "{variable}")?;
+        }
+        Ok(())
+    }
+}
+
+fn write_coefficients(f: &mut std::fmt::Formatter<'_>, coefficients: &[CoefficientChange]) -> std::fmt::Result {
+    for coefficient in coefficients {
+        writeln!(f, "  {}: {}", coefficient.variable, coefficient.change)?;
+    }
+    Ok(())
+}
+
+fn write_attributes(f: &mut std::fmt::Formatter<'_>, attributes: &[AttributeChange]) -> std::fmt::Result {
+    for attribute in attributes {
+        writeln!(f, "  attribute {}: {}", attribute.key, attribute.change)?;
+    }
+    Ok(())
+}
+
+#[inline]
+pub(crate) fn hash_of<T: Hash>(value: T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[inline]
+/// Returns the bit pattern of a value, treating `-0.0` and `0.0` as equal.
+pub(crate) fn float_key(value: f64) -> u64 {
+    if value == 0.0 {
+        0.0_f64.to_bits()
+    } else {
+        value.to_bits()
+    }
+}
+
+/// Matches `name` against a glob `pattern`, where `*` matches any run of characters and `?` any single character.
+pub(crate) fn glob_match(pattern: &str, name: &str) -> bool {
+    let (pattern, name): (Vec<char>, Vec<char>) = (pattern.chars().collect(), name.chars().collect());
+    let (mut p, mut n) = (0, 0);
+    // The position of the last `*` in the pattern, and the name position it was tried against.
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    n = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Sums the coefficients of each variable, so repeated terms compare equal to their total.
+pub(crate) fn coefficient_map<'a>(coefficients: &[Coefficient<'a>]) -> BTreeMap<&'a str, f64> {
+    let mut map = BTreeMap::new();
+    for coefficient in coefficients {
+        *map.entry(coefficient.var_name).or_insert(0.0) += coefficient.coefficient;
+    }
+    map
+}
+
+fn diff_coefficients(old: &BTreeMap<&str, f64>, new: &BTreeMap<&str, f64>, options: &DiffOptions) -> Vec<CoefficientChange> {
+    let names: BTreeSet<&str> = old.keys().chain(new.keys()).copied().collect();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            Change::between_by(old.get(name).copied(), new.get(name).copied(), |a, b| options.values_equal(*a, *b))
+                .map(|change| CoefficientChange { variable: name.to_string(), change })
+        })
+        .collect()
+}
+
+fn diff_attributes(old: Option<&Attributes>, new: Option<&Attributes>) -> Vec<AttributeChange> {
+    let keys: BTreeSet<&String> = old.into_iter().chain(new).flat_map(BTreeMap::keys).collect();
+    keys.into_iter()
+        .filter_map(|key| {
+            Change::between(old.and_then(|old| old.get(key)).cloned(), new.and_then(|new| new.get(key)).cloned())
+                .map(|change| AttributeChange { key: key.clone(), change })
+        })
+        .collect()
+}
+
+#[inline]
+fn diff_kind<T>(old: Option<&T>, new: Option<&T>) -> DiffKind {
+    match (old, new) {
+        (None, _) => DiffKind::Added,
+        (_, None) => DiffKind::Removed,
+        _ => DiffKind::Modified,
+    }
+}
+
+/// The parts of a constraint that are compared, regardless of its kind.
+pub(crate) struct ConstraintParts<'a> {
+    pub(crate) coefficients: BTreeMap<&'a str, f64>,
+    pub(crate) operator: Option<ComparisonOp>,
+    pub(crate) rhs: Option<f64>,
+    pub(crate) sos_type: Option<SOSType>,
+}
+
+impl<'a> From<&Constraint<'a>> for ConstraintParts<'a> {
+    fn from(constraint: &Constraint<'a>) -> Self {
+        match constraint {
+            Constraint::Standard { coefficients, operator, rhs, .. } => {
+                Self { coefficients: coefficient_map(coefficients), operator: Some(operator.clone()), rhs: Some(*rhs), sos_type: None }
+            }
+            Constraint::SOS { sos_type, weights, .. } => {
+                Self { coefficients: coefficient_map(weights), operator: None, rhs: None, sos_type: Some(*sos_type) }
+            }
+        }
+    }
+}
+
+fn diff_objectives(old: &LpProblem, new: &LpProblem, options: &DiffOptions) -> Vec<ObjectiveDiff> {
+    let names: BTreeSet<&str> = old
+        .objectives
+        .keys()
+        .chain(new.objectives.keys())
+        .map(AsRef::as_ref)
+        .filter(|name| options.includes(DiffSection::Objectives, name))
+        .collect();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let (old, new) = (old.objectives.get(name), new.objectives.get(name));
+            let empty = BTreeMap::new();
+            let old_map = old.map_or_else(|| empty.clone(), |objective| coefficient_map(&objective.coefficients));
+            let new_map = new.map_or(empty, |objective| coefficient_map(&objective.coefficients));
+            let coefficients = diff_coefficients(&old_map, &new_map, options);
+            let attributes = diff_attributes(old.map(|objective| &objective.attributes), new.map(|objective| &objective.attributes));
+            let kind = diff_kind(old, new);
+            (kind != DiffKind::Modified || !coefficients.is_empty() || !attributes.is_empty()).then_some(ObjectiveDiff {
+                name: name.to_string(),
+                kind,
+                coefficients,
+                attributes,
+            })
+        })
+        .collect()
+}
+
+fn diff_constraints(old: &LpProblem, new: &LpProblem, options: &DiffOptions) -> Vec<ConstraintDiff> {
+    let names: BTreeSet<&str> = old
+        .constraints
+        .keys()
+        .chain(new.constraints.keys())
+        .map(AsRef::as_ref)
+        .filter(|name| options.includes(DiffSection::Constraints, name))
+        .collect();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let (old, new) = (old.constraints.get(name), new.constraints.get(name));
+            let kind = diff_kind(old, new);
+            let (old_parts, new_parts) = (old.map(ConstraintParts::from), new.map(ConstraintParts::from));
+            let empty = BTreeMap::new();
+            let diff = ConstraintDiff {
+                name: name.to_string(),
+                kind,
+                coefficients: diff_coefficients(
+                    old_parts.as_ref().map_or(&empty, |parts| &parts.coefficients),
+                    new_parts.as_ref().map_or(&empty, |parts| &parts.coefficients),
+                    options,
+                ),
+                operator: Change::between(
+                    old_parts.as_ref().and_then(|parts| parts.operator.clone()),
+                    new_parts.as_ref().and_then(|parts| parts.operator.clone()),
+                ),
+                rhs: Change::between_by(
+                    old_parts.as_ref().and_then(|parts| parts.rhs),
+                    new_parts.as_ref().and_then(|parts| parts.rhs),
+                    |a, b| options.values_equal(*a, *b),
+                ),
+                sos_type: Change::between(
+                    old_parts.as_ref().and_then(|parts| parts.sos_type),
+                    new_parts.as_ref().and_then(|parts| parts.sos_type),
+                ),
+                renamed_from: None,
+                attributes: diff_attributes(old.map(Constraint::attributes), new.map(Constraint::attributes)),
+            };
+            let changed = !diff.coefficients.is_empty()
+                || diff.operator.is_some()
+                || diff.rhs.is_some()
+                || diff.sos_type.is_some()
+                || !diff.attributes.is_empty();
+            (kind != DiffKind::Modified || changed).then_some(diff)
+        })
+        .collect()
+}
+
+impl ConstraintParts<'_> {
+    /// Returns `true` if both constraints are the same within the tolerances in `options`.
+    fn equivalent(&self, other: &ConstraintParts, options: &DiffOptions) -> bool {
+        let rhs_equal = match (self.rhs, other.rhs) {
+            (Some(a), Some(b)) => options.values_equal(a, b),
+            (a, b) => a == b,
+        };
+        self.operator == other.operator
+            && self.sos_type == other.sos_type
+            && rhs_equal
+            && self.coefficients.len() == other.coefficients.len()
+            && self
+                .coefficients
+                .iter()
+                .zip(&other.coefficients)
+                .all(|((a_name, a), (b_name, b))| a_name == b_name && options.values_equal(*a, *b))
+    }
+}
+
+/// Constraints are only compared for renames if they have the same operator, SOS type and variables.
+type ConstraintShape<'a> = (Option<ComparisonOp>, Option<SOSType>, Vec<&'a str>);
+
+#[inline]
+fn constraint_shape<'a>(parts: &ConstraintParts<'a>) -> ConstraintShape<'a> {
+    (parts.operator.clone(), parts.sos_type, parts.coefficients.keys().copied().collect())
+}
+
+/// Pairs each removed constraint with the first equivalent added constraint, and reports the pair as a single rename.
+fn detect_renames(old: &LpProblem, new: &LpProblem, diffs: Vec<ConstraintDiff>, options: &DiffOptions) -> Vec<ConstraintDiff> {
+    let added: Vec<(&str, ConstraintParts)> = diffs
+        .iter()
+        .filter(|diff| diff.kind == DiffKind::Added)
+        .filter_map(|diff| new.constraints.get(diff.name.as_str()).map(|c| (diff.name.as_str(), ConstraintParts::from(c))))
+        .collect();
+    let mut buckets: HashMap<ConstraintShape, Vec<usize>> = HashMap::new();
+    for (index, (_, parts)) in added.iter().enumerate() {
+        buckets.entry(constraint_shape(parts)).or_default().push(index);
+    }
+
+    // Maps the new name of each renamed constraint to its old name.
+    let mut renames: HashMap<String, String> = HashMap::new();
+    let mut used = vec![false; added.len()];
+    for diff in diffs.iter().filter(|diff| diff.kind == DiffKind::Removed) {
+        let parts = match old.constraints.get(diff.name.as_str()) {
+            Some(constraint) => ConstraintParts::from(constraint),
+            None => continue,
+        };
+        let candidates = match buckets.get(&constraint_shape(&parts)) {
+            Some(candidates) => candidates,
+            None => continue,
+        };
+        if let Some(&index) = candidates.iter().find(|&&index| !used[index] && added[index].1.equivalent(&parts, options)) {
+            used[index] = true;
+            renames.insert(added[index].0.to_string(), diff.name.clone());
+        }
+    }
+
+    let renamed: HashSet<String> = renames.values().cloned().collect();
+    diffs
+        .into_iter()
+        .filter(|diff| !(diff.kind == DiffKind::Removed && renamed.contains(&diff.name)))
+        .map(|diff| match renames.remove(&diff.name) {
+            Some(previous) => ConstraintDiff {
+                kind: DiffKind::Renamed,
+                coefficients: Vec::new(),
+                operator: None,
+                rhs: None,
+                sos_type: None,
+                attributes: diff_attributes(
+                    old.constraints.get(previous.as_str()).map(Constraint::attributes),
+                    new.constraints.get(diff.name.as_str()).map(Constraint::attributes),
+                ),
+                renamed_from: Some(previous),
+                ..diff
+            },
+            None => diff,
+        })
+        .collect()
+}
+
+fn diff_variables(old: &LpProblem, new: &LpProblem, options: &DiffOptions) -> Vec<VariableDiff> {
+    let names: BTreeSet<&str> =
+        old.variables.keys().chain(new.variables.keys()).copied().filter(|name| options.includes(DiffSection::Variables, name)).collect();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let (old, new) = (old.variables.get(name), new.variables.get(name));
+            let kind = diff_kind(old, new);
+            let (old_bounds, new_bounds) = (old.map(Variable::effective_bounds), new.map(Variable::effective_bounds));
+            let bound_change = |bound: fn((f64, f64)) -> f64| {
+                Change::between_by(old_bounds.map(bound), new_bounds.map(bound), |a, b| options.values_equal(*a, *b))
+            };
+            let (old_type, new_type) = (old.map(|variable| variable.var_type.clone()), new.map(|variable| variable.var_type.clone()));
+            let type_changed = Change::between_by(old_type.clone(), new_type.clone(), |a, b| options.var_types_equal(a, b)).is_some();
+            let (lower, upper) = (bound_change(|(lower, _)| lower), bound_change(|(_, upper)| upper));
+            let attributes = diff_attributes(old.map(|variable| &variable.attributes), new.map(|variable| &variable.attributes));
+            // Declared bounds of integer and semi-continuous variables change without changing the type
+            (type_changed || lower.is_some() || upper.is_some() || !attributes.is_empty()).then(|| VariableDiff {
+                name: name.to_string(),
+                kind,
+                var_type: Change { old: old_type, new: new_type },
+                lower,
+                upper,
+                attributes,
+            })
+        })
+        .collect()
+}
+
+#[must_use]
+/// Compares two problems and reports every objective, constraint and variable that differs.
+///
+/// Coefficients are compared per variable, so reordered or split terms are
+/// not reported as changes. Numeric values are compared using the tolerances
+/// in `options`, which can also enable rename detection for constraints.
+///
+/// # Arguments
+///
+/// * `old` - The problem to compare from.
+/// * `new` - The problem to compare to.
+/// * `options` - Options controlling the comparison.
+///
+pub fn build_diff_report(old: &LpProblem, new: &LpProblem, options: &DiffOptions) -> DiffReport {
+    DiffReport {
+        sense: if options.includes_section(DiffSection::Sense) {
+            Change::between(Some(old.sense.clone()), Some(new.sense.clone()))
+        } else {
+            None
+        },
+        objectives: diff_objectives(old, new, options),
+        constraints: if options.detect_renames {
+            detect_renames(old, new, diff_constraints(old, new, options), options)
+        } else {
+            diff_constraints(old, new, options)
+        },
+        variables: diff_variables(old, new, options),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        diff::{build_diff_report, glob_match, Change, DiffKind, DiffOptions, DiffSection},
+        model::{AttrValue, ComparisonOp, Sense, VariableType},
+        problem::LpProblem,
+    };
+
+    const OLD: &str = "
+maximize
+obj: 3x + 2y
+subject to
+c1: x + y <= 4
+c2: x + 3y <= 6
+c3: x >= 0
+bounds
+x <= 3
+end";
+
+    const NEW: &str = "
+maximize
+obj: 2y + 3x + z
+subject to
+c1: y + x <= 4
+c2: x + 2y <= 6
+c4: x + z >= 1
+bounds
+x <= 5
+end";
+
+    #[test]
+    fn test_build_diff_report() {
+        let old = LpProblem::parse(OLD).expect("test case not to fail");
+        let new = LpProblem::parse(NEW).expect("test case not to fail");
+
+        let report = build_diff_report(&old, &new, &DiffOptions::default());
+        assert!(report.sense.is_none());
+
+        assert_eq!(report.objectives.len(), 1);
+        assert_eq!(report.objectives[0].kind, DiffKind::Modified);
+        assert_eq!(report.objectives[0].coefficients.len(), 1);
+        assert_eq!(report.objectives[0].coefficients[0].variable, "z");
+        assert_eq!(report.objectives[0].coefficients[0].change, Change { old: None, new: Some(1.0) });
+
+        let constraints: Vec<_> = report.constraints.iter().map(|c| (c.name.as_str(), c.kind)).collect();
+        assert_eq!(constraints, [("c2", DiffKind::Modified), ("c3", DiffKind::Removed), ("c4", DiffKind::Added)]);
+        assert_eq!(report.constraints[0].coefficients[0].change, Change { old: Some(3.0), new: Some(2.0) });
+        assert!(report.constraints[0].rhs.is_none());
+        assert_eq!(report.constraints[2].operator, Some(Change { old: None, new: Some(ComparisonOp::GTE) }));
+
+        let variables: Vec<_> = report.variables.iter().map(|v| (v.name.as_str(), v.kind)).collect();
+        assert_eq!(variables, [("x", DiffKind::Modified), ("z", DiffKind::Added)]);
+        assert_eq!(
+            report.variables[0].var_type,
+            Change { old: Some(VariableType::UpperBound(3.0)), new: Some(VariableType::UpperBound(5.0)) }
+        );
+        assert_eq!(report.variables[0].upper, Some(Change { old: Some(3.0), new: Some(5.0) }));
+        assert!(report.variables[0].lower.is_none());
+        assert!(!report.variables[0].type_changed());
+        assert_eq!(report.variables[0].to_string(), "Variable x modified: ub 3 -> 5");
+    }
+
+    #[test]
+    fn test_build_diff_report_identical() {
+        let old = LpProblem::parse(OLD).expect("test case not to fail");
+        let mut new = LpProblem::parse(OLD).expect("test case not to fail");
+        assert!(build_diff_report(&old, &new, &DiffOptions::default()).is_empty());
+
+        new.sense = Sense::Minimize;
+        let report = build_diff_report(&old, &new, &DiffOptions::default());
+        assert_eq!(report.sense, Some(Change { old: Some(Sense::Maximize), new: Some(Sense::Minimize) }));
+        assert_eq!(report.to_string(), "Sense changed: Maximize -> Minimize\n");
+    }
+
+    #[test]
+    fn test_build_diff_report_declared_bounds() {
+        let source = |upper: u32| format!("maximize\nobj: x\nsubject to\nc1: x >= 0\nbounds\n0 <= x <= {upper}\nintegers\nx\nend");
+        let (old_source, new_source) = (source(10), source(20));
+        let old = LpProblem::parse(&old_source).expect("test case not to fail");
+        let new = LpProblem::parse(&new_source).expect("test case not to fail");
+
+        let report = build_diff_report(&old, &new, &DiffOptions::default());
+        assert_eq!(report.variables.len(), 1);
+        assert!(!report.variables[0].type_changed());
+        assert_eq!(report.variables[0].to_string(), "Variable x modified: ub 10 -> 20");
+    }
+
+    #[test]
+    fn test_build_diff_report_attributes() {
+        let old = LpProblem::parse(OLD).expect("test case not to fail");
+        let mut new = LpProblem::parse(OLD).expect("test case not to fail");
+        if let Some(objective) = new.objectives.get_mut("obj") {
+            objective.attributes.insert("owner".to_string(), "finance".into());
+        }
+        if let Some(c1) = new.constraints.get_mut("c1") {
+            c1.attributes_mut().insert("tag".to_string(), "capacity".into());
+        }
+        if let Some(x) = new.variables.get_mut("x") {
+            x.attributes.insert("priority".to_string(), 2_i64.into());
+        }
+
+        let report = build_diff_report(&old, &new, &DiffOptions::default());
+        assert_eq!(report.objectives[0].attributes[0].change, Change { old: None, new: Some(AttrValue::Text("finance".to_string())) });
+        assert_eq!(report.constraints[0].attributes[0].key, "tag");
+        assert_eq!(report.variables[0].to_string(), "Variable x modified: attribute priority - -> 2");
+        assert!(report.to_string().contains("Constraint c1 modified\n  attribute tag: - -> capacity\n"));
+    }
+
+    #[test]
+    fn test_build_diff_report_tolerance() {
+        let old = LpProblem::parse("minimize\nobj: 1.0 x + 1000 y\nsubject to\nc1: x + y >= 2\nbounds\nx <= 10\nend")
+            .expect("test case not to fail");
+        let new = LpProblem::parse(
+            "minimize\nobj: 1.0000000001 x + 1000.001 y\nsubject to\nc1: x + y >= 2.0000000001\nbounds\nx <= 10.0000000001\nend",
+        )
+        .expect("test case not to fail");
+
+        let report = build_diff_report(&old, &new, &DiffOptions::default());
+        assert_eq!(report.objectives[0].coefficients.len(), 2);
+        assert_eq!(report.constraints.len(), 1);
+        assert_eq!(report.variables.len(), 1);
+
+        let report = build_diff_report(&old, &new, &DiffOptions::default().with_absolute_tolerance(1e-6));
+        assert_eq!(report.objectives[0].coefficients.len(), 1);
+        assert_eq!(report.objectives[0].coefficients[0].variable, "y");
+        assert!(report.constraints.is_empty());
+        assert!(report.variables.is_empty());
+
+        let options = DiffOptions::default().with_absolute_tolerance(1e-6).with_relative_tolerance(1e-5);
+        assert!(build_diff_report(&old, &new, &options).is_empty());
+        assert!(!options.var_types_equal(&VariableType::Free, &VariableType::General));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_diff_report_serde() {
+        let old = LpProblem::parse(OLD).expect("test case not to fail");
+        let new = LpProblem::parse(NEW).expect("test case not to fail");
+        let report = build_diff_report(&old, &new, &DiffOptions::default());
+
+        let value = serde_json::to_value(&report).expect("test case not to fail");
+        assert_eq!(value["sense"], serde_json::Value::Null);
+        assert_eq!(
+            value["objectives"][0],
+            serde_json::json!({
+                "name": "obj",
+                "kind": "modified",
+                "coefficients": [{"variable": "z", "change": {"old": null, "new": 1.0}}],
+                "attributes": []
+            })
+        );
+        assert_eq!(value["constraints"][1]["kind"], "removed");
+        assert_eq!(value["variables"][0]["var_type"], serde_json::json!({"old": {"UpperBound": 3.0}, "new": {"UpperBound": 5.0}}));
+        assert_eq!(value["variables"][1]["upper"], serde_json::json!({"old": null, "new": "inf"}));
+
+        let deserialized: crate::diff::DiffReport = serde_json::from_value(value).expect("test case not to fail");
+        assert_eq!(deserialized, report);
+    }
+
+    #[test]
+    fn test_build_diff_report_renames() {
+        let old = LpProblem::parse("minimize\nobj: x + y\nsubject to\nc_1: x + y >= 1\nc_2: x - y <= 3\nc_3: x <= 7\nend")
+            .expect("test case not to fail");
+        let new = LpProblem::parse("minimize\nobj: x + y\nsubject to\nr_1: y + x >= 1\nr_2: x - y <= 3\nr_3: x <= 8\nend")
+            .expect("test case not to fail");
+
+        assert_eq!(build_diff_report(&old, &new, &DiffOptions::default()).constraints.len(), 6);
+
+        let report = build_diff_report(&old, &new, &DiffOptions::default().with_rename_detection(true));
+        let constraints: Vec<_> = report.constraints.iter().map(|c| (c.name.as_str(), c.kind, c.renamed_from.as_deref())).collect();
+        assert_eq!(
+            constraints,
+            [
+                ("c_3", DiffKind::Removed, None),
+                ("r_1", DiffKind::Renamed, Some("c_1")),
+                ("r_2", DiffKind::Renamed, Some("c_2")),
+                ("r_3", DiffKind::Added, None)
+            ]
+        );
+        assert!(report.to_string().contains("Constraint r_1 renamed from c_1\n"));
+    }
+
+    #[test]
+    fn test_build_diff_report_filtering() {
+        let old = LpProblem::parse("minimize\nobj: x + y\nsubject to\ncapacity_1: x <= 4\ncapacity_2: y <= 4\ndemand: x + y >= 1\nend")
+            .expect("test case not to fail");
+        let new = LpProblem::parse("maximize\nobj: x + 2y\nsubject to\ncapacity_1: x <= 5\ncapacity_2: y <= 4\ndemand: x + y >= 2\nend")
+            .expect("test case not to fail");
+
+        let options = DiffOptions::default().with_sections(&[DiffSection::Constraints]).with_name_glob("capacity_*");
+        let report = build_diff_report(&old, &new, &options);
+        assert!(report.sense.is_none());
+        assert!(report.objectives.is_empty());
+        let constraints: Vec<_> = report.constraints.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(constraints, ["capacity_1"]);
+
+        let options = DiffOptions::default().with_name_filter(|section, name| section != DiffSection::Constraints || name == "demand");
+        let report = build_diff_report(&old, &new, &options);
+        assert!(report.sense.is_some());
+        assert_eq!(report.objectives.len(), 1);
+        let constraints: Vec<_> = report.constraints.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(constraints, ["demand"]);
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("capacity_*", "capacity_12"));
+        assert!(glob_match("*_1?", "capacity_12"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("c*p*y_*2", "capacity_12"));
+        assert!(!glob_match("capacity_?", "capacity_12"));
+        assert!(!glob_match("demand*", "capacity_12"));
+    }
+}
@@ -0,0 +1,586 @@
+//! Replaying a diff onto a problem.
+//!
+//! An `LpPatch` is the serializable form of a `DiffReport`: it records only
+//! the new values of everything that changed, so a reviewed set of changes can
+//! be applied to another copy of the model with `LpProblem::apply_patch`.
+//!
+
+use std::borrow::Cow;
+
+use crate::{
+    diff::{AttributeChange, CoefficientChange, DiffKind, DiffReport},
+    model::{AttrValue, Attributes, Coefficient, ComparisonOp, Constraint, Objective, SOSType, Sense, Variable, VariableType},
+    problem::LpProblem,
+};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// Sets or removes the coefficient of a single variable.
+pub struct TermPatch {
+    /// The name of the variable.
+    pub variable: String,
+    /// The new coefficient, or `None` to remove the variable from the expression.
+    pub coefficient: Option<f64>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// Sets or removes a single attribute.
+pub struct AttributePatch {
+    /// The key of the attribute.
+    pub key: String,
+    /// The new value, or `None` to remove the attribute.
+    pub value: Option<AttrValue>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// Adds, removes or modifies an objective.
+pub struct ObjectivePatch {
+    /// The name of the objective.
+    pub name: String,
+    /// Whether the objective is added, removed or modified.
+    pub kind: DiffKind,
+    /// The coefficients to set or remove.
+    pub coefficients: Vec<TermPatch>,
+    /// The attributes to set or remove.
+    pub attributes: Vec<AttributePatch>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// Adds, removes or modifies a constraint.
+///
+/// Fields left as `None` are unchanged. Setting `sos_type` on a standard
+/// constraint turns it into an SOS constraint, and setting both `operator` and
+/// `rhs` on an SOS constraint turns it into a standard constraint. A renamed
+/// constraint is moved from `renamed_from` to `name` before it is modified.
+pub struct ConstraintPatch {
+    /// The name of the constraint.
+    pub name: String,
+    /// Whether the constraint is added, removed or modified.
+    pub kind: DiffKind,
+    /// The coefficients (or SOS weights) to set or remove.
+    pub coefficients: Vec<TermPatch>,
+    /// The new comparison operator.
+    pub operator: Option<ComparisonOp>,
+    /// The new right-hand side.
+    pub rhs: Option<f64>,
+    /// The new SOS type.
+    pub sos_type: Option<SOSType>,
+    /// The previous name of a renamed constraint.
+    pub renamed_from: Option<String>,
+    /// The attributes to set or remove.
+    pub attributes: Vec<AttributePatch>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// Adds, removes or modifies a variable.
+///
+/// `lower` and `upper` are the new effective bounds, `None` when unchanged.
+/// They matter for integer, binary, semi-continuous and SOS variables, whose
+/// declared bounds are kept apart from the type; the bounds of a continuous
+/// variable are already part of `var_type`.
+pub struct VariablePatch {
+    /// The name of the variable.
+    pub name: String,
+    /// Whether the variable is added, removed or modified.
+    pub kind: DiffKind,
+    /// The new variable type, `None` when the variable is removed.
+    pub var_type: Option<VariableType>,
+    /// The new lower bound.
+    #[cfg_attr(feature = "serde", serde(with = "crate::model::infinite::optional"))]
+    pub lower: Option<f64>,
+    /// The new upper bound.
+    #[cfg_attr(feature = "serde", serde(with = "crate::model::infinite::optional"))]
+    pub upper: Option<f64>,
+    /// The attributes to set or remove.
+    pub attributes: Vec<AttributePatch>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default, PartialEq)]
+/// A set of changes that can be applied to a problem with `LpProblem::apply_patch`.
+pub struct LpPatch {
+    /// The new optimization sense.
+    pub sense: Option<Sense>,
+    /// The objective changes.
+    pub objectives: Vec<ObjectivePatch>,
+    /// The constraint changes.
+    pub constraints: Vec<ConstraintPatch>,
+    /// The variable changes.
+    pub variables: Vec<VariablePatch>,
+}
+
+impl LpPatch {
+    #[must_use]
+    #[inline]
+    /// Returns `true` if applying the patch would not change a problem.
+    pub fn is_empty(&self) -> bool {
+        self.sense.is_none() && self.objectives.is_empty() && self.constraints.is_empty() && self.variables.is_empty()
+    }
+}
+
+fn term_patches(kind: DiffKind, coefficients: &[CoefficientChange]) -> Vec<TermPatch> {
+    if kind == DiffKind::Removed {
+        return Vec::new();
+    }
+    coefficients.iter().map(|c| TermPatch { variable: c.variable.clone(), coefficient: c.change.new }).collect()
+}
+
+fn attribute_patches(kind: DiffKind, attributes: &[AttributeChange]) -> Vec<AttributePatch> {
+    if kind == DiffKind::Removed {
+        return Vec::new();
+    }
+    attributes.iter().map(|a| AttributePatch { key: a.key.clone(), value: a.change.new.clone() }).collect()
+}
+
+impl From<&DiffReport> for LpPatch {
+    fn from(report: &DiffReport) -> Self {
+        Self {
+            sense: report.sense.as_ref().and_then(|sense| sense.new.clone()),
+            objectives: report
+                .objectives
+                .iter()
+                .map(|o| ObjectivePatch {
+                    name: o.name.clone(),
+                    kind: o.kind,
+                    coefficients: term_patches(o.kind, &o.coefficients),
+                    attributes: attribute_patches(o.kind, &o.attributes),
+                })
+                .collect(),
+            constraints: report
+                .constraints
+                .iter()
+                .map(|c| ConstraintPatch {
+                    name: c.name.clone(),
+                    kind: c.kind,
+                    coefficients: term_patches(c.kind, &c.coefficients),
+                    operator: c.operator.as_ref().and_then(|operator| operator.new.clone()),
+                    rhs: c.rhs.as_ref().and_then(|rhs| rhs.new),
+                    sos_type: c.sos_type.as_ref().and_then(|sos_type| sos_type.new),
+                    renamed_from: c.renamed_from.clone(),
+                    attributes: attribute_patches(c.kind, &c.attributes),
+                })
+                .collect(),
+            variables: report
+                .variables
+                .iter()
+                .map(|v| VariablePatch {
+                    name: v.name.clone(),
+                    kind: v.kind,
+                    var_type: v.var_type.new.clone(),
+                    lower: v.lower.as_ref().and_then(|lower| lower.new),
+                    upper: v.upper.as_ref().and_then(|upper| upper.new),
+                    attributes: attribute_patches(v.kind, &v.attributes),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The reasons a patch cannot be applied to a problem.
+pub enum PatchError {
+    /// An entity to be modified or removed does not exist.
+    NotFound { section: &'static str, name: String },
+    /// An entity to be added already exists.
+    AlreadyExists { section: &'static str, name: String },
+    /// An added constraint has neither an SOS type nor an operator and right-hand side,
+    /// or a renamed constraint has no previous name.
+    Incomplete { name: String },
+    /// An objective or variable is marked as renamed; only constraints can be renamed.
+    UnsupportedRename { section: &'static str, name: String },
+}
+
+impl std::fmt::Display for PatchError {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound { section, name } => write!(f, "Cannot patch {section} {name}: it does not exist"),
+            Self::AlreadyExists { section, name } => write!(f, "Cannot add {section} {name}: it already exists"),
+            Self::Incomplete { name } => write!(f, "Cannot add constraint {name}: the patch does not fully define it"),
+            Self::UnsupportedRename { section, name } => write!(f, "Cannot rename {section} {name}: only constraints can be renamed"),
+        }
+    }
+}
+
+impl std::error::Error for PatchError {}
+
+#[inline]
+fn check_exists(section: &'static str, name: &str, kind: DiffKind, exists: bool) -> Result<(), PatchError> {
+    match (kind, exists) {
+        (DiffKind::Added | DiffKind::Renamed, true) => Err(PatchError::AlreadyExists { section, name: name.to_string() }),
+        (DiffKind::Removed | DiffKind::Modified, false) => Err(PatchError::NotFound { section, name: name.to_string() }),
+        _ => Ok(()),
+    }
+}
+
+/// Sets or removes terms, keeping a modified term at the position of its first occurrence.
+fn apply_terms<'a>(coefficients: &mut Vec<Coefficient<'a>>, terms: &'a [TermPatch]) {
+    for term in terms {
+        let position = coefficients.iter().position(|c| c.var_name == term.variable);
+        coefficients.retain(|c| c.var_name != term.variable);
+        if let Some(coefficient) = term.coefficient {
+            coefficients.insert(position.unwrap_or(coefficients.len()), Coefficient { var_name: &term.variable, coefficient });
+        }
+    }
+}
+
+fn apply_attributes(attributes: &mut Attributes, patches: &[AttributePatch]) {
+    for patch in patches {
+        match &patch.value {
+            Some(value) => {
+                attributes.insert(patch.key.clone(), value.clone());
+            }
+            None => {
+                attributes.remove(&patch.key);
+            }
+        }
+    }
+}
+
+/// Returns the bound declaration for `[lower, upper]`, or `None` for the default `[0, +inf)`.
+fn declared_bounds(lower: f64, upper: f64) -> Option<VariableType> {
+    if upper == f64::INFINITY {
+        if lower == 0.0 {
+            None
+        } else if lower == f64::NEG_INFINITY {
+            Some(VariableType::Free)
+        } else {
+            Some(VariableType::LowerBound(lower))
+        }
+    } else if lower == 0.0 {
+        Some(VariableType::UpperBound(upper))
+    } else {
+        Some(VariableType::DoubleBound(lower, upper))
+    }
+}
+
+fn new_coefficients(terms: &[TermPatch]) -> Vec<Coefficient<'_>> {
+    terms.iter().filter_map(|t| t.coefficient.map(|coefficient| Coefficient { var_name: &t.variable, coefficient })).collect()
+}
+
+impl<'a> LpProblem<'a> {
+    /// Checks that every entity the patch modifies or removes exists, and that none it adds do.
+    fn validate_patch(&self, patch: &LpPatch) -> Result<(), PatchError> {
+        for objective in &patch.objectives {
+            if objective.kind == DiffKind::Renamed {
+                return Err(PatchError::UnsupportedRename { section: "objective", name: objective.name.clone() });
+            }
+            check_exists("objective", &objective.name, objective.kind, self.objectives.contains_key(objective.name.as_str()))?;
+        }
+        for constraint in &patch.constraints {
+            check_exists("constraint", &constraint.name, constraint.kind, self.constraints.contains_key(constraint.name.as_str()))?;
+            if constraint.kind == DiffKind::Added
+                && constraint.sos_type.is_none()
+                && (constraint.operator.is_none() || constraint.rhs.is_none())
+            {
+                return Err(PatchError::Incomplete { name: constraint.name.clone() });
+            }
+            if constraint.kind == DiffKind::Renamed {
+                let previous =
+                    constraint.renamed_from.as_deref().ok_or_else(|| PatchError::Incomplete { name: constraint.name.clone() })?;
+                check_exists("constraint", previous, DiffKind::Modified, self.constraints.contains_key(previous))?;
+            }
+        }
+        for variable in &patch.variables {
+            if variable.kind == DiffKind::Renamed {
+                return Err(PatchError::UnsupportedRename { section: "variable", name: variable.name.clone() });
+            }
+            if variable.kind == DiffKind::Removed || variable.kind == DiffKind::Modified {
+                check_exists("variable", &variable.name, variable.kind, self.variables.contains_key(variable.name.as_str()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds any variable referenced by `terms` that the problem does not yet declare.
+    fn declare_terms(&mut self, terms: &'a [TermPatch], var_type: &VariableType) {
+        for term in terms.iter().filter(|t| t.coefficient.is_some()) {
+            if !self.variables.contains_key(term.variable.as_str()) {
+                self.add_variable(Variable::new(&term.variable).with_var_type(var_type.clone()));
+            }
+        }
+    }
+
+    fn apply_constraint_patch(&mut self, patch: &'a ConstraintPatch) {
+        let constraint = match self.constraints.get_mut(patch.name.as_str()) {
+            Some(constraint) => constraint,
+            None => return,
+        };
+        let replacement = match constraint {
+            Constraint::Standard { name, coefficients, operator, rhs, attributes } => {
+                apply_terms(coefficients, &patch.coefficients);
+                if let Some(new_operator) = &patch.operator {
+                    *operator = new_operator.clone();
+                }
+                if let Some(new_rhs) = patch.rhs {
+                    *rhs = new_rhs;
+                }
+                patch.sos_type.map(|sos_type| Constraint::SOS {
+                    name: name.clone(),
+                    sos_type,
+                    weights: std::mem::take(coefficients),
+                    attributes: std::mem::take(attributes),
+                })
+            }
+            Constraint::SOS { name, sos_type, weights, attributes } => {
+                apply_terms(weights, &patch.coefficients);
+                if let Some(new_sos_type) = patch.sos_type {
+                    *sos_type = new_sos_type;
+                }
+                match (&patch.operator, patch.rhs) {
+                    (Some(operator), Some(rhs)) if patch.sos_type.is_none() => Some(Constraint::Standard {
+                        name: name.clone(),
+                        coefficients: std::mem::take(weights),
+                        operator: operator.clone(),
+                        rhs,
+                        attributes: std::mem::take(attributes),
+                    }),
+                    _ => None,
+                }
+            }
+        };
+        if let Some(replacement) = replacement {
+            *constraint = replacement;
+        }
+    }
+
+    /// Applies `patch` to the problem.
+    ///
+    /// The patch is validated before anything is changed, so on error the
+    /// problem is left untouched. Variables referenced by added terms are
+    /// declared automatically, as they would be when parsing.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PatchError` if the patch modifies or removes an entity that
+    /// does not exist, or adds an objective or constraint that already exists.
+    ///
+    pub fn apply_patch(&mut self, patch: &'a LpPatch) -> Result<(), PatchError> {
+        self.validate_patch(patch)?;
+
+        if let Some(sense) = &patch.sense {
+            self.sense = sense.clone();
+        }
+
+        for objective in &patch.objectives {
+            match objective.kind {
+                DiffKind::Added => {
+                    self.add_objective(Objective::new(Cow::Borrowed(&objective.name), new_coefficients(&objective.coefficients)));
+                }
+                DiffKind::Removed => {
+                    self.objectives.remove(objective.name.as_str());
+                }
+                DiffKind::Modified | DiffKind::Renamed => {
+                    if let Some(existing) = self.objectives.get_mut(objective.name.as_str()) {
+                        apply_terms(&mut existing.coefficients, &objective.coefficients);
+                    }
+                    self.declare_terms(&objective.coefficients, &VariableType::default());
+                }
+            }
+            if let Some(existing) = self.objectives.get_mut(objective.name.as_str()) {
+                apply_attributes(&mut existing.attributes, &objective.attributes);
+            }
+        }
+
+        for constraint in &patch.constraints {
+            match constraint.kind {
+                DiffKind::Added => {
+                    let name = Cow::Borrowed(constraint.name.as_str());
+                    let coefficients = new_coefficients(&constraint.coefficients);
+                    self.add_constraint(match (constraint.sos_type, &constraint.operator, constraint.rhs) {
+                        (Some(sos_type), _, _) => Constraint::SOS { name, sos_type, weights: coefficients, attributes: Attributes::new() },
+                        (None, Some(operator), Some(rhs)) => {
+                            Constraint::Standard { name, coefficients, operator: operator.clone(), rhs, attributes: Attributes::new() }
+                        }
+                        (None, _, _) => unreachable!("incomplete constraints are rejected by validation"),
+                    });
+                }
+                DiffKind::Removed => {
+                    self.constraints.remove(constraint.name.as_str());
+                }
+                DiffKind::Modified | DiffKind::Renamed => {
+                    if let Some(mut renamed) = constraint.renamed_from.as_deref().and_then(|previous| self.constraints.remove(previous)) {
+                        match &mut renamed {
+                            Constraint::Standard { name, .. } | Constraint::SOS { name, .. } => *name = Cow::Borrowed(&constraint.name),
+                        }
+                        self.constraints.insert(Cow::Borrowed(&constraint.name), renamed);
+                    }
+                    self.apply_constraint_patch(constraint);
+                    let var_type = if constraint.sos_type.is_some() { VariableType::SOS } else { VariableType::default() };
+                    self.declare_terms(&constraint.coefficients, &var_type);
+                }
+            }
+            if let Some(existing) = self.constraints.get_mut(constraint.name.as_str()) {
+                apply_attributes(existing.attributes_mut(), &constraint.attributes);
+            }
+        }
+
+        for variable in &patch.variables {
+            match &variable.var_type {
+                Some(var_type) => match self.variables.get_mut(variable.name.as_str()) {
+                    // Update in place to keep the attributes, tracking declared bounds as the parser does
+                    Some(existing) => {
+                        if var_type.is_integer() || *var_type == VariableType::SemiContinuous {
+                            if existing.has_bound_type() {
+                                existing.declared_bounds = Some(existing.var_type.clone());
+                            }
+                        } else if *var_type != VariableType::Free {
+                            existing.declared_bounds = None;
+                        }
+                        existing.set_var_type(var_type.clone());
+                    }
+                    None => self.add_variable(Variable::new(&variable.name).with_var_type(var_type.clone())),
+                },
+                None => {
+                    self.variables.remove(variable.name.as_str());
+                }
+            }
+            if let Some(existing) = self.variables.get_mut(variable.name.as_str()) {
+                let (lower, upper) = existing.effective_bounds();
+                let bounds = (variable.lower.unwrap_or(lower), variable.upper.unwrap_or(upper));
+                if bounds != (lower, upper) && !existing.has_bound_type() {
+                    existing.declared_bounds = declared_bounds(bounds.0, bounds.1);
+                }
+                apply_attributes(&mut existing.attributes, &variable.attributes);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        diff::{
+            build_diff_report,
+            patch::{LpPatch, PatchError},
+            DiffOptions,
+        },
+        model::VariableType,
+        problem::LpProblem,
+    };
+
+    const OLD: &str = "
+maximize
+obj: 3x + 2y
+subject to
+c1: x + y <= 4
+c2: x + 3y <= 6
+c3: x >= 0
+bounds
+x <= 3
+end";
+
+    const NEW: &str = "
+minimize
+obj: 2y + 3x + z
+subject to
+c1: y + x <= 4
+c2: x + 2y = 6
+c4: x + w >= 1
+bounds
+x <= 5
+end";
+
+    #[test]
+    fn test_apply_patch() {
+        let old = LpProblem::parse(OLD).expect("test case not to fail");
+        let new = LpProblem::parse(NEW).expect("test case not to fail");
+        let patch = LpPatch::from(&build_diff_report(&old, &new, &DiffOptions::default()));
+        assert!(!patch.is_empty());
+
+        let mut copy = LpProblem::parse(OLD).expect("test case not to fail");
+        copy.apply_patch(&patch).expect("test case not to fail");
+        assert!(build_diff_report(&copy, &new, &DiffOptions::default()).is_empty());
+        assert!(LpPatch::from(&build_diff_report(&new, &new, &DiffOptions::default())).is_empty());
+    }
+
+    #[test]
+    fn test_apply_patch_keeps_variable_state() {
+        let source = "minimize\nobj: x + y\nsubject to\nc1: x + y >= 1\nbounds\n0 <= x <= 10\ny <= 4\nintegers\nx\nend";
+        let changed = source.replace("integers\nx", "integers\ngenerals\nbinaries\nx").replace("y <= 4", "y <= 6");
+        let mut new = LpProblem::parse(&changed).expect("test case not to fail");
+        let mut old = LpProblem::parse(source).expect("test case not to fail");
+        for problem in [&mut old, &mut new] {
+            for variable in problem.variables.values_mut() {
+                variable.attributes.insert("owner".to_string(), "planning".into());
+            }
+        }
+        if let Some(y) = new.variables.get_mut("y") {
+            y.attributes.insert("reviewed".to_string(), true.into());
+        }
+        let patch = LpPatch::from(&build_diff_report(&old, &new, &DiffOptions::default()));
+        assert_eq!(patch.variables.len(), 2);
+
+        old.apply_patch(&patch).expect("test case not to fail");
+        assert_eq!(old.variables["x"].var_type, VariableType::Binary);
+        assert_eq!(old.variables["x"].declared_bounds, Some(VariableType::DoubleBound(0.0, 10.0)));
+        assert_eq!(old.variables["y"].var_type, VariableType::UpperBound(6.0));
+        assert!(["x", "y"].iter().all(|name| old.variables[name].attributes.contains_key("owner")));
+        assert!(build_diff_report(&old, &new, &DiffOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn test_apply_patch_declared_bounds() {
+        let old = LpProblem::parse("minimize\nobj: x + y\nsubject to\nc1: x + y >= 1\nbounds\n0 <= x <= 10\nintegers\nx\nend")
+            .expect("test case not to fail");
+        let new = LpProblem::parse(
+            "minimize\nobj: x + y + z\nsubject to\nc1: x + y >= 1\nbounds\n0 <= x <= 20\n-inf <= z <= 5\nintegers\nx\nz\nend",
+        )
+        .expect("test case not to fail");
+        let patch = LpPatch::from(&build_diff_report(&old, &new, &DiffOptions::default()));
+        assert_eq!(patch.variables[0].var_type, Some(VariableType::Integer));
+        assert_eq!((patch.variables[0].lower, patch.variables[0].upper), (None, Some(20.0)));
+
+        let mut copy = LpProblem::parse("minimize\nobj: x + y\nsubject to\nc1: x + y >= 1\nbounds\n0 <= x <= 10\nintegers\nx\nend")
+            .expect("test case not to fail");
+        copy.apply_patch(&patch).expect("test case not to fail");
+        assert_eq!(copy.variables["x"].effective_bounds(), (0.0, 20.0));
+        assert_eq!(copy.variables["z"].effective_bounds(), (f64::NEG_INFINITY, 5.0));
+        assert!(build_diff_report(&copy, &new, &DiffOptions::default()).is_empty());
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&patch).expect("test case not to fail");
+            assert!(json.contains(r#""lower":"-inf""#));
+            assert_eq!(serde_json::from_str::<LpPatch>(&json).expect("test case not to fail"), patch);
+        }
+    }
+
+    #[test]
+    fn test_apply_patch_conflicts() {
+        let old = LpProblem::parse(OLD).expect("test case not to fail");
+        let new = LpProblem::parse(NEW).expect("test case not to fail");
+        let patch = LpPatch::from(&build_diff_report(&old, &new, &DiffOptions::default()));
+
+        let mut other = LpProblem::parse("maximize\nobj: 3x + 2y\nsubject to\nc1: x + y <= 4\nend").expect("test case not to fail");
+        assert_eq!(other.apply_patch(&patch), Err(PatchError::NotFound { section: "constraint", name: "c2".to_string() }));
+        assert_eq!(other.constraint_count(), 1);
+        assert!(!other.variables.contains_key("z"));
+
+        let mut patched = LpProblem::parse(NEW).expect("test case not to fail");
+        assert!(matches!(patched.apply_patch(&patch), Err(PatchError::NotFound { .. } | PatchError::AlreadyExists { .. })));
+    }
+
+    #[test]
+    fn test_apply_patch_renames() {
+        let old =
+            LpProblem::parse("minimize\nobj: x + y\nsubject to\nc_1: x + y >= 1\nc_2: x - y <= 3\nend").expect("test case not to fail");
+        let new =
+            LpProblem::parse("minimize\nobj: x + y\nsubject to\nr_1: y + x >= 1\nr_2: x - y <= 4\nend").expect("test case not to fail");
+        let options = DiffOptions::default().with_rename_detection(true);
+        let patch = LpPatch::from(&build_diff_report(&old, &new, &options));
+        assert_eq!(patch.constraints.iter().filter(|c| c.renamed_from.is_some()).count(), 1);
+
+        let mut copy =
+            LpProblem::parse("minimize\nobj: x + y\nsubject to\nc_1: x + y >= 1\nc_2: x - y <= 3\nend").expect("test case not to fail");
+        copy.apply_patch(&patch).expect("test case not to fail");
+        assert_eq!(copy.constraints["r_1"].name(), "r_1");
+        assert!(build_diff_report(&copy, &new, &DiffOptions::default()).is_empty());
+    }
+}
@@ -0,0 +1,366 @@
+//! Name-agnostic comparison of two LP problems.
+//!
+//! Model generators often emit the same problem with different names, for
+//! example when an internal counter shifts `x_17` to `x_42`. `structural_match`
+//! detects when two problems are identical up to a renaming of objectives,
+//! constraints and variables, and reports the inferred mapping.
+//!
+//! Each problem is treated as a bipartite graph of rows (objectives and
+//! constraints) and variables, with coefficients as edge labels. Colour
+//! refinement gives every node a signature built from its own data and that of
+//! its neighbourhood; nodes are then paired within equal signatures, with
+//! backtracking to resolve symmetric choices. Coefficients are compared exactly.
+//!
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    mem::discriminant,
+};
+
+use crate::{
+    diff::{coefficient_map, float_key, hash_of, ConstraintParts},
+    model::VariableType,
+    problem::LpProblem,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// The renaming that maps one problem onto another, keyed by the old name.
+pub struct StructuralMatch {
+    /// The mapping from old to new objective names.
+    pub objectives: BTreeMap<String, String>,
+    /// The mapping from old to new constraint names.
+    pub constraints: BTreeMap<String, String>,
+    /// The mapping from old to new variable names.
+    pub variables: BTreeMap<String, String>,
+}
+
+impl StructuralMatch {
+    #[must_use]
+    #[inline]
+    /// Returns `true` if every objective, constraint and variable kept its name.
+    pub fn is_identity(&self) -> bool {
+        self.renamed().next().is_none()
+    }
+
+    #[inline]
+    /// Returns the `(old, new)` names of every entity whose name changed.
+    pub fn renamed(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.objectives
+            .iter()
+            .chain(&self.constraints)
+            .chain(&self.variables)
+            .filter(|(old, new)| old != new)
+            .map(|(old, new)| (old.as_str(), new.as_str()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum NodeKind {
+    Objective,
+    Constraint,
+    Variable,
+}
+
+/// A problem viewed as a bipartite graph of rows and variables.
+pub(crate) struct Graph<'a> {
+    pub(crate) nodes: Vec<(NodeKind, &'a str)>,
+    pub(crate) colours: Vec<u64>,
+    edges: Vec<Vec<(usize, u64)>>,
+}
+
+impl<'a> Graph<'a> {
+    pub(crate) fn new(problem: &'a LpProblem) -> Self {
+        let mut variable_names: BTreeSet<&str> = problem.variables.keys().copied().collect();
+        let objectives: BTreeMap<&str, BTreeMap<&str, f64>> =
+            problem.objectives.iter().map(|(name, objective)| (name.as_ref(), coefficient_map(&objective.coefficients))).collect();
+        let constraints: BTreeMap<&str, ConstraintParts> =
+            problem.constraints.iter().map(|(name, constraint)| (name.as_ref(), ConstraintParts::from(constraint))).collect();
+        for coefficients in objectives.values().chain(constraints.values().map(|parts| &parts.coefficients)) {
+            variable_names.extend(coefficients.keys().copied());
+        }
+
+        let mut graph = Self { nodes: Vec::new(), colours: Vec::new(), edges: Vec::new() };
+        let mut variable_index = HashMap::with_capacity(variable_names.len());
+        for name in variable_names {
+            let (var_type, (lower, upper)) =
+                problem.variables.get(name).map_or((VariableType::default(), VariableType::default().bounds()), |variable| {
+                    (variable.var_type.clone(), variable.effective_bounds())
+                });
+            variable_index.insert(name, graph.nodes.len());
+            graph.push(
+                NodeKind::Variable,
+                name,
+                hash_of((NodeKind::Variable, discriminant(&var_type), float_key(lower), float_key(upper))),
+            );
+        }
+        for (name, coefficients) in &objectives {
+            let row = graph.push(NodeKind::Objective, name, hash_of(NodeKind::Objective));
+            graph.connect(row, coefficients, &variable_index);
+        }
+        for (name, parts) in &constraints {
+            let colour = hash_of((
+                NodeKind::Constraint,
+                parts.operator.as_ref().map(discriminant),
+                parts.rhs.map(float_key),
+                parts.sos_type.as_ref().map(discriminant),
+            ));
+            let row = graph.push(NodeKind::Constraint, name, colour);
+            graph.connect(row, &parts.coefficients, &variable_index);
+        }
+        graph
+    }
+
+    fn push(&mut self, kind: NodeKind, name: &'a str, colour: u64) -> usize {
+        self.nodes.push((kind, name));
+        self.colours.push(colour);
+        self.edges.push(Vec::new());
+        self.nodes.len() - 1
+    }
+
+    fn connect(&mut self, row: usize, coefficients: &BTreeMap<&str, f64>, variable_index: &HashMap<&str, usize>) {
+        for (name, coefficient) in coefficients {
+            let variable = variable_index[name];
+            self.edges[row].push((variable, float_key(*coefficient)));
+            self.edges[variable].push((row, float_key(*coefficient)));
+        }
+    }
+
+    /// Performs one round of colour refinement.
+    fn refine_step(&self, colours: &[u64]) -> Vec<u64> {
+        self.edges
+            .iter()
+            .zip(colours)
+            .map(|(edges, colour)| {
+                let mut neighbourhood: Vec<(u64, u64)> = edges.iter().map(|&(node, coefficient)| (coefficient, colours[node])).collect();
+                neighbourhood.sort_unstable();
+                hash_of((colour, neighbourhood))
+            })
+            .collect()
+    }
+
+    /// Returns `true` if `mapping` maps every node and edge of `self` onto an identical node and edge of `other`.
+    fn is_isomorphism(&self, other: &Graph, mapping: &[usize]) -> bool {
+        self.nodes.iter().enumerate().all(|(node, (kind, _))| {
+            let image = mapping[node];
+            if other.nodes[image].0 != *kind || other.colours[image] != self.colours[node] {
+                return false;
+            }
+            let mut mapped: Vec<(usize, u64)> =
+                self.edges[node].iter().map(|&(neighbour, coefficient)| (mapping[neighbour], coefficient)).collect();
+            let mut expected = other.edges[image].clone();
+            mapped.sort_unstable();
+            expected.sort_unstable();
+            mapped == expected
+        })
+    }
+}
+
+#[inline]
+fn class_count(colours: &[u64]) -> usize {
+    colours.iter().collect::<BTreeSet<_>>().len()
+}
+
+/// Refines both colourings in lockstep until neither partition splits further.
+pub(crate) fn refine(old: &Graph, new: &Graph, mut old_colours: Vec<u64>, mut new_colours: Vec<u64>) -> (Vec<u64>, Vec<u64>) {
+    loop {
+        let (old_next, new_next) = (old.refine_step(&old_colours), new.refine_step(&new_colours));
+        if class_count(&old_next) == class_count(&old_colours) && class_count(&new_next) == class_count(&new_colours) {
+            return (old_colours, new_colours);
+        }
+        old_colours = old_next;
+        new_colours = new_next;
+    }
+}
+
+/// The number of search tree nodes explored before `search` gives up.
+pub(crate) const SEARCH_BUDGET: usize = 10_000;
+
+/// The outcome of a search for a mapping between two graphs.
+pub(crate) enum Search {
+    /// A mapping from old to new nodes.
+    Found(Vec<usize>),
+    /// No mapping exists.
+    Different,
+    /// The budget ran out before the search could decide.
+    Unknown,
+}
+
+/// A level of the search: the old node being individualised and the new nodes tried in its place.
+struct Frame {
+    node: usize,
+    candidates: Vec<usize>,
+    next: usize,
+}
+
+/// Searches for a mapping of `old` onto `new`, individualising a node whenever several candidates share a colour.
+///
+/// `individualised` pairs old and new nodes that must map onto each other.
+/// The search is a depth-first backtracking over a stack of choices; each
+/// visited node is refined again from the starting colours with its choices
+/// applied, so no partition is kept per level. At most `budget` nodes are
+/// visited before the search returns `Search::Unknown`.
+pub(crate) fn search(
+    old: &Graph,
+    new: &Graph,
+    old_colours: &[u64],
+    new_colours: &[u64],
+    individualised: &[(usize, usize)],
+    budget: usize,
+) -> Search {
+    let mut stack: Vec<Frame> = Vec::new();
+    for _ in 0..budget {
+        let (mut old_next, mut new_next) = (old_colours.to_vec(), new_colours.to_vec());
+        let choices = stack.iter().map(|frame| (frame.node, frame.candidates[frame.next]));
+        for (depth, (node, candidate)) in individualised.iter().copied().chain(choices).enumerate() {
+            let marker = hash_of((depth, "individualised"));
+            old_next[node] = marker;
+            new_next[candidate] = marker;
+        }
+        let (old_next, new_next) = refine(old, new, old_next, new_next);
+
+        let mut classes: BTreeMap<u64, (Vec<usize>, Vec<usize>)> = BTreeMap::new();
+        for (node, colour) in old_next.iter().enumerate() {
+            classes.entry(*colour).or_default().0.push(node);
+        }
+        for (node, colour) in new_next.iter().enumerate() {
+            classes.entry(*colour).or_default().1.push(node);
+        }
+        if classes.values().all(|(old_nodes, new_nodes)| old_nodes.len() == new_nodes.len()) {
+            match classes.values().filter(|(old_nodes, _)| old_nodes.len() > 1).min_by_key(|(old_nodes, _)| old_nodes.len()) {
+                None => {
+                    let mapping: Vec<usize> = old_next.iter().map(|colour| classes[colour].1[0]).collect();
+                    if old.is_isomorphism(new, &mapping) {
+                        return Search::Found(mapping);
+                    }
+                }
+                Some((old_nodes, new_nodes)) => {
+                    stack.push(Frame { node: old_nodes[0], candidates: new_nodes.clone(), next: 0 });
+                    continue;
+                }
+            }
+        }
+
+        // Backtrack to the deepest level with an untried candidate
+        while let Some(frame) = stack.last_mut() {
+            frame.next += 1;
+            if frame.next < frame.candidates.len() {
+                break;
+            }
+            stack.pop();
+        }
+        if stack.is_empty() {
+            return Search::Different;
+        }
+    }
+    Search::Unknown
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The result of comparing two problems up to a renaming.
+pub enum MatchOutcome {
+    /// The problems are identical up to the contained renaming.
+    Matched(StructuralMatch),
+    /// The problems differ in something other than names.
+    Different,
+    /// The search budget ran out before a renaming was found or ruled out.
+    Unknown,
+}
+
+impl MatchOutcome {
+    #[must_use]
+    #[inline]
+    /// Returns the renaming if one was found.
+    pub fn into_match(self) -> Option<StructuralMatch> {
+        match self {
+            Self::Matched(mapping) => Some(mapping),
+            Self::Different | Self::Unknown => None,
+        }
+    }
+}
+
+#[must_use]
+/// Determines whether two problems are identical up to a renaming of their objectives, constraints and variables.
+///
+/// Returns the inferred mapping from old to new names, or
+/// `MatchOutcome::Different` if the problems differ in anything other than
+/// names. When several mappings are valid, for example between
+/// interchangeable variables, one is chosen deterministically. Highly
+/// symmetric problems may require backtracking, which is capped by a search
+/// budget; `MatchOutcome::Unknown` is returned once it is exhausted.
+///
+/// # Arguments
+///
+/// * `old` - The problem to match from.
+/// * `new` - The problem to match to.
+///
+pub fn structural_match(old: &LpProblem, new: &LpProblem) -> MatchOutcome {
+    if old.sense != new.sense {
+        return MatchOutcome::Different;
+    }
+
+    let (old_graph, new_graph) = (Graph::new(old), Graph::new(new));
+    if old_graph.nodes.len() != new_graph.nodes.len() {
+        return MatchOutcome::Different;
+    }
+    let mapping = match search(&old_graph, &new_graph, &old_graph.colours, &new_graph.colours, &[], SEARCH_BUDGET) {
+        Search::Found(mapping) => mapping,
+        Search::Different => return MatchOutcome::Different,
+        Search::Unknown => return MatchOutcome::Unknown,
+    };
+
+    let mut result = StructuralMatch::default();
+    for ((kind, old_name), image) in old_graph.nodes.iter().zip(mapping) {
+        let names = match kind {
+            NodeKind::Objective => &mut result.objectives,
+            NodeKind::Constraint => &mut result.constraints,
+            NodeKind::Variable => &mut result.variables,
+        };
+        names.insert(old_name.to_string(), new_graph.nodes[image].1.to_string());
+    }
+    MatchOutcome::Matched(result)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        diff::structure::{search, structural_match, Graph, MatchOutcome, Search},
+        problem::LpProblem,
+    };
+
+    #[test]
+    fn test_structural_match() {
+        let old = LpProblem::parse("maximize\nobj: 3x + 2y\nsubject to\nc1: x + y <= 4\nc2: x + 3y <= 6\nbounds\nx <= 3\nend")
+            .expect("test case not to fail");
+        let new = LpProblem::parse("maximize\nprofit: 2b + 3a\nsubject to\nr2: a + 3b <= 6\nr1: b + a <= 4\nbounds\na <= 3\nend")
+            .expect("test case not to fail");
+
+        let mapping = structural_match(&old, &new).into_match().expect("test case not to fail");
+        assert_eq!(mapping.objectives["obj"], "profit");
+        assert_eq!(mapping.constraints["c1"], "r1");
+        assert_eq!(mapping.constraints["c2"], "r2");
+        assert_eq!(mapping.variables["x"], "a");
+        assert_eq!(mapping.variables["y"], "b");
+        assert_eq!(mapping.renamed().count(), 5);
+        assert!(structural_match(&old, &old).into_match().expect("test case not to fail").is_identity());
+
+        let changed = LpProblem::parse("maximize\nprofit: 2b + 3a\nsubject to\nr2: a + 2b <= 6\nr1: b + a <= 4\nbounds\na <= 3\nend")
+            .expect("test case not to fail");
+        assert_eq!(structural_match(&old, &changed), MatchOutcome::Different);
+    }
+
+    #[test]
+    fn test_structural_match_symmetric() {
+        let old = LpProblem::parse("minimize\nobj: x + y + z\nsubject to\nc1: x + y >= 1\nc2: y + z >= 1\nc3: x + z >= 1\nend")
+            .expect("test case not to fail");
+        let new = LpProblem::parse("minimize\nobj: p + q + r\nsubject to\nk1: q + r >= 1\nk2: p + r >= 1\nk3: q + p >= 1\nend")
+            .expect("test case not to fail");
+
+        let mapping = structural_match(&old, &new).into_match().expect("test case not to fail");
+        assert_eq!(mapping.variables.len(), 3);
+        assert_eq!(mapping.constraints.len(), 3);
+
+        // The triangle needs a choice at the root, so a single node of budget cannot decide
+        let (old_graph, new_graph) = (Graph::new(&old), Graph::new(&new));
+        assert!(matches!(search(&old_graph, &new_graph, &old_graph.colours, &new_graph.colours, &[], 1), Search::Unknown));
+    }
+}
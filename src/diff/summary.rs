@@ -0,0 +1,199 @@
+//! Summary-only comparison of two LP problems.
+//!
+//! `diff_summary` answers "did the model change, and roughly how much?"
+//! without building a `DiffReport`. Each objective, constraint and variable is
+//! reduced to a fingerprint hash, and only entities with the same name are
+//! compared, so no per-entry details are materialised. Values and attributes
+//! are compared exactly; use `build_diff_report` for tolerance-aware
+//! comparisons.
+//!
+
+use std::{collections::HashMap, hash::Hash, mem::discriminant};
+
+use crate::{
+    diff::{coefficient_map, float_key, hash_of, ConstraintParts},
+    model::{AttrValue, Attributes, Constraint, Objective, Variable},
+    problem::LpProblem,
+};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// The number of added, removed and modified entries in one section of a problem.
+pub struct SectionSummary {
+    /// The number of entries only in the new problem.
+    pub added: usize,
+    /// The number of entries only in the old problem.
+    pub removed: usize,
+    /// The number of entries in both problems that differ.
+    pub modified: usize,
+}
+
+impl SectionSummary {
+    #[must_use]
+    #[inline]
+    /// Returns `true` if no entries were added, removed or modified.
+    pub const fn is_unchanged(&self) -> bool {
+        self.added == 0 && self.removed == 0 && self.modified == 0
+    }
+}
+
+impl std::fmt::Display for SectionSummary {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} added, {} removed, {} modified", self.added, self.removed, self.modified)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// Per-section change counts between two problems.
+pub struct DiffSummary {
+    /// Whether the optimization sense differs.
+    pub sense_changed: bool,
+    /// The objective change counts.
+    pub objectives: SectionSummary,
+    /// The constraint change counts.
+    pub constraints: SectionSummary,
+    /// The variable change counts.
+    pub variables: SectionSummary,
+}
+
+impl DiffSummary {
+    #[must_use]
+    #[inline]
+    /// Returns `true` if the two problems compared were equivalent.
+    pub const fn is_empty(&self) -> bool {
+        !self.sense_changed && self.objectives.is_unchanged() && self.constraints.is_unchanged() && self.variables.is_unchanged()
+    }
+}
+
+impl std::fmt::Display for DiffSummary {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.sense_changed {
+            writeln!(f, "Sense changed")?;
+        }
+        writeln!(f, "Objectives: {}", self.objectives)?;
+        writeln!(f, "Constraints: {}", self.constraints)?;
+        writeln!(f, "Variables: {}", self.variables)
+    }
+}
+
+/// Returns a hashable form of each attribute, sorted by key.
+fn attribute_keys(attributes: &Attributes) -> Vec<(&str, u64)> {
+    attributes
+        .iter()
+        .map(|(key, value)| {
+            let value = match value {
+                AttrValue::Bool(flag) => hash_of((0_u8, flag)),
+                AttrValue::Integer(number) => hash_of((1_u8, number)),
+                AttrValue::Number(number) => hash_of((2_u8, float_key(*number))),
+                AttrValue::Text(text) => hash_of((3_u8, text)),
+            };
+            (key.as_str(), value)
+        })
+        .collect()
+}
+
+#[inline]
+/// Returns a hash of an objective's coefficients and attributes that ignores term order.
+pub(crate) fn objective_fingerprint(objective: &Objective) -> u64 {
+    let coefficients: Vec<(&str, u64)> =
+        coefficient_map(&objective.coefficients).into_iter().map(|(name, c)| (name, float_key(c))).collect();
+    hash_of((coefficients, attribute_keys(&objective.attributes)))
+}
+
+#[inline]
+/// Returns a hash of a constraint's definition and attributes that ignores term order.
+pub(crate) fn constraint_fingerprint(constraint: &Constraint) -> u64 {
+    let parts = ConstraintParts::from(constraint);
+    let coefficients: Vec<(&str, u64)> = parts.coefficients.into_iter().map(|(name, c)| (name, float_key(c))).collect();
+    hash_of((parts.operator, parts.rhs.map(float_key), parts.sos_type, coefficients, attribute_keys(constraint.attributes())))
+}
+
+#[inline]
+/// Returns a hash of a variable's type, effective bounds and attributes.
+pub(crate) fn variable_fingerprint(variable: &Variable) -> u64 {
+    let (lower, upper) = variable.effective_bounds();
+    hash_of((discriminant(&variable.var_type), float_key(lower), float_key(upper), attribute_keys(&variable.attributes)))
+}
+
+fn summarise_section<K: Eq + Hash, V>(old: &HashMap<K, V>, new: &HashMap<K, V>, fingerprint: impl Fn(&V) -> u64) -> SectionSummary {
+    let mut summary = SectionSummary::default();
+    for (name, old_value) in old {
+        match new.get(name) {
+            Some(new_value) if fingerprint(old_value) != fingerprint(new_value) => summary.modified += 1,
+            Some(_) => {}
+            None => summary.removed += 1,
+        }
+    }
+    summary.added = new.keys().filter(|name| !old.contains_key(*name)).count();
+    summary
+}
+
+#[must_use]
+/// Counts the objectives, constraints and variables that were added, removed or modified between two problems.
+///
+/// This is much cheaper than `build_diff_report` on large problems, but only
+/// detects exact changes and does not report what changed. Like
+/// `build_diff_report`, a change to an entity's attributes alone counts as a
+/// modification.
+///
+/// # Arguments
+///
+/// * `old` - The problem to compare from.
+/// * `new` - The problem to compare to.
+///
+pub fn diff_summary(old: &LpProblem, new: &LpProblem) -> DiffSummary {
+    DiffSummary {
+        sense_changed: old.sense != new.sense,
+        objectives: summarise_section(&old.objectives, &new.objectives, objective_fingerprint),
+        constraints: summarise_section(&old.constraints, &new.constraints, constraint_fingerprint),
+        variables: summarise_section(&old.variables, &new.variables, variable_fingerprint),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        diff::{
+            build_diff_report,
+            summary::{diff_summary, SectionSummary},
+            DiffOptions,
+        },
+        problem::LpProblem,
+    };
+
+    #[test]
+    fn test_diff_summary() {
+        let old = LpProblem::parse("maximize\nobj: 3x + 2y\nsubject to\nc1: x + y <= 4\nc2: x + 3y <= 6\nc3: x >= 0\nbounds\nx <= 3\nend")
+            .expect("test case not to fail");
+        let new =
+            LpProblem::parse("maximize\nobj: 2y + 3x\nsubject to\nc1: y + x <= 4\nc2: x + 2y <= 6\nc4: x + z >= 1\nbounds\nx <= 5\nend")
+                .expect("test case not to fail");
+
+        let summary = diff_summary(&old, &new);
+        assert!(!summary.sense_changed);
+        assert_eq!(summary.objectives, SectionSummary::default());
+        assert_eq!(summary.constraints, SectionSummary { added: 1, removed: 1, modified: 1 });
+        assert_eq!(summary.variables, SectionSummary { added: 1, removed: 0, modified: 1 });
+        assert!(!summary.is_empty());
+        assert!(diff_summary(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn test_diff_summary_attributes() {
+        let old = LpProblem::parse("maximize\nobj: 3x + 2y\nsubject to\nc1: x + y <= 4\nend").expect("test case not to fail");
+        let mut new = LpProblem::parse("maximize\nobj: 3x + 2y\nsubject to\nc1: x + y <= 4\nend").expect("test case not to fail");
+        new.constraints.get_mut("c1").expect("test case not to fail").attributes_mut().insert("owner".to_string(), "planning".into());
+        new.variables.get_mut("x").expect("test case not to fail").attributes.insert("priority".to_string(), 2.into());
+
+        let summary = diff_summary(&old, &new);
+        assert_eq!(summary.objectives, SectionSummary::default());
+        assert_eq!(summary.constraints, SectionSummary { added: 0, removed: 0, modified: 1 });
+        assert_eq!(summary.variables, SectionSummary { added: 0, removed: 0, modified: 1 });
+        let report = build_diff_report(&old, &new, &DiffOptions::default());
+        assert_eq!((report.constraints.len(), report.variables.len()), (1, 1));
+        assert_ne!(old.canonical_hash(), new.canonical_hash());
+    }
+}
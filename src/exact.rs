@@ -0,0 +1,491 @@
+//! Exact coefficients for models where `f64` rounding is unacceptable.
+//!
+//! `LpProblem` stores every number as an `f64`, so a literal such as `0.1`
+//! is already rounded once it is parsed. `ExactProblem` instead reads the
+//! objectives and constraints from the lossless syntax tree and converts each
+//! literal, exactly as written, into a caller-chosen `ExactNumber`:
+//!
+//! - `rust_decimal::Decimal` with the `decimal` feature, for financial models
+//! - `num_rational::BigRational` with the `rational` feature, for verification
+//!   and exact presolve, where sums and products must not round either
+//! - `f64`, which behaves like the semantic parser and needs no feature
+//!
+//! Besides the rows themselves, an `ExactProblem` writes back to LP text and
+//! compares against another problem without any rounding. Values without a
+//! finite decimal expansion, such as a rational `1/3`, cannot be written as
+//! LP literals and are rejected rather than printed in a form that does not
+//! parse.
+//!
+//! Bounds and variable declarations are not part of the exact view; use
+//! `LpProblem` alongside it for those.
+//!
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    fmt::{Debug, Display},
+    ops::{Add, Neg},
+};
+
+use crate::{
+    model::ComparisonOp,
+    syntax::{
+        cst::{SectionKind, SyntaxNode, SyntaxTree},
+        Token, TokenKind,
+    },
+};
+
+/// A number type that can hold LP literals without rounding.
+pub trait ExactNumber: Clone + PartialEq + Debug + Display + Add<Output = Self> + Neg<Output = Self> {
+    /// Converts a literal as written in the file, or returns `None` if it cannot be represented.
+    fn from_literal(text: &str) -> Option<Self>;
+
+    /// Returns the coefficient of a term written without one.
+    fn one() -> Self;
+
+    /// Returns `true` if the value is below zero.
+    fn is_negative(&self) -> bool;
+
+    /// Writes the value as an LP literal, or returns `None` if it has no finite decimal expansion.
+    fn to_literal(&self) -> Option<String>;
+}
+
+impl ExactNumber for f64 {
+    #[inline]
+    fn from_literal(text: &str) -> Option<Self> {
+        text.parse().ok()
+    }
+
+    #[inline]
+    fn one() -> Self {
+        1.0
+    }
+
+    #[inline]
+    fn is_negative(&self) -> bool {
+        *self < 0.0
+    }
+
+    #[inline]
+    fn to_literal(&self) -> Option<String> {
+        (!self.is_nan()).then(|| self.to_string())
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl ExactNumber for rust_decimal::Decimal {
+    #[inline]
+    fn from_literal(text: &str) -> Option<Self> {
+        if text.contains(['e', 'E']) {
+            Self::from_scientific(text).ok()
+        } else {
+            text.parse().ok()
+        }
+    }
+
+    #[inline]
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    #[inline]
+    fn is_negative(&self) -> bool {
+        self.is_sign_negative() && !self.is_zero()
+    }
+
+    #[inline]
+    fn to_literal(&self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+/// The largest power of ten a rational literal may be scaled by, so `1e999999999` cannot exhaust memory.
+#[cfg(feature = "rational")]
+const MAX_RATIONAL_EXPONENT: u32 = 4096;
+
+#[cfg(feature = "rational")]
+/// Rationals are written as decimals when their denominator only has the factors 2 and 5,
+/// so `1/100` becomes `0.01`. Others, such as `1/3`, have no LP literal.
+impl ExactNumber for num_rational::BigRational {
+    #[inline]
+    fn from_literal(text: &str) -> Option<Self> {
+        use num_bigint::BigInt;
+
+        let (mantissa, exponent) = match text.find(['e', 'E']) {
+            Some(index) => (&text[..index], text[index + 1..].parse::<i64>().ok()?),
+            None => (text, 0),
+        };
+        let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        let digits: BigInt = format!("{whole}{fraction}").parse().ok()?;
+        let scale = exponent.checked_sub(i64::try_from(fraction.len()).ok()?)?;
+        let power = BigInt::from(10).pow(u32::try_from(scale.unsigned_abs()).ok().filter(|power| *power <= MAX_RATIONAL_EXPONENT)?);
+        Some(if scale >= 0 { Self::from_integer(digits * power) } else { Self::new(digits, power) })
+    }
+
+    #[inline]
+    fn one() -> Self {
+        Self::from_integer(1.into())
+    }
+
+    #[inline]
+    fn is_negative(&self) -> bool {
+        *self < Self::from_integer(0.into())
+    }
+
+    #[inline]
+    fn to_literal(&self) -> Option<String> {
+        use num_bigint::BigInt;
+
+        let mut remainder = self.denom().clone();
+        let mut places = [0_u32; 2];
+        for (factor, count) in [2, 5].into_iter().zip(&mut places) {
+            let factor = BigInt::from(factor);
+            while (&remainder % &factor) == BigInt::from(0) {
+                remainder /= &factor;
+                *count += 1;
+            }
+        }
+        if remainder != BigInt::from(1) {
+            return None;
+        }
+
+        let places = places[0].max(places[1]);
+        let scaled = self.numer() * BigInt::from(10).pow(places) / self.denom();
+        let digits = format!("{:0>width$}", scaled.magnitude(), width = places as usize + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - places as usize);
+        let sign = if self.is_negative() { "-" } else { "" };
+        Some(if fraction.is_empty() { format!("{sign}{whole}") } else { format!("{sign}{whole}.{fraction}") })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The reasons a source cannot be read exactly.
+pub enum ExactError {
+    /// A literal cannot be represented by the number type, such as `inf` as a decimal.
+    Unrepresentable { name: String, text: String },
+    /// A row does not have the form `terms operator rhs`, such as a range constraint.
+    Unsupported { name: String },
+    /// A value has no LP literal, such as the rational `1/3`.
+    Unwritable { name: String, value: String },
+}
+
+impl std::fmt::Display for ExactError {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unrepresentable { name, text } => write!(f, "Cannot read {name} exactly: {text} is not representable"),
+            Self::Unsupported { name } => write!(f, "Cannot read {name} exactly: it is not of the form `terms operator rhs`"),
+            Self::Unwritable { name, value } => write!(f, "Cannot write {name} exactly: {value} has no finite decimal expansion"),
+        }
+    }
+}
+
+impl std::error::Error for ExactError {}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A term of an objective or constraint.
+pub struct ExactTerm<'a, N> {
+    /// The name of the variable.
+    pub variable: &'a str,
+    /// The coefficient, with the sign applied.
+    pub coefficient: N,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// An objective or constraint with exact coefficients.
+pub struct ExactRow<'a, N> {
+    /// The label of the row, or `R1`, `R2`, ... by position when it has none.
+    pub name: Cow<'a, str>,
+    /// The terms, in source order.
+    pub terms: Vec<ExactTerm<'a, N>>,
+    /// The comparison and right-hand side of a constraint; `None` for an objective.
+    pub rhs: Option<(ComparisonOp, N)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A difference between two exact problems.
+pub struct ExactChange<N> {
+    /// The objective or constraint that changed.
+    pub row: String,
+    /// The variable whose coefficient changed, or `None` for the right-hand side.
+    pub variable: Option<String>,
+    /// The value before the change, `None` if it was added.
+    pub old: Option<N>,
+    /// The value after the change, `None` if it was removed.
+    pub new: Option<N>,
+}
+
+impl<N: Display> Display for ExactChange<N> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let show = |value: &Option<N>| value.as_ref().map_or_else(|| "(none)".to_string(), ToString::to_string);
+        write!(f, "{} {}: {} -> {}", self.row, self.variable.as_deref().unwrap_or("rhs"), show(&self.old), show(&self.new))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The objectives and constraints of a problem with exact coefficients.
+pub struct ExactProblem<'a, N> {
+    /// `true` if the objective header is `Maximize` or one of its aliases.
+    pub maximize: bool,
+    /// The objectives, in source order.
+    pub objectives: Vec<ExactRow<'a, N>>,
+    /// The constraints, in source order.
+    pub constraints: Vec<ExactRow<'a, N>>,
+}
+
+/// Reads the terms and optional right-hand side of a statement.
+fn read_row<'a, N: ExactNumber>(statement: &SyntaxNode<'a>, position: usize) -> Result<ExactRow<'a, N>, ExactError> {
+    let tokens: Vec<&Token<'a>> = statement.tokens().into_iter().filter(|token| !token.kind.is_trivia()).collect();
+    let label = statement.label();
+    let name = label.map_or_else(|| Cow::Owned(format!("R{position}")), Cow::Borrowed);
+    let literal = |token: &Token<'_>| {
+        N::from_literal(token.text).ok_or_else(|| ExactError::Unrepresentable { name: name.to_string(), text: token.text.to_string() })
+    };
+    let unsupported = || ExactError::Unsupported { name: name.to_string() };
+
+    let mut terms = Vec::new();
+    let (mut negative, mut coefficient) = (false, None);
+    let mut rest = tokens[if label.is_some() { 2 } else { 0 }..].iter();
+    let mut operator = None;
+    for token in rest.by_ref() {
+        match token.kind {
+            TokenKind::Sign => negative ^= token.text == "-",
+            TokenKind::Number if coefficient.is_none() => coefficient = Some(literal(token)?),
+            TokenKind::Identifier => {
+                let value = coefficient.take().unwrap_or_else(N::one);
+                terms.push(ExactTerm { variable: token.text, coefficient: if negative { -value } else { value } });
+                negative = false;
+            }
+            TokenKind::Operator if coefficient.is_none() && !negative => {
+                operator = Some(token.text);
+                break;
+            }
+            _ => return Err(unsupported()),
+        }
+    }
+    if coefficient.is_some() || negative {
+        return Err(unsupported());
+    }
+
+    let rhs = match (operator, rest.as_slice()) {
+        (None, []) => None,
+        (Some(operator), [sign, number]) if sign.kind == TokenKind::Sign && number.kind == TokenKind::Number => {
+            let value = literal(number)?;
+            Some((operator, if sign.text == "-" { -value } else { value }))
+        }
+        (Some(operator), [number]) if number.kind == TokenKind::Number => Some((operator, literal(number)?)),
+        _ => return Err(unsupported()),
+    };
+    let rhs = match rhs {
+        Some((operator, value)) => Some((comparison(operator).ok_or_else(unsupported)?, value)),
+        None => None,
+    };
+    Ok(ExactRow { name, terms, rhs })
+}
+
+/// Returns the comparison an operator token stands for.
+fn comparison(operator: &str) -> Option<ComparisonOp> {
+    match operator {
+        "<=" | "=<" => Some(ComparisonOp::LTE),
+        "<" => Some(ComparisonOp::LT),
+        ">=" | "=>" => Some(ComparisonOp::GTE),
+        ">" => Some(ComparisonOp::GT),
+        "=" => Some(ComparisonOp::EQ),
+        _ => None,
+    }
+}
+
+/// Sums the coefficients of each row and variable, with the right-hand sides under `None`.
+fn entries<'r, N: ExactNumber>(rows: &'r [ExactRow<'_, N>]) -> BTreeMap<(&'r str, Option<&'r str>), N> {
+    let mut entries: BTreeMap<(&str, Option<&str>), N> = BTreeMap::new();
+    for row in rows {
+        for term in &row.terms {
+            entries
+                .entry((row.name.as_ref(), Some(term.variable)))
+                .and_modify(|sum| *sum = sum.clone() + term.coefficient.clone())
+                .or_insert_with(|| term.coefficient.clone());
+        }
+        if let Some((_, rhs)) = &row.rhs {
+            entries.insert((row.name.as_ref(), None), rhs.clone());
+        }
+    }
+    entries
+}
+
+impl<'a, N: ExactNumber> ExactProblem<'a, N> {
+    /// Reads the objectives and constraints of `source` with exact coefficients.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ExactError` if a literal cannot be represented by `N` or a row has an unsupported form.
+    pub fn parse(source: &'a str) -> Result<Self, ExactError> {
+        let tree = SyntaxTree::parse(source);
+        let mut problem = Self { maximize: false, objectives: Vec::new(), constraints: Vec::new() };
+        for (kind, section) in tree.sections() {
+            let rows = match kind {
+                SectionKind::Objective => {
+                    let header = section.tokens().into_iter().find(|token| token.kind == TokenKind::SectionHeader);
+                    problem.maximize = header.map_or(false, |header| header.text.to_ascii_lowercase().starts_with("max"));
+                    &mut problem.objectives
+                }
+                SectionKind::Constraints => &mut problem.constraints,
+                _ => continue,
+            };
+            for statement in section.nodes() {
+                let row = read_row(statement, rows.len() + 1)?;
+                rows.push(row);
+            }
+        }
+        Ok(problem)
+    }
+
+    #[must_use]
+    /// Returns every coefficient and right-hand side that differs from `other`, sorted by row and variable.
+    ///
+    /// Repeated terms of a variable in a row are summed before comparing.
+    pub fn diff(&self, other: &ExactProblem<'_, N>) -> Vec<ExactChange<N>> {
+        let mut old = entries(&self.objectives);
+        old.extend(entries(&self.constraints));
+        let mut new = entries(&other.objectives);
+        new.extend(entries(&other.constraints));
+
+        let keys: BTreeSet<_> = old.keys().chain(new.keys()).copied().collect();
+        keys.into_iter()
+            .filter(|key| old.get(key) != new.get(key))
+            .map(|key @ (row, variable)| ExactChange {
+                row: row.to_string(),
+                variable: variable.map(ToString::to_string),
+                old: old.get(&key).cloned(),
+                new: new.get(&key).cloned(),
+            })
+            .collect()
+    }
+}
+
+impl<N: ExactNumber> ExactRow<'_, N> {
+    /// Writes the row as a line of LP text, such as `c1: 0.1 x - y <= 0.3`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExactError::Unwritable` if a coefficient or right-hand side has no LP literal.
+    pub fn to_lp_string(&self) -> Result<String, ExactError> {
+        let literal =
+            |value: &N| value.to_literal().ok_or_else(|| ExactError::Unwritable { name: self.name.to_string(), value: value.to_string() });
+
+        let mut line = format!("{}:", self.name);
+        for (index, term) in self.terms.iter().enumerate() {
+            let (sign, magnitude) =
+                if term.coefficient.is_negative() { ("-", -term.coefficient.clone()) } else { ("+", term.coefficient.clone()) };
+            match (index, sign) {
+                (0, "+") => line.push(' '),
+                (0, _) => line.push_str(" -"),
+                _ => line.push_str(&format!(" {sign} ")),
+            }
+            if magnitude != N::one() {
+                line.push_str(&literal(&magnitude)?);
+                line.push(' ');
+            }
+            line.push_str(term.variable);
+        }
+        if let Some((operator, rhs)) = &self.rhs {
+            line.push_str(&format!(" {operator} {}", literal(rhs)?));
+        }
+        Ok(line)
+    }
+}
+
+impl<N: ExactNumber> ExactProblem<'_, N> {
+    /// Writes the objectives and constraints back to LP text.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExactError::Unwritable` if a coefficient or right-hand side has no LP literal.
+    pub fn to_lp_string(&self) -> Result<String, ExactError> {
+        let mut text = format!("{}\n", if self.maximize { "Maximize" } else { "Minimize" });
+        for row in &self.objectives {
+            text.push_str(&format!(" {}\n", row.to_lp_string()?));
+        }
+        text.push_str("Subject To\n");
+        for row in &self.constraints {
+            text.push_str(&format!(" {}\n", row.to_lp_string()?));
+        }
+        text.push_str("End\n");
+        Ok(text)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::exact::{ExactError, ExactProblem};
+
+    const SOURCE: &str = "Maximize\n obj: 0.1 x + 0.2 y\nSubject To\n c1: 0.1 x + 0.2 y <= 0.3\n -x + 1e-2 y >= -5\nBounds\n x <= 4\nEnd\n";
+
+    #[test]
+    fn test_exact_problem() {
+        let problem: ExactProblem<f64> = ExactProblem::parse(SOURCE).expect("test case not to fail");
+        assert!(problem.maximize);
+        assert_eq!(problem.constraints[1].name, "R2");
+        assert_eq!(problem.constraints[1].terms[0].coefficient, -1.0);
+        assert_eq!(
+            problem.to_lp_string().expect("test case not to fail"),
+            "Maximize\n obj: 0.1 x + 0.2 y\nSubject To\n c1: 0.1 x + 0.2 y <= 0.3\n R2: -x + 0.01 y >= -5\nEnd\n"
+        );
+
+        let source = SOURCE.replace("<= 0.3", "<= 0.4");
+        let changed: ExactProblem<f64> = ExactProblem::parse(&source).expect("test case not to fail");
+        let changes = problem.diff(&changed);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].to_string(), "c1 rhs: 0.3 -> 0.4");
+
+        assert_eq!(
+            ExactProblem::<f64>::parse("minimize\nobj: x\nsubject to\nr: -2 <= x <= 3\nend").unwrap_err(),
+            ExactError::Unsupported { name: "r".to_string() }
+        );
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_exact_decimal() {
+        use rust_decimal::Decimal;
+
+        let problem: ExactProblem<Decimal> = ExactProblem::parse(SOURCE).expect("test case not to fail");
+        let c1 = &problem.constraints[0];
+        let sum = c1.terms[0].coefficient + c1.terms[1].coefficient;
+        assert_eq!(Some(&sum), c1.rhs.as_ref().map(|(_, rhs)| rhs));
+        assert_eq!(problem.constraints[1].terms[1].coefficient.to_string(), "0.01");
+
+        assert_eq!(
+            ExactProblem::<Decimal>::parse("minimize\nobj: x\nsubject to\nc: x <= inf\nend").unwrap_err(),
+            ExactError::Unrepresentable { name: "c".to_string(), text: "inf".to_string() }
+        );
+    }
+
+    #[cfg(feature = "rational")]
+    #[test]
+    fn test_exact_rational() {
+        use num_rational::BigRational;
+
+        use crate::exact::ExactNumber;
+
+        let problem: ExactProblem<BigRational> = ExactProblem::parse(SOURCE).expect("test case not to fail");
+        let c1 = &problem.constraints[0];
+        let sum = c1.terms[0].coefficient.clone() + c1.terms[1].coefficient.clone();
+        assert_eq!(Some(&sum), c1.rhs.as_ref().map(|(_, rhs)| rhs));
+        assert_eq!(problem.constraints[1].terms[1].coefficient.to_string(), "1/100");
+        assert_eq!(
+            problem.to_lp_string().expect("test case not to fail"),
+            ExactProblem::<f64>::parse(SOURCE).and_then(|problem| problem.to_lp_string()).expect("test case not to fail")
+        );
+        assert_eq!(BigRational::new((-5).into(), 4.into()).to_literal().as_deref(), Some("-1.25"));
+        assert_eq!(BigRational::from_integer(30.into()).to_literal().as_deref(), Some("30"));
+        assert_eq!(BigRational::new(1.into(), 3.into()).to_literal(), None);
+
+        let mut thirds = problem.clone();
+        thirds.constraints[0].terms[0].coefficient = BigRational::new(1.into(), 3.into());
+        assert_eq!(thirds.to_lp_string().unwrap_err(), ExactError::Unwritable { name: "c1".to_string(), value: "1/3".to_string() });
+        assert_eq!(BigRational::from_literal("2.5e3"), Some(BigRational::from_integer(2500.into())));
+        assert_eq!(BigRational::from_literal("1e999999"), None);
+        assert_eq!(BigRational::from_literal("inf"), None);
+    }
+}
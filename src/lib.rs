@@ -29,18 +29,39 @@
 //!
 //! # Module Organization
 //!
+//! - `analysis`: Structural analysis of a single problem
+//! - `compat`: Conversions into third-party optimisation crates
+//! - `diff`: Coefficient-level comparison of two problems
+//! - `exact`: Objectives and constraints with exact coefficients (`decimal` and `rational` features)
 //! - `model`: Core data structures for LP problems
+//! - `page`: Sorted windows of constraints and variables for browsing large problems
 //! - `parser`: File parsing utilities
 //! - `parsers`: Component-specific parsers
 //! - `lp_problem`: Main problem representation and parsing
+//! - `query`: Selector queries over the entities of a problem
+//! - `syntax`: Lossless concrete syntax trees of LP files
+//! - `template`: Indexed templates expanding into families of rows
+//! - `validation`: Configurable lint rules for problems
+//! - `wasm`: WebAssembly bindings for JavaScript (`wasm` feature)
 //!
 
 // #![deny(missing_docs)]
 
 pub mod problem;
+pub mod analysis;
+pub mod compat;
+pub mod diff;
+pub mod exact;
 pub mod model;
+pub mod page;
 pub mod parser;
 pub mod parsers;
+pub mod query;
+pub mod syntax;
+pub mod template;
+pub mod validation;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 use nom::{
     branch::alt,
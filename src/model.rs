@@ -12,13 +12,92 @@
 //! - `Objective`: Struct representing an optimization objective with a name and coefficients.
 //! - `VariableType`: Enum for different types of variables in optimization models.
 //! - `Variable`: Struct representing a variable with a name and type.
+//! - `SolutionStatus`: Enum for the outcome reported by a solver.
+//! - `Solution`: Struct representing a solver-agnostic solution to a problem.
+//! - `SolutionViolation`: Enum describing how a solution fails to satisfy a problem.
+//! - `SolutionDifference`: Enum describing how the solutions of two problems differ.
+//! - `BoundChange`: Struct recording a variable bound tightened by bound propagation.
+//! - `AttrValue`: Enum for metadata values attached to variables, constraints and objectives.
 //!
 
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+};
+
+/// Metadata attached to a variable, constraint or objective, keyed by name.
+///
+/// The parser leaves it empty. Transforms and external tools use it to carry
+/// tags, provenance or any other annotation through serialization, patching
+/// and diffing.
+pub type Attributes = BTreeMap<String, AttrValue>;
 
 #[cfg_attr(feature = "diff", derive(diff::Diff), diff(attr(#[derive(Debug, PartialEq)])))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", serde(untagged))]
+#[derive(Debug, Clone, PartialEq)]
+/// A metadata value.
+pub enum AttrValue {
+    /// A flag.
+    Bool(bool),
+    /// A whole number.
+    Integer(i64),
+    /// A floating-point number.
+    Number(f64),
+    /// Free text.
+    Text(String),
+}
+
+impl std::fmt::Display for AttrValue {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Integer(value) => write!(f, "{value}"),
+            Self::Number(value) => write!(f, "{value}"),
+            Self::Text(value) => f.write_str(value),
+        }
+    }
+}
+
+impl From<bool> for AttrValue {
+    #[inline]
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i64> for AttrValue {
+    #[inline]
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<f64> for AttrValue {
+    #[inline]
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<&str> for AttrValue {
+    #[inline]
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<String> for AttrValue {
+    #[inline]
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+#[cfg_attr(feature = "diff", derive(diff::Diff), diff(attr(#[derive(Debug, PartialEq)])))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 /// Represents comparison operations that can be used to compare values.
 pub enum ComparisonOp {
     #[default]
@@ -77,7 +156,7 @@ impl std::fmt::Display for Sense {
 
 #[cfg_attr(feature = "diff", derive(diff::Diff), diff(attr(#[derive(Debug, PartialEq)])))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 /// Represents the type of SOS (System of Systems) with variants `S1` and `S2`.
 pub enum SOSType {
     /// At most one variable in the set can be non-zero.
@@ -135,12 +214,27 @@ impl std::fmt::Display for Coefficient<'_> {
 /// * `rhs` - The right-hand side value for the standard constraint.
 /// * `sos_type` - The type of SOS for the SOS constraint.
 /// * `weights` - A vector of weights for the SOS constraint.
+/// * `attributes` - Metadata attached to either kind of constraint.
 ///
 pub enum Constraint<'a> {
     /// A linear constraint defined by a name, a vector of coefficients, a comparison operator, and a right-hand side value.
-    Standard { name: Cow<'a, str>, coefficients: Vec<Coefficient<'a>>, operator: ComparisonOp, rhs: f64 },
+    Standard {
+        name: Cow<'a, str>,
+        coefficients: Vec<Coefficient<'a>>,
+        operator: ComparisonOp,
+        #[cfg_attr(feature = "serde", serde(with = "infinite"))]
+        rhs: f64,
+        #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "BTreeMap::is_empty"))]
+        attributes: Attributes,
+    },
     /// A special ordered set constraint defined by a name, a type of SOS and a vector of weights.
-    SOS { name: Cow<'a, str>, sos_type: SOSType, weights: Vec<Coefficient<'a>> },
+    SOS {
+        name: Cow<'a, str>,
+        sos_type: SOSType,
+        weights: Vec<Coefficient<'a>>,
+        #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "BTreeMap::is_empty"))]
+        attributes: Attributes,
+    },
 }
 
 impl<'a> Constraint<'a> {
@@ -152,13 +246,30 @@ impl<'a> Constraint<'a> {
             Constraint::Standard { name, .. } | Constraint::SOS { name, .. } => name.clone(),
         }
     }
+
+    #[must_use]
+    #[inline]
+    /// Returns the metadata attached to the constraint.
+    pub const fn attributes(&self) -> &Attributes {
+        match self {
+            Constraint::Standard { attributes, .. } | Constraint::SOS { attributes, .. } => attributes,
+        }
+    }
+
+    #[inline]
+    /// Returns the metadata attached to the constraint for modification.
+    pub fn attributes_mut(&mut self) -> &mut Attributes {
+        match self {
+            Constraint::Standard { attributes, .. } | Constraint::SOS { attributes, .. } => attributes,
+        }
+    }
 }
 
 impl std::fmt::Display for Constraint<'_> {
     #[inline]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Constraint::Standard { name, coefficients, operator, rhs } => {
+            Constraint::Standard { name, coefficients, operator, rhs, .. } => {
                 write!(f, "{name}: ")?;
                 for (i, coef) in coefficients.iter().enumerate() {
                     if i > 0 && coef.coefficient > 0.0 {
@@ -168,7 +279,7 @@ impl std::fmt::Display for Constraint<'_> {
                 }
                 write!(f, "{operator} {rhs}")
             }
-            Constraint::SOS { name, sos_type, weights } => {
+            Constraint::SOS { name, sos_type, weights, .. } => {
                 write!(f, "{name}: {sos_type}:: ")?;
                 for (i, weight) in weights.iter().enumerate() {
                     if i > 0 {
@@ -194,6 +305,26 @@ pub struct Objective<'a> {
     pub name: Cow<'a, str>,
     /// A vector of `Coefficient` instances associated with the objective.
     pub coefficients: Vec<Coefficient<'a>>,
+    /// Metadata attached to the objective.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "BTreeMap::is_empty"))]
+    pub attributes: Attributes,
+}
+
+impl<'a> Objective<'a> {
+    #[must_use]
+    #[inline]
+    /// Initialise a new `Objective` without attributes.
+    pub fn new(name: Cow<'a, str>, coefficients: Vec<Coefficient<'a>>) -> Self {
+        Self { name, coefficients, attributes: Attributes::new() }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Builder method attaching the metadata `value` under `key`.
+    pub fn with_attribute(mut self, key: impl Into<String>, value: impl Into<AttrValue>) -> Self {
+        self.attributes.insert(key.into(), value.into());
+        self
+    }
 }
 
 #[cfg_attr(feature = "diff", derive(diff::Diff), diff(attr(#[derive(Debug, PartialEq)])))]
@@ -207,11 +338,11 @@ pub enum VariableType {
     /// General variable [0, +Infinity]
     General,
     /// Variable with a lower bound (`x >= lb`).
-    LowerBound(f64),
+    LowerBound(#[cfg_attr(feature = "serde", serde(with = "infinite"))] f64),
     /// Variable with an upper bound (`x ≤ ub`).
-    UpperBound(f64),
+    UpperBound(#[cfg_attr(feature = "serde", serde(with = "infinite"))] f64),
     /// Variable with both lower and upper bounds (`lb ≤ x ≤ ub`).
-    DoubleBound(f64, f64),
+    DoubleBound(#[cfg_attr(feature = "serde", serde(with = "infinite"))] f64, #[cfg_attr(feature = "serde", serde(with = "infinite"))] f64),
     /// Binary variable.
     Binary,
     /// Integer variable.
@@ -222,6 +353,33 @@ pub enum VariableType {
     SOS,
 }
 
+impl VariableType {
+    #[must_use]
+    #[inline]
+    /// Returns the effective `(lower, upper)` bounds of a variable of this type.
+    ///
+    /// Variables without a declaration in the Bounds section are parsed as `Free`,
+    /// so `Free` is given the LP format default bounds of `[0, +inf)`. An explicit
+    /// `x free` declaration is recorded in `Variable::declared_bounds`, see
+    /// `Variable::effective_bounds`.
+    pub const fn bounds(&self) -> (f64, f64) {
+        match self {
+            Self::Free | Self::General | Self::Integer | Self::SemiContinuous | Self::SOS => (0.0, f64::INFINITY),
+            Self::LowerBound(lb) => (*lb, f64::INFINITY),
+            Self::UpperBound(ub) => (0.0, *ub),
+            Self::DoubleBound(lb, ub) => (*lb, *ub),
+            Self::Binary => (0.0, 1.0),
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Returns `true` if a variable of this type must take an integer value.
+    pub const fn is_integer(&self) -> bool {
+        matches!(self, Self::General | Self::Integer | Self::Binary)
+    }
+}
+
 impl std::fmt::Display for VariableType {
     #[inline]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -265,6 +423,14 @@ pub struct Variable<'a> {
     pub name: &'a str,
     /// The type of the variable, represented by `VariableType`.
     pub var_type: VariableType,
+    /// The bound from the Bounds section (`LowerBound`, `UpperBound` or `DoubleBound`)
+    /// when a later integer, general, binary or semi-continuous section replaced it as `var_type`,
+    /// or `Free` when the Bounds section declares the variable `free`.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub declared_bounds: Option<VariableType>,
+    /// Metadata attached to the variable.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "BTreeMap::is_empty"))]
+    pub attributes: Attributes,
 }
 
 impl<'a> Variable<'a> {
@@ -272,7 +438,7 @@ impl<'a> Variable<'a> {
     #[inline]
     /// Initialise a new `Variable`.
     pub fn new(name: &'a str) -> Self {
-        Self { name, var_type: VariableType::default() }
+        Self { name, var_type: VariableType::default(), declared_bounds: None, attributes: Attributes::new() }
     }
 
     #[inline]
@@ -281,12 +447,306 @@ impl<'a> Variable<'a> {
         self.var_type = var_type;
     }
 
+    #[must_use]
+    #[inline]
+    /// Returns `true` if the variable type is one of the bound declarations.
+    pub const fn has_bound_type(&self) -> bool {
+        matches!(self.var_type, VariableType::LowerBound(_) | VariableType::UpperBound(_) | VariableType::DoubleBound(..))
+    }
+
+    #[must_use]
+    #[inline]
+    /// Returns the `(lower, upper)` bounds of the variable.
+    ///
+    /// Bounds from the Bounds section take precedence over the defaults of an integer,
+    /// general or semi-continuous type that replaced them, and binary variables are
+    /// further restricted to `[0, 1]`. A semi-continuous variable may also be zero.
+    ///
+    /// Unlike an undeclared variable, which defaults to `[0, +inf)`, a variable
+    /// declared `free` is unbounded.
+    pub fn effective_bounds(&self) -> (f64, f64) {
+        let (lower, upper) = match &self.declared_bounds {
+            Some(VariableType::Free) if !self.has_bound_type() => (f64::NEG_INFINITY, f64::INFINITY),
+            Some(declared) if !self.has_bound_type() => declared.bounds(),
+            _ => self.var_type.bounds(),
+        };
+        if self.var_type == VariableType::Binary {
+            (lower.max(0.0), upper.min(1.0))
+        } else {
+            (lower, upper)
+        }
+    }
+
     #[must_use]
     #[inline]
     /// Builder method for constructing a `Variable` with a non-default `VariableType`.
-    pub const fn with_var_type(self, var_type: VariableType) -> Self {
+    pub fn with_var_type(self, var_type: VariableType) -> Self {
         Self { var_type, ..self }
     }
+
+    #[must_use]
+    #[inline]
+    /// Builder method attaching the metadata `value` under `key`.
+    pub fn with_attribute(mut self, key: impl Into<String>, value: impl Into<AttrValue>) -> Self {
+        self.attributes.insert(key.into(), value.into());
+        self
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// Represents the outcome reported by a solver.
+pub enum SolutionStatus {
+    /// A provably optimal solution was found.
+    Optimal,
+    /// A feasible, but not necessarily optimal, solution was found.
+    Feasible,
+    /// The problem has no feasible solution.
+    Infeasible,
+    /// The objective can be improved without limit.
+    Unbounded,
+    /// The solve was cancelled, or reached its deadline, before completing.
+    Interrupted,
+    #[default]
+    /// The solver did not report a conclusive status.
+    Unknown,
+}
+
+impl SolutionStatus {
+    #[must_use]
+    #[inline]
+    /// Returns `true` if the status carries variable values.
+    pub const fn has_values(&self) -> bool {
+        matches!(self, Self::Optimal | Self::Feasible)
+    }
+}
+
+impl std::fmt::Display for SolutionStatus {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Optimal => write!(f, "Optimal"),
+            Self::Feasible => write!(f, "Feasible"),
+            Self::Infeasible => write!(f, "Infeasible"),
+            Self::Unbounded => write!(f, "Unbounded"),
+            Self::Interrupted => write!(f, "Interrupted"),
+            Self::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq)]
+/// Represents a solver-agnostic solution to an LP problem.
+///
+/// Values are keyed by variable name, duals by constraint name and reduced
+/// costs by variable name. Solvers commonly omit zero values, so a missing
+/// entry in `values` is treated as `0.0`.
+pub struct Solution {
+    /// The status reported by the solver.
+    pub status: SolutionStatus,
+    /// The objective value of the incumbent, if the solver reported one.
+    pub objective: Option<f64>,
+    /// The best proven bound on the objective, if the solver reported one.
+    pub best_bound: Option<f64>,
+    /// The value of each variable.
+    pub values: HashMap<String, f64>,
+    /// The dual value (shadow price) of each constraint.
+    pub duals: HashMap<String, f64>,
+    /// The reduced cost of each variable.
+    pub reduced_costs: HashMap<String, f64>,
+}
+
+impl Solution {
+    #[must_use]
+    #[inline]
+    /// Initialise a new `Solution` with the given status and no values.
+    pub fn new(status: SolutionStatus) -> Self {
+        Self { status, ..Self::default() }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Builder method for setting the objective value.
+    pub fn with_objective(self, objective: f64) -> Self {
+        Self { objective: Some(objective), ..self }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Builder method for setting the best proven bound on the objective.
+    pub fn with_best_bound(self, best_bound: f64) -> Self {
+        Self { best_bound: Some(best_bound), ..self }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Returns the relative gap between the incumbent objective and the best bound.
+    ///
+    /// The gap is computed as `|objective - best_bound| / max(|objective|, 1e-10)`,
+    /// matching the convention used by most MIP solvers.
+    pub fn relative_gap(&self) -> Option<f64> {
+        match (self.objective, self.best_bound) {
+            (Some(objective), Some(best_bound)) => Some((objective - best_bound).abs() / objective.abs().max(1e-10)),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Builder method for setting the variable values.
+    pub fn with_values(self, values: HashMap<String, f64>) -> Self {
+        Self { values, ..self }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Returns the value of the variable `name`, defaulting to `0.0` when absent.
+    pub fn value(&self, name: &str) -> f64 {
+        self.values.get(name).copied().unwrap_or_default()
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// Describes a way in which a `Solution` fails to satisfy an `LpProblem`.
+pub enum SolutionViolation {
+    /// A standard constraint is not satisfied; `activity` is the evaluated left-hand side.
+    Constraint { name: String, activity: f64, operator: ComparisonOp, rhs: f64 },
+    /// A variable lies outside of its bounds.
+    Bound { name: String, value: f64, lower: f64, upper: f64 },
+    /// An integer variable takes a fractional value.
+    Integrality { name: String, value: f64 },
+    /// A special ordered set has too many, or non-adjacent, non-zero members.
+    SOS { name: String, sos_type: SOSType, non_zero: Vec<String> },
+    /// The solution refers to a variable that is not part of the problem.
+    UnknownVariable { name: String },
+}
+
+impl std::fmt::Display for SolutionViolation {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Constraint { name, activity, operator, rhs } => write!(f, "Constraint {name} violated: {activity} {operator} {rhs}"),
+            Self::Bound { name, value, lower, upper } => write!(f, "Variable {name} = {value} outside of bounds [{lower}, {upper}]"),
+            Self::Integrality { name, value } => write!(f, "Integer variable {name} has fractional value {value}"),
+            Self::SOS { name, sos_type, non_zero } => {
+                write!(f, "{sos_type} set {name} violated by non-zero members {}", non_zero.join(", "))
+            }
+            Self::UnknownVariable { name } => write!(f, "Variable {name} is not part of the problem"),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// Describes a way in which the solutions of two problems differ, as found by `LpProblem::compare_solutions`.
+///
+/// `left` belongs to the first problem and `right` to the second; `None` marks
+/// a value that is missing, or an entity that is not part of that problem.
+pub enum SolutionDifference {
+    /// The solver reported different statuses.
+    Status { left: SolutionStatus, right: SolutionStatus },
+    /// The objective values differ.
+    Objective { left: Option<f64>, right: Option<f64> },
+    /// The values of a variable differ.
+    Variable { name: String, left: Option<f64>, right: Option<f64> },
+    /// The activities, the evaluated left-hand sides, of a standard constraint differ.
+    Activity { name: String, left: Option<f64>, right: Option<f64> },
+}
+
+impl std::fmt::Display for SolutionDifference {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn side(value: Option<f64>) -> String {
+            value.map_or_else(|| "-".to_string(), |value| value.to_string())
+        }
+
+        match self {
+            Self::Status { left, right } => write!(f, "Status: {left} -> {right}"),
+            Self::Objective { left, right } => write!(f, "Objective: {} -> {}", side(*left), side(*right)),
+            Self::Variable { name, left, right } => write!(f, "Variable {name}: {} -> {}", side(*left), side(*right)),
+            Self::Activity { name, left, right } => write!(f, "Constraint {name} activity: {} -> {}", side(*left), side(*right)),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// A variable whose bounds were tightened by `LpProblem::tighten_bounds`.
+pub struct BoundChange {
+    /// The name of the variable.
+    pub name: String,
+    /// The `(lower, upper)` bounds before propagation.
+    pub old: (f64, f64),
+    /// The `(lower, upper)` bounds after propagation.
+    pub new: (f64, f64),
+}
+
+impl std::fmt::Display for BoundChange {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self { name, old: (old_lower, old_upper), new: (new_lower, new_upper) } = self;
+        write!(f, "Variable {name} bounds [{old_lower}, {old_upper}] tightened to [{new_lower}, {new_upper}]")
+    }
+}
+
+#[cfg(feature = "serde")]
+/// Serializes infinite bounds and right-hand sides as `"inf"` and `"-inf"`, as JSON has no representation for them.
+pub(crate) mod infinite {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(untagged)]
+    /// A number as written to JSON.
+    pub(crate) enum Bound {
+        Finite(f64),
+        Infinite(String),
+    }
+
+    impl From<f64> for Bound {
+        fn from(value: f64) -> Self {
+            if value.is_finite() {
+                Self::Finite(value)
+            } else if value > 0.0 {
+                Self::Infinite("inf".to_string())
+            } else {
+                Self::Infinite("-inf".to_string())
+            }
+        }
+    }
+
+    pub(crate) fn to_f64<E: serde::de::Error>(bound: Bound) -> Result<f64, E> {
+        match bound {
+            Bound::Finite(value) => Ok(value),
+            Bound::Infinite(value) if value == "inf" => Ok(f64::INFINITY),
+            Bound::Infinite(value) if value == "-inf" => Ok(f64::NEG_INFINITY),
+            Bound::Infinite(value) => Err(E::custom(format!("invalid bound {value}"))),
+        }
+    }
+
+    pub fn serialize<S: Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        Bound::from(*value).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        to_f64(Bound::deserialize(deserializer)?)
+    }
+
+    /// The same representation for an optional value.
+    pub mod optional {
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        use super::{to_f64, Bound};
+
+        pub fn serialize<S: Serializer>(value: &Option<f64>, serializer: S) -> Result<S::Ok, S::Error> {
+            value.map(Bound::from).serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+            Option::<Bound>::deserialize(deserializer)?.map(to_f64).transpose()
+        }
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -296,6 +756,7 @@ impl<'de: 'a, 'a> serde::Deserialize<'de> for Constraint<'a> {
         #[derive(PartialEq, serde::Deserialize)]
         #[serde(field_identifier, rename_all = "lowercase")]
         enum Field {
+            Attributes,
             Coefficients,
             Name,
             Operator,
@@ -326,13 +787,15 @@ impl<'de: 'a, 'a> serde::Deserialize<'de> for Constraint<'a> {
                         let mut coefficients = None;
                         let mut operator = None;
                         let mut rhs = None;
+                        let mut attributes = None;
 
                         while let Some(key) = map.next_key()? {
                             match key {
+                                Field::Attributes => attributes = Some(map.next_value()?),
                                 Field::Name => name = map.next_value()?,
                                 Field::Coefficients => coefficients = Some(map.next_value()?),
                                 Field::Operator => operator = Some(map.next_value()?),
-                                Field::Rhs => rhs = Some(map.next_value()?),
+                                Field::Rhs => rhs = Some(infinite::to_f64(map.next_value()?)?),
                                 Field::Type | Field::Weights | Field::SosType => {
                                     let _ = map.next_value::<serde::de::IgnoredAny>()?;
                                 }
@@ -344,15 +807,18 @@ impl<'de: 'a, 'a> serde::Deserialize<'de> for Constraint<'a> {
                             coefficients: coefficients.ok_or_else(|| serde::de::Error::missing_field("coefficients"))?,
                             operator: operator.ok_or_else(|| serde::de::Error::missing_field("operator"))?,
                             rhs: rhs.ok_or_else(|| serde::de::Error::missing_field("rhs"))?,
+                            attributes: attributes.unwrap_or_default(),
                         })
                     }
                     "SOS" => {
                         let mut name = "";
                         let mut sos_type = None;
                         let mut weights = None;
+                        let mut attributes = None;
 
                         while let Some(key) = map.next_key()? {
                             match key {
+                                Field::Attributes => attributes = Some(map.next_value()?),
                                 Field::Name => name = map.next_value()?,
                                 Field::SosType => sos_type = Some(map.next_value()?),
                                 Field::Weights => weights = Some(map.next_value()?),
@@ -366,6 +832,7 @@ impl<'de: 'a, 'a> serde::Deserialize<'de> for Constraint<'a> {
                             name: Cow::Borrowed(name),
                             sos_type: sos_type.ok_or_else(|| serde::de::Error::missing_field("sos_type"))?,
                             weights: weights.ok_or_else(|| serde::de::Error::missing_field("weights"))?,
+                            attributes: attributes.unwrap_or_default(),
                         })
                     }
                     _ => Err(serde::de::Error::unknown_variant(&constraint_type, &["Standard", "SOS"])),
@@ -373,7 +840,7 @@ impl<'de: 'a, 'a> serde::Deserialize<'de> for Constraint<'a> {
             }
         }
 
-        const FIELDS: &[&str] = &["type", "name", "coefficients", "weights", "operator", "rhs", "sos_type"];
+        const FIELDS: &[&str] = &["type", "name", "coefficients", "weights", "operator", "rhs", "sos_type", "attributes"];
         deserializer.deserialize_struct("Constraint", FIELDS, ConstraintVisitor(std::marker::PhantomData))
     }
 }
@@ -385,6 +852,7 @@ impl<'de: 'a, 'a> serde::Deserialize<'de> for Objective<'a> {
         #[derive(serde::Deserialize)]
         #[serde(field_identifier, rename_all = "lowercase")]
         enum Field {
+            Attributes,
             Coefficients,
             Name,
         }
@@ -401,9 +869,11 @@ impl<'de: 'a, 'a> serde::Deserialize<'de> for Objective<'a> {
             fn visit_map<V: serde::de::MapAccess<'de>>(self, mut map: V) -> Result<Objective<'a>, V::Error> {
                 let mut name = "";
                 let mut coefficients = None;
+                let mut attributes = None;
 
                 while let Some(key) = map.next_key()? {
                     match key {
+                        Field::Attributes => attributes = Some(map.next_value()?),
                         Field::Name => name = map.next_value()?,
                         Field::Coefficients => coefficients = Some(map.next_value()?),
                     }
@@ -412,10 +882,11 @@ impl<'de: 'a, 'a> serde::Deserialize<'de> for Objective<'a> {
                 Ok(Objective {
                     name: Cow::Borrowed(name),
                     coefficients: coefficients.ok_or_else(|| serde::de::Error::missing_field("coefficients"))?,
+                    attributes: attributes.unwrap_or_default(),
                 })
             }
         }
 
-        deserializer.deserialize_struct("Objective", &["name", "coefficients"], ObjectiveVisitor(std::marker::PhantomData))
+        deserializer.deserialize_struct("Objective", &["name", "coefficients", "attributes"], ObjectiveVisitor(std::marker::PhantomData))
     }
 }
@@ -0,0 +1,144 @@
+//! Sorted windows over the constraints and variables of a problem.
+//!
+//! User interfaces and APIs browsing a large problem rarely need more than a
+//! screenful of rows at a time. `LpProblem::constraints_page` and
+//! `LpProblem::variables_page` return one window of a stable ordering, along
+//! with the total count, without sorting or copying the rest of the problem.
+//!
+//! Ties in the ordering are broken by name, so consecutive pages neither
+//! repeat nor skip entities while the problem is unchanged.
+//!
+
+use std::{cmp::Ordering, collections::HashMap};
+
+use crate::{
+    model::{Constraint, Variable},
+    problem::LpProblem,
+};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+/// The order of the entities a page is cut from.
+pub enum PageSort {
+    #[default]
+    /// By name, `a` to `z`.
+    NameAscending,
+    /// By name, `z` to `a`.
+    NameDescending,
+    /// By the number of terms of a constraint, or of terms naming a variable, fewest first.
+    TermsAscending,
+    /// By the number of terms of a constraint, or of terms naming a variable, most first.
+    TermsDescending,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// A window of a sorted collection.
+pub struct Page<'p, T> {
+    /// The position of the first item in the whole ordering.
+    pub offset: usize,
+    /// The number of entities in the whole collection.
+    pub total: usize,
+    /// The names and entities in the window, in order.
+    pub items: Vec<(&'p str, &'p T)>,
+}
+
+impl<T> Page<'_, T> {
+    #[must_use]
+    #[inline]
+    /// Returns `true` if entities follow this window.
+    pub fn has_more(&self) -> bool {
+        self.offset + self.items.len() < self.total
+    }
+}
+
+/// Cuts the window `offset..offset + limit` from `entries`, each holding a name, a term count and an entity.
+fn window<'p, T>(mut entries: Vec<(&'p str, usize, &'p T)>, offset: usize, limit: usize, sort: PageSort) -> Page<'p, T> {
+    let compare = |a: &(&str, usize, &T), b: &(&str, usize, &T)| -> Ordering {
+        match sort {
+            PageSort::NameAscending => a.0.cmp(b.0),
+            PageSort::NameDescending => b.0.cmp(a.0),
+            PageSort::TermsAscending => a.1.cmp(&b.1).then_with(|| a.0.cmp(b.0)),
+            PageSort::TermsDescending => b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)),
+        }
+    };
+
+    let total = entries.len();
+    let end = offset.saturating_add(limit).min(total);
+    if offset >= end {
+        return Page { offset, total, items: Vec::new() };
+    }
+    if end < total {
+        entries.select_nth_unstable_by(end, compare);
+        entries.truncate(end);
+    }
+    entries.sort_unstable_by(compare);
+    Page { offset, total, items: entries.drain(offset..).map(|(name, _, entity)| (name, entity)).collect() }
+}
+
+impl<'a> LpProblem<'a> {
+    #[must_use]
+    /// Returns at most `limit` constraints, starting at position `offset` of the ordering `sort`.
+    pub fn constraints_page(&self, offset: usize, limit: usize, sort: PageSort) -> Page<'_, Constraint<'a>> {
+        let entries = self
+            .constraints
+            .iter()
+            .map(|(name, constraint)| {
+                let terms = match constraint {
+                    Constraint::Standard { coefficients, .. } => coefficients.len(),
+                    Constraint::SOS { weights, .. } => weights.len(),
+                };
+                (name.as_ref(), terms, constraint)
+            })
+            .collect();
+        window(entries, offset, limit, sort)
+    }
+
+    #[must_use]
+    /// Returns at most `limit` variables, starting at position `offset` of the ordering `sort`.
+    ///
+    /// Sorting by terms counts the objective and constraint terms that name each variable.
+    pub fn variables_page(&self, offset: usize, limit: usize, sort: PageSort) -> Page<'_, Variable<'a>> {
+        let mut terms: HashMap<&str, usize> = HashMap::new();
+        if matches!(sort, PageSort::TermsAscending | PageSort::TermsDescending) {
+            let objective_terms = self.objectives.values().flat_map(|objective| &objective.coefficients);
+            let constraint_terms = self.constraints.values().flat_map(|constraint| match constraint {
+                Constraint::Standard { coefficients, .. } => coefficients,
+                Constraint::SOS { weights, .. } => weights,
+            });
+            for term in objective_terms.chain(constraint_terms) {
+                *terms.entry(term.var_name).or_default() += 1;
+            }
+        }
+        let entries = self.variables.iter().map(|(name, variable)| (*name, terms.get(name).copied().unwrap_or(0), variable)).collect();
+        window(entries, offset, limit, sort)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{page::PageSort, problem::LpProblem};
+
+    #[test]
+    fn test_pages() {
+        let input =
+            "minimize\nobj: x + y + z\nsubject to\nc1: x + y + z >= 1\nc2: x >= 0\nc3: x + y <= 4\nc4: y - z = 0\nbounds\nw <= 3\nend";
+        let problem = LpProblem::parse(input).expect("test case not to fail");
+
+        fn names<T>(items: &[(&str, &T)]) -> Vec<String> {
+            items.iter().map(|(name, _)| name.to_string()).collect()
+        }
+
+        let first = problem.constraints_page(0, 3, PageSort::NameAscending);
+        assert_eq!((names(&first.items), first.total, first.has_more()), (vec!["c1".to_string(), "c2".into(), "c3".into()], 4, true));
+        let second = problem.constraints_page(3, 3, PageSort::NameAscending);
+        assert_eq!((names(&second.items), second.has_more()), (vec!["c4".to_string()], false));
+        assert!(problem.constraints_page(9, 3, PageSort::NameAscending).items.is_empty());
+
+        assert_eq!(names(&problem.constraints_page(0, 3, PageSort::TermsDescending).items), ["c1", "c3", "c4"]);
+        assert_eq!(names(&problem.constraints_page(1, 2, PageSort::TermsAscending).items), ["c3", "c4"]);
+        assert_eq!(names(&problem.constraints_page(0, 2, PageSort::NameDescending).items), ["c4", "c3"]);
+        assert_eq!(names(&problem.variables_page(0, 4, PageSort::TermsDescending).items), ["x", "y", "z", "w"]);
+        assert_eq!(names(&problem.variables_page(1, 10, PageSort::NameAscending).items), ["x", "y", "z"]);
+    }
+}
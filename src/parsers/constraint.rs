@@ -23,7 +23,7 @@ use unique_id::{sequence::SequenceGenerator, Generator as _};
 
 use crate::{
     log_unparsed_content,
-    model::{Constraint, Variable},
+    model::{Attributes, Constraint, Variable},
     parsers::{
         coefficient::parse_coefficient,
         number::{parse_cmp_op, parse_num_value},
@@ -71,6 +71,12 @@ type ConstraintParseResult<'a> = IResult<&'a str, (HashMap<Cow<'a, str>, Constra
 ///   constraints and a hashmap of variables, or an error if parsing fails.
 ///
 pub fn parse_constraints<'a>(input: &'a str) -> ConstraintParseResult<'a> {
+    let (input, (constraints, constraint_vars)) = parse_constraint_list(input)?;
+    Ok((input, (constraints.into_iter().map(|c| (Cow::Owned(c.name().to_string()), c)).collect(), constraint_vars)))
+}
+
+/// Parses the constraints like `parse_constraints`, keeping them in source order.
+pub(crate) fn parse_constraint_list<'a>(input: &'a str) -> IResult<&'a str, (Vec<Constraint<'a>>, HashMap<&'a str, Variable<'a>>)> {
     let mut constraint_vars: HashMap<&'a str, Variable<'a>> = HashMap::with_capacity(512);
     let gen = SequenceGenerator;
 
@@ -105,13 +111,14 @@ pub fn parse_constraints<'a>(input: &'a str) -> ConstraintParseResult<'a> {
                     coefficients,
                     operator,
                     rhs,
+                    attributes: Attributes::new(),
                 }
             })
         },
     );
 
     let (remaining, constraints) = many1(parser)(input)?;
-    let cons = constraints.into_iter().flatten().map(|c| (Cow::Owned(c.name().to_string()), c)).collect();
+    let cons = constraints.into_iter().flatten().collect();
 
     log_unparsed_content("Failed to parse constraints fully", remaining);
     Ok(("", (cons, constraint_vars)))
@@ -0,0 +1,214 @@
+//! Hooks for nonstandard sections.
+//!
+//! Some tools embed their own data in LP files as a block delimited by comment
+//! markers, which solvers skip when every line of the block is a comment:
+//!
+//! ```text
+//! \BEGIN METADATA
+//! \ author: planning team
+//! \ revision: 7
+//! \END METADATA
+//! ```
+//!
+//! `CustomSections` maps block names to handlers. `extract` passes the raw text
+//! between the markers of each registered block to its handler, collects the
+//! results into `Extensions` and returns the source with those blocks blanked
+//! out, ready for `LpProblem::parse`. Line breaks are kept so line numbers
+//! still match the original file, and blocks without a handler are left alone.
+//!
+
+use std::{any::Any, borrow::Cow, collections::HashMap};
+
+type Handler = Box<dyn Fn(&str) -> Result<Box<dyn Any>, String>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The reasons a custom section cannot be extracted.
+pub enum SectionError {
+    /// A `\BEGIN` marker has no matching `\END` marker.
+    Unterminated { name: String, line: usize },
+    /// The handler rejected the contents of the section.
+    Handler { name: String, message: String },
+}
+
+impl std::fmt::Display for SectionError {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unterminated { name, line } => write!(f, "Section {name} starting on line {line} has no \\END {name} marker"),
+            Self::Handler { name, message } => write!(f, "Failed to handle section {name}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for SectionError {}
+
+#[derive(Debug, Default)]
+/// The values produced by custom section handlers, keyed by section name.
+pub struct Extensions {
+    values: HashMap<String, Box<dyn Any>>,
+}
+
+impl Extensions {
+    #[must_use]
+    #[inline]
+    /// Returns the value produced for section `name`, if it was found and produced a `T`.
+    pub fn get<T: Any>(&self, name: &str) -> Option<&T> {
+        self.values.get(&name.to_ascii_uppercase()).and_then(|value| value.downcast_ref())
+    }
+
+    #[must_use]
+    #[inline]
+    /// Returns `true` if section `name` was found.
+    pub fn contains(&self, name: &str) -> bool {
+        self.values.contains_key(&name.to_ascii_uppercase())
+    }
+
+    #[must_use]
+    #[inline]
+    /// Returns the number of sections found.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    #[must_use]
+    #[inline]
+    /// Returns `true` if no sections were found.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+#[derive(Default)]
+/// A registry of handlers for nonstandard sections.
+pub struct CustomSections {
+    handlers: HashMap<String, Handler>,
+}
+
+impl std::fmt::Debug for CustomSections {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut names: Vec<&String> = self.handlers.keys().collect();
+        names.sort();
+        f.debug_struct("CustomSections").field("handlers", &names).finish()
+    }
+}
+
+/// Returns the marker keyword and section name of a `\BEGIN NAME` or `\END NAME` line.
+fn marker(line: &str) -> Option<(&str, &str)> {
+    let mut words = line.trim_start().strip_prefix('\\')?.split_whitespace();
+    match (words.next(), words.next(), words.next()) {
+        (Some(keyword), Some(name), None) => Some((keyword, name)),
+        _ => None,
+    }
+}
+
+impl CustomSections {
+    #[must_use]
+    #[inline]
+    /// Creates a registry without any handlers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    #[inline]
+    /// Registers `handler` for the section `name`, compared without regard to case.
+    ///
+    /// The handler receives the text between the markers, including any
+    /// leading `\`, and returns the value to store or an error message.
+    pub fn with_handler<T, F>(mut self, name: &str, handler: F) -> Self
+    where
+        T: Any,
+        F: Fn(&str) -> Result<T, String> + 'static,
+    {
+        let handler: Handler = Box::new(move |text| handler(text).map(|value| Box::new(value) as Box<dyn Any>));
+        self.handlers.insert(name.to_ascii_uppercase(), handler);
+        self
+    }
+
+    /// Runs the handlers over the registered sections of `input`.
+    ///
+    /// Returns the input with those sections blanked out, borrowed when there
+    /// were none, and the values the handlers produced. When a section occurs
+    /// more than once, the last one wins.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SectionError` if a section is not terminated or its handler fails.
+    pub fn extract<'a>(&self, input: &'a str) -> Result<(Cow<'a, str>, Extensions), SectionError> {
+        let mut extensions = Extensions::default();
+        let mut output = String::new();
+        let mut open: Option<(String, usize, usize)> = None;
+        let mut offset = 0;
+
+        for (index, line) in input.split_inclusive('\n').enumerate() {
+            let content = line.trim_end_matches(['\r', '\n']);
+            let line_break = &line[content.len()..];
+            match (&open, marker(content)) {
+                (None, Some((keyword, name)))
+                    if keyword.eq_ignore_ascii_case("BEGIN") && self.handlers.contains_key(&name.to_ascii_uppercase()) =>
+                {
+                    open = Some((name.to_ascii_uppercase(), offset + line.len(), index + 1));
+                    output.push_str(line_break);
+                }
+                (Some((name, start, _)), Some((keyword, end_name)))
+                    if keyword.eq_ignore_ascii_case("END") && end_name.eq_ignore_ascii_case(name) =>
+                {
+                    let value = self.handlers[name](&input[*start..offset])
+                        .map_err(|message| SectionError::Handler { name: name.clone(), message })?;
+                    extensions.values.insert(name.clone(), value);
+                    open = None;
+                    output.push_str(line_break);
+                }
+                (Some(_), _) => output.push_str(line_break),
+                (None, _) => output.push_str(line),
+            }
+            offset += line.len();
+        }
+
+        match open {
+            Some((name, _, line)) => Err(SectionError::Unterminated { name, line }),
+            None if extensions.is_empty() => Ok((Cow::Borrowed(input), extensions)),
+            None => Ok((Cow::Owned(output), extensions)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        parsers::custom_section::{CustomSections, SectionError},
+        problem::LpProblem,
+    };
+
+    #[test]
+    fn test_custom_sections() {
+        let input = "\\BEGIN METADATA\nauthor: planning\nrevision: 7\n\\END METADATA\nminimize\nobj: x\nsubject to\nc1: x >= 1\nend\n";
+        let sections = CustomSections::new().with_handler("metadata", |text| {
+            text.lines()
+                .map(|line| line.split_once(": ").map(|(key, value)| (key.to_string(), value.to_string())).ok_or(line.to_string()))
+                .collect::<Result<Vec<_>, _>>()
+        });
+
+        let (stripped, extensions) = sections.extract(input).expect("test case not to fail");
+        assert_eq!(stripped, "\n\n\n\nminimize\nobj: x\nsubject to\nc1: x >= 1\nend\n");
+        assert_eq!(extensions.len(), 1);
+        let metadata = extensions.get::<Vec<(String, String)>>("METADATA").expect("test case not to fail");
+        assert_eq!(metadata[1], ("revision".to_string(), "7".to_string()));
+        assert!(extensions.get::<String>("metadata").is_none());
+        assert_eq!(LpProblem::parse(&stripped).expect("test case not to fail").constraint_count(), 1);
+
+        let (untouched, extensions) = CustomSections::new().extract(input).expect("test case not to fail");
+        assert_eq!(untouched, input);
+        assert!(extensions.is_empty());
+
+        assert_eq!(
+            sections.extract("\\BEGIN METADATA\nbroken\n\\END METADATA\n").unwrap_err(),
+            SectionError::Handler { name: "METADATA".to_string(), message: "broken".to_string() }
+        );
+        assert_eq!(
+            sections.extract("minimize\n\\begin metadata\n").unwrap_err(),
+            SectionError::Unterminated { name: "METADATA".to_string(), line: 2 }
+        );
+    }
+}
@@ -4,10 +4,13 @@
 
 pub mod coefficient;
 pub mod constraint;
+pub mod custom_section;
 pub mod number;
 pub mod objective;
+pub mod parameter;
 pub mod parser_traits;
 pub mod problem_name;
 pub mod sense;
 pub mod sos_constraint;
 pub mod variable;
+pub mod visitor;
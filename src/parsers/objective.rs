@@ -23,7 +23,7 @@ use unique_id::{sequence::SequenceGenerator, Generator as _};
 
 use crate::{
     log_unparsed_content,
-    model::{Coefficient, Objective, Variable},
+    model::{Attributes, Coefficient, Objective, Variable},
     parsers::{coefficient::parse_coefficient, parser_traits::parse_variable},
 };
 
@@ -63,6 +63,12 @@ type ObjectiveParseResult<'a> = IResult<&'a str, (HashMap<Cow<'a, str>, Objectiv
 /// if parsing fails.
 ///
 pub fn parse_objectives(input: &str) -> ObjectiveParseResult<'_> {
+    let (input, (objectives, objective_vars)) = parse_objective_list(input)?;
+    Ok((input, (objectives.into_iter().map(|ob| (ob.name.clone(), ob)).collect(), objective_vars)))
+}
+
+/// Parses the objectives like `parse_objectives`, keeping them in source order.
+pub(crate) fn parse_objective_list(input: &str) -> IResult<&str, (Vec<Objective<'_>>, HashMap<&str, Variable<'_>>)> {
     let mut objective_vars = HashMap::with_capacity(2);
     let gen = SequenceGenerator;
 
@@ -95,6 +101,7 @@ pub fn parse_objectives(input: &str) -> ObjectiveParseResult<'_> {
                     Cow::Owned(format!("OBJECTIVE_{next}"))
                 },
                 coefficients,
+                attributes: Attributes::new(),
             }
         },
     );
@@ -102,7 +109,7 @@ pub fn parse_objectives(input: &str) -> ObjectiveParseResult<'_> {
     let (remaining, objectives) = many1(parser)(input)?;
 
     log_unparsed_content("Failed to parse objectives fully", remaining);
-    Ok(("", (objectives.into_iter().map(|ob| (ob.name.clone(), ob)).collect(), objective_vars)))
+    Ok(("", (objectives, objective_vars)))
 }
 
 #[cfg(test)]
@@ -0,0 +1,470 @@
+//! Named parameters in place of numeric literals.
+//!
+//! A parameter is declared on a comment line, so solvers that do not know the
+//! convention skip it, and may then be written wherever a coefficient,
+//! right-hand side or bound is expected:
+//!
+//! ```text
+//! \param CAP = 100
+//! Maximize
+//!  obj: 3 x + 2 y
+//! Subject To
+//!  c1: x + y <= CAP
+//! Bounds
+//!  x <= CAP
+//! End
+//! ```
+//!
+//! `Parameters::extract` returns the source with every parameter replaced by
+//! its value, ready for `LpProblem::parse`, together with the positions each
+//! parameter was substituted into. `Parameters::set` then writes a new value
+//! straight into those positions of the parsed problem, without going back to
+//! the source.
+//!
+//! Parameters can be used as the coefficients and right-hand sides of
+//! objectives and constraints, and as bounds. Rows without a label are
+//! referred to as `R1`, `R2`, ... by their position in the section. Names are case sensitive and must
+//! not clash with variable names. As the problem name is taken from the last
+//! comment before the first section, declare parameters ahead of it.
+//!
+
+use std::{borrow::Cow, collections::BTreeMap, ops::Range};
+
+use crate::{
+    model::{Constraint, VariableType},
+    problem::LpProblem,
+    syntax::{
+        cst::{SectionKind, SyntaxTree},
+        Token, TokenKind,
+    },
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The reasons parameters cannot be extracted or set.
+pub enum ParameterError {
+    /// A `\param` line is not of the form `\param NAME = VALUE`.
+    Invalid { line: usize, text: String },
+    /// A parameter is declared more than once.
+    Duplicate { name: String, line: usize },
+    /// A parameter is used somewhere other than a coefficient, right-hand side or bound.
+    Unsupported { name: String, line: usize },
+    /// No parameter has the name.
+    Unknown { name: String },
+    /// The problem has no entity the parameter was substituted into.
+    Missing { name: String, entity: String },
+}
+
+impl std::fmt::Display for ParameterError {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid { line, text } => {
+                write!(f, "Cannot read parameter on line {line}: expected `\\param NAME = VALUE`, found `{text}`")
+            }
+            Self::Duplicate { name, line } => write!(f, "Cannot declare parameter {name} on line {line}: it is already declared"),
+            Self::Unsupported { name, line } => {
+                write!(f, "Cannot substitute parameter {name} on line {line}: it is not a coefficient, right-hand side or bound")
+            }
+            Self::Unknown { name } => write!(f, "Cannot set parameter {name}: it is not declared"),
+            Self::Missing { name, entity } => write!(f, "Cannot set parameter {name}: the problem has no {entity}"),
+        }
+    }
+}
+
+impl std::error::Error for ParameterError {}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A position of the model a parameter was substituted into.
+pub enum ParameterUse {
+    /// The coefficient of the term at `index` in objective or constraint `name`.
+    Coefficient { name: String, objective: bool, index: usize, negated: bool },
+    /// The right-hand side of constraint `name`.
+    Rhs { name: String, negated: bool },
+    /// The lower or upper bound of `variable`.
+    Bound { variable: String, upper: bool, negated: bool },
+}
+
+impl ParameterUse {
+    #[must_use]
+    #[inline]
+    const fn negated(&self) -> bool {
+        match self {
+            Self::Coefficient { negated, .. } | Self::Rhs { negated, .. } | Self::Bound { negated, .. } => *negated,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// The declared parameters of a source and where each was substituted.
+pub struct Parameters {
+    values: BTreeMap<String, f64>,
+    uses: BTreeMap<String, Vec<ParameterUse>>,
+    /// The position among the rows without a label of each `R1`, `R2`, ... row a parameter is used in, keyed by `objective`.
+    unlabelled: BTreeMap<(bool, String), usize>,
+    /// The number of constraints and objectives without a label.
+    unlabelled_counts: [usize; 2],
+}
+
+/// Returns the name and value text of a `\param NAME = VALUE` line, or `None` if it is not a declaration.
+fn declaration(line: &str) -> Option<Result<(&str, &str), ()>> {
+    let rest = line.trim_start().strip_prefix('\\')?;
+    let rest = rest.get(..5).filter(|keyword| keyword.eq_ignore_ascii_case("param")).map(|_| &rest[5..])?;
+    if !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+    Some(match rest.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() && !name.trim().contains(char::is_whitespace) => Ok((name.trim(), value.trim())),
+        _ => Err(()),
+    })
+}
+
+/// Returns `true` for a `<=`, `=<` or `<` operator.
+fn is_less(operator: &str) -> bool {
+    operator.contains('<')
+}
+
+impl Parameters {
+    /// Reads the parameter declarations of `input` and substitutes their values.
+    ///
+    /// Returns the input with every parameter replaced by its value, borrowed
+    /// when no parameter is used, and the parameters with their uses.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParameterError` if a declaration is malformed or repeated, or
+    /// a parameter is used where it cannot be set later.
+    pub fn extract(input: &str) -> Result<(Cow<'_, str>, Self), ParameterError> {
+        let mut parameters = Self::default();
+        for (index, line) in input.lines().enumerate() {
+            let invalid = || ParameterError::Invalid { line: index + 1, text: line.trim().to_string() };
+            if let Some(declaration) = declaration(line) {
+                let (name, value) = declaration.map_err(|()| invalid())?;
+                let value: f64 = value.parse().ok().filter(|value: &f64| !value.is_nan()).ok_or_else(invalid)?;
+                if parameters.values.insert(name.to_string(), value).is_some() {
+                    return Err(ParameterError::Duplicate { name: name.to_string(), line: index + 1 });
+                }
+            }
+        }
+        if parameters.values.is_empty() {
+            return Ok((Cow::Borrowed(input), parameters));
+        }
+
+        let tree = SyntaxTree::parse(input);
+        let mut replacements: Vec<(Range<usize>, String)> = Vec::new();
+        // The number of rows and of rows without a label read so far, for constraints and objectives
+        let mut positions = [(0, 0); 2];
+        for (kind, section) in tree.sections() {
+            for statement in section.nodes() {
+                let objective = kind == SectionKind::Objective;
+                let row = match kind {
+                    SectionKind::Objective | SectionKind::Constraints => {
+                        let (rows, unlabelled) = &mut positions[usize::from(objective)];
+                        *rows += 1;
+                        Some(statement.label().map_or_else(
+                            || {
+                                *unlabelled += 1;
+                                (format!("R{rows}"), Some(*unlabelled - 1))
+                            },
+                            |label| (label.to_string(), None),
+                        ))
+                    }
+                    _ => None,
+                };
+                let tokens: Vec<&Token<'_>> = statement.tokens().into_iter().filter(|token| !token.kind.is_trivia()).collect();
+                for (index, token) in tokens.iter().enumerate() {
+                    let value = match parameters.values.get(token.text) {
+                        Some(value) if token.kind == TokenKind::Identifier => *value,
+                        _ => continue,
+                    };
+                    if tokens.get(index + 1).map_or(false, |next| next.kind == TokenKind::Colon) {
+                        continue;
+                    }
+                    let sign =
+                        Some(index).filter(|index| *index > 0).map(|index| tokens[index - 1]).filter(|token| token.kind == TokenKind::Sign);
+                    let negated = sign.map_or(false, |sign| sign.text == "-");
+                    let start = if statement.label().is_some() { 2 } else { 0 };
+                    let usage =
+                        parameters.locate(kind, row.as_ref().map(|(name, _)| name.as_str()), &tokens[start..], index - start, negated);
+                    let usage = usage.ok_or_else(|| ParameterError::Unsupported {
+                        name: token.text.to_string(),
+                        line: input[..token.span.start].matches('\n').count() + 1,
+                    })?;
+                    if let Some((name, Some(position))) = &row {
+                        parameters.unlabelled.insert((objective, name.clone()), *position);
+                    }
+                    parameters.uses.entry(token.text.to_string()).or_default().push(usage);
+
+                    match sign {
+                        Some(sign) if value < 0.0 => {
+                            let flipped = if negated { '+' } else { '-' };
+                            replacements.push((
+                                sign.span.start..token.span.end,
+                                format!("{flipped}{}{}", &input[sign.span.end..token.span.start], -value),
+                            ));
+                        }
+                        _ => replacements.push((token.span.clone(), format!("{value}"))),
+                    }
+                }
+            }
+        }
+        parameters.unlabelled_counts = positions.map(|(_, unlabelled)| unlabelled);
+        if replacements.is_empty() {
+            return Ok((Cow::Borrowed(input), parameters));
+        }
+
+        let mut output = String::with_capacity(input.len());
+        let mut position = 0;
+        for (span, replacement) in replacements {
+            output.push_str(&input[position..span.start]);
+            output.push_str(&replacement);
+            position = span.end;
+        }
+        output.push_str(&input[position..]);
+        Ok((Cow::Owned(output), parameters))
+    }
+
+    /// Returns where the parameter at `index` of a statement's significant `tokens`, without its label, ends up in the model.
+    fn locate(&self, kind: SectionKind, row: Option<&str>, tokens: &[&Token<'_>], index: usize, negated: bool) -> Option<ParameterUse> {
+        let is_variable = |token: &Token<'_>| token.kind == TokenKind::Identifier && !self.values.contains_key(token.text);
+        match kind {
+            SectionKind::Objective | SectionKind::Constraints => {
+                let name = row?.to_string();
+                if tokens.get(index + 1).map_or(false, |next| is_variable(next)) {
+                    let terms = tokens[..index].iter().filter(|token| is_variable(token)).count();
+                    return Some(ParameterUse::Coefficient { name, objective: kind == SectionKind::Objective, index: terms, negated });
+                }
+                let operator = tokens[..index].iter().rposition(|token| token.kind == TokenKind::Operator)?;
+                let rhs = index + 1 == tokens.len()
+                    && (operator + 1 == index || (operator + 2 == index && tokens[index - 1].kind == TokenKind::Sign));
+                (kind == SectionKind::Constraints && rhs).then_some(ParameterUse::Rhs { name, negated })
+            }
+            SectionKind::Bounds => {
+                let variable = tokens.iter().position(|token| is_variable(token))?;
+                let operator = if variable < index { tokens[variable + 1] } else { tokens.get(index + 1)? };
+                if operator.kind != TokenKind::Operator || operator.text == "=" {
+                    return None;
+                }
+                let upper = is_less(operator.text) == (variable < index);
+                Some(ParameterUse::Bound { variable: tokens[variable].text.to_string(), upper, negated })
+            }
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Returns the value of parameter `name`.
+    pub fn get(&self, name: &str) -> Option<f64> {
+        self.values.get(name).copied()
+    }
+
+    #[inline]
+    /// Returns the parameters and their values, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.values.iter().map(|(name, value)| (name.as_str(), *value))
+    }
+
+    #[must_use]
+    #[inline]
+    /// Returns the positions parameter `name` was substituted into.
+    pub fn uses(&self, name: &str) -> &[ParameterUse] {
+        self.uses.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Sets parameter `name` to `value` and writes it into every position of `problem` it was substituted into.
+    ///
+    /// `problem` must have been parsed from the source returned by `extract`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParameterError` if the parameter is not declared or the
+    /// problem lacks an objective, constraint or variable it was used in. On
+    /// error nothing is changed.
+    pub fn set(&mut self, problem: &mut LpProblem<'_>, name: &str, value: f64) -> Result<(), ParameterError> {
+        if !self.values.contains_key(name) {
+            return Err(ParameterError::Unknown { name: name.to_string() });
+        }
+        let uses = self.uses(name);
+        let missing = |entity: String| ParameterError::Missing { name: name.to_string(), entity };
+        let mut rows = Vec::with_capacity(uses.len());
+        for usage in uses {
+            let row = match usage {
+                ParameterUse::Coefficient { name, objective, .. } => self.row_name(problem, *objective, name),
+                ParameterUse::Rhs { name, .. } => self.row_name(problem, false, name),
+                ParameterUse::Bound { .. } => None,
+            };
+            let present = match (usage, row.as_deref()) {
+                (ParameterUse::Coefficient { objective: true, index, .. }, Some(row)) => {
+                    problem.objectives.get(row).map_or(false, |objective| *index < objective.coefficients.len())
+                }
+                (ParameterUse::Coefficient { index, .. }, Some(row)) => {
+                    matches!(problem.constraints.get(row), Some(Constraint::Standard { coefficients, .. }) if *index < coefficients.len())
+                }
+                (ParameterUse::Rhs { .. }, Some(row)) => matches!(problem.constraints.get(row), Some(Constraint::Standard { .. })),
+                (ParameterUse::Bound { variable, .. }, _) => problem.variables.contains_key(variable.as_str()),
+                (_, None) => false,
+            };
+            if !present {
+                return Err(missing(match usage {
+                    ParameterUse::Coefficient { name, index, .. } => format!("term {} in {name}", index + 1),
+                    ParameterUse::Rhs { name, .. } => format!("constraint {name}"),
+                    ParameterUse::Bound { variable, .. } => format!("variable {variable}"),
+                }));
+            }
+            rows.push(row);
+        }
+
+        for (usage, row) in uses.iter().zip(rows) {
+            let signed = if usage.negated() { -value } else { value };
+            let row = row.as_deref().unwrap_or_default();
+            match usage {
+                ParameterUse::Coefficient { objective, index, .. } => {
+                    let coefficients = if *objective {
+                        problem.objectives.get_mut(row).map(|objective| &mut objective.coefficients)
+                    } else {
+                        match problem.constraints.get_mut(row) {
+                            Some(Constraint::Standard { coefficients, .. }) => Some(coefficients),
+                            _ => None,
+                        }
+                    };
+                    if let Some(coefficients) = coefficients {
+                        coefficients[*index].coefficient = signed;
+                    }
+                }
+                ParameterUse::Rhs { .. } => {
+                    if let Some(Constraint::Standard { rhs, .. }) = problem.constraints.get_mut(row) {
+                        *rhs = signed;
+                    }
+                }
+                ParameterUse::Bound { variable, upper, .. } => {
+                    if let Some(variable) = problem.variables.get_mut(variable.as_str()) {
+                        let bounds =
+                            if variable.has_bound_type() { Some(&mut variable.var_type) } else { variable.declared_bounds.as_mut() };
+                        if let Some(bounds) = bounds {
+                            *bounds = with_bound(bounds, *upper, signed);
+                        }
+                    }
+                }
+            }
+        }
+        self.values.insert(name.to_string(), value);
+        Ok(())
+    }
+
+    /// Returns the name `problem` gives to objective or constraint `name`.
+    ///
+    /// The parser names rows without a label from a shared counter, so the row
+    /// at position `n` among them has the `n`th lowest generated name. Returns
+    /// `None` when the problem does not have one generated name per such row,
+    /// rather than guess which row was meant.
+    fn row_name(&self, problem: &LpProblem<'_>, objective: bool, name: &str) -> Option<String> {
+        let position = match self.unlabelled.get(&(objective, name.to_string())) {
+            Some(position) => *position,
+            None => return Some(name.to_string()),
+        };
+        let (prefix, names): (_, Vec<&str>) = if objective {
+            ("OBJECTIVE_", problem.objectives.keys().map(AsRef::as_ref).collect())
+        } else {
+            ("CONSTRAINT_", problem.constraints.keys().map(AsRef::as_ref).collect())
+        };
+        let mut generated: Vec<(i64, &str)> =
+            names.into_iter().filter_map(|row| row.strip_prefix(prefix)?.parse().ok().map(|id| (id, row))).collect();
+        if generated.len() != self.unlabelled_counts[usize::from(objective)] {
+            return None;
+        }
+        generated.sort_unstable();
+        generated.get(position).map(|(_, row)| (*row).to_string())
+    }
+}
+
+/// Returns `bounds` with its lower or upper bound replaced by `value`.
+fn with_bound(bounds: &VariableType, upper: bool, value: f64) -> VariableType {
+    let (lower_bound, upper_bound) = match bounds {
+        VariableType::LowerBound(lower) => (Some(*lower), None),
+        VariableType::UpperBound(upper) => (None, Some(*upper)),
+        VariableType::DoubleBound(lower, upper) => (Some(*lower), Some(*upper)),
+        _ => (None, None),
+    };
+    let (lower_bound, upper_bound) = if upper { (lower_bound, Some(value)) } else { (Some(value), upper_bound) };
+    match (lower_bound, upper_bound) {
+        (Some(lower), Some(upper)) => VariableType::DoubleBound(lower, upper),
+        (Some(lower), None) => VariableType::LowerBound(lower),
+        (None, Some(upper)) => VariableType::UpperBound(upper),
+        (None, None) => bounds.clone(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        model::{Constraint, VariableType},
+        parsers::parameter::{ParameterError, ParameterUse, Parameters},
+        problem::LpProblem,
+    };
+
+    #[test]
+    fn test_parameters() {
+        let input = "\\param CAP = 100\n\\PARAM cost = -2.5\n\\Problem name: demo\nmaximize\nobj: 3 x - cost y\nsubject to\nc1: x + y <= CAP\nc2: cost x + y >= -CAP\nbounds\nx <= CAP\n-CAP <= y <= 40\nend\n";
+        let (source, mut parameters) = Parameters::extract(input).expect("test case not to fail");
+        assert_eq!(
+            source,
+            "\\param CAP = 100\n\\PARAM cost = -2.5\n\\Problem name: demo\nmaximize\nobj: 3 x + 2.5 y\nsubject to\nc1: x + y <= 100\nc2: -2.5 x + y >= -100\nbounds\nx <= 100\n-100 <= y <= 40\nend\n"
+        );
+        assert_eq!(parameters.iter().collect::<Vec<_>>(), [("CAP", 100.0), ("cost", -2.5)]);
+        assert_eq!(
+            parameters.uses("cost")[0],
+            ParameterUse::Coefficient { name: "obj".to_string(), objective: true, index: 1, negated: true }
+        );
+        assert_eq!(parameters.uses("CAP").len(), 4);
+
+        let mut problem = LpProblem::parse(&source).expect("test case not to fail");
+        parameters.set(&mut problem, "CAP", 120.0).expect("test case not to fail");
+        parameters.set(&mut problem, "cost", 4.0).expect("test case not to fail");
+        assert_eq!(parameters.get("CAP"), Some(120.0));
+
+        let updated = input.replace("= 100", "= 120").replace("= -2.5", "= 4");
+        let (resubstituted, _) = Parameters::extract(&updated).expect("test case not to fail");
+        assert_eq!(problem, LpProblem::parse(&resubstituted).expect("test case not to fail"));
+        assert!(matches!(problem.constraints["c2"], Constraint::Standard { rhs, .. } if rhs == -120.0));
+        assert_eq!(problem.variables["y"].var_type, VariableType::DoubleBound(-120.0, 40.0));
+
+        assert_eq!(parameters.set(&mut problem, "cap", 1.0), Err(ParameterError::Unknown { name: "cap".to_string() }));
+        assert_eq!(
+            Parameters::extract("\\param CAP 100\n").unwrap_err(),
+            ParameterError::Invalid { line: 1, text: "\\param CAP 100".to_string() }
+        );
+        assert_eq!(
+            Parameters::extract("\\param A = 1\n\\param A = 2\n").unwrap_err(),
+            ParameterError::Duplicate { name: "A".to_string(), line: 2 }
+        );
+        assert_eq!(
+            Parameters::extract("\\param A = 1\nminimize\nobj: x\nsubject to\n-A <= x <= 3\nend").unwrap_err(),
+            ParameterError::Unsupported { name: "A".to_string(), line: 5 }
+        );
+        let (untouched, parameters) = Parameters::extract("\\ parameters: none\nminimize\nobj: x\nend").expect("test case not to fail");
+        assert_eq!(untouched, "\\ parameters: none\nminimize\nobj: x\nend");
+        assert_eq!(parameters, Parameters::default());
+    }
+
+    #[test]
+    fn test_parameters_unlabelled_rows() {
+        let input = "\\param A = 2\nminimize\n A x + y\nsubject to\nc1: x >= 1\n -x + A y >= A\nend\n";
+        let (source, mut parameters) = Parameters::extract(input).expect("test case not to fail");
+        assert_eq!(
+            parameters.uses("A"),
+            [
+                ParameterUse::Coefficient { name: "R1".to_string(), objective: true, index: 0, negated: false },
+                ParameterUse::Coefficient { name: "R2".to_string(), objective: false, index: 1, negated: false },
+                ParameterUse::Rhs { name: "R2".to_string(), negated: false },
+            ]
+        );
+
+        let mut problem = LpProblem::parse(&source).expect("test case not to fail");
+        parameters.set(&mut problem, "A", 5.0).expect("test case not to fail");
+        let objective = problem.objectives.values().next().expect("test case not to fail");
+        assert_eq!(objective.coefficients[0].coefficient, 5.0);
+        let row = problem.constraints.iter().find(|(name, _)| *name != "c1").map(|(_, row)| row);
+        assert!(matches!(row, Some(Constraint::Standard { coefficients, rhs, .. }) if coefficients[1].coefficient == 5.0 && *rhs == 5.0));
+    }
+}
@@ -22,7 +22,7 @@ use nom::{
 
 use crate::{
     log_unparsed_content,
-    model::{Coefficient, Constraint, SOSType, Variable, VariableType},
+    model::{Attributes, Coefficient, Constraint, SOSType, Variable, VariableType},
     parsers::{number::parse_num_value, parser_traits::parse_variable},
 };
 
@@ -63,6 +63,12 @@ type ParsedConstraints<'a> = IResult<&'a str, (HashMap<Cow<'a, str>, Constraint<
 /// variables, or an error if parsing fails.
 ///
 pub fn parse_sos_section<'a>(input: &'a str) -> ParsedConstraints<'a> {
+    let (input, (constraints, constraint_vars)) = parse_sos_list(input)?;
+    Ok((input, (constraints.into_iter().map(|c| (Cow::Owned(c.name().to_string()), c)).collect(), constraint_vars)))
+}
+
+/// Parses a section of SOS constraints like `parse_sos_section`, keeping them in source order.
+pub(crate) fn parse_sos_list<'a>(input: &'a str) -> IResult<&'a str, (Vec<Constraint<'a>>, HashMap<&'a str, Variable<'a>>)> {
     let mut constraint_vars: HashMap<&'a str, Variable<'a>> = HashMap::default();
 
     let parser = map(
@@ -81,13 +87,11 @@ pub fn parse_sos_section<'a>(input: &'a str) -> ParsedConstraints<'a> {
                 }
             }
 
-            Constraint::SOS { name: Cow::Borrowed(name), sos_type, weights }
+            Constraint::SOS { name: Cow::Borrowed(name), sos_type, weights, attributes: Attributes::new() }
         },
     );
 
     let (remaining, constraints) = preceded(tuple((multispace0, tag_no_case("SOS"), opt(char(':')), multispace1)), many1(parser))(input)?;
-    let constraints = constraints.into_iter().map(|c| (Cow::Owned(c.name().to_string()), c)).collect();
-
     log_unparsed_content("Failed to parse sos constraints fully", remaining);
     Ok(("", (constraints, constraint_vars)))
 }
@@ -0,0 +1,144 @@
+//! Callbacks invoked while a problem is parsed.
+//!
+//! `LpProblem::parse_with_visitor` reports each section as it starts and each
+//! objective, constraint and variable declaration once its section has been
+//! parsed, in source order. A visitor can gather statistics, report progress
+//! through a large file, or return `ControlFlow::Break` to stop parsing early,
+//! in which case the problem holds every section parsed up to that point.
+//!
+//! This is not a streaming API: the input is held in memory, and each section
+//! is parsed in full before its items are visited.
+//!
+
+use std::ops::ControlFlow;
+
+use crate::{
+    model::{Constraint, Objective, VariableType},
+    syntax::cst::SectionKind,
+};
+
+/// Receives the parts of a problem as it is parsed.
+///
+/// Every method continues by default, so a visitor only implements the ones it needs.
+pub trait ParseVisitor<'a> {
+    #[inline]
+    /// Called before a section is parsed, with its byte offset in the input.
+    fn section(&mut self, _kind: SectionKind, _offset: usize) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    #[inline]
+    /// Called for each objective.
+    fn objective(&mut self, _objective: &Objective<'a>) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    #[inline]
+    /// Called for each constraint, including SOS constraints.
+    fn constraint(&mut self, _constraint: &Constraint<'a>) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    #[inline]
+    /// Called for each bound and each integer, general, binary or semi-continuous declaration.
+    fn variable(&mut self, _name: &'a str, _var_type: &VariableType) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+}
+
+/// The visitor that continues through every callback.
+impl ParseVisitor<'_> for () {}
+
+#[cfg(test)]
+mod test {
+    use std::ops::ControlFlow;
+
+    use crate::{
+        model::{Constraint, Objective, VariableType},
+        parsers::visitor::ParseVisitor,
+        problem::LpProblem,
+        syntax::cst::SectionKind,
+    };
+
+    #[derive(Default)]
+    struct Names(Vec<String>);
+
+    impl<'a> ParseVisitor<'a> for Names {
+        fn objective(&mut self, objective: &Objective<'a>) -> ControlFlow<()> {
+            self.0.push(objective.name.to_string());
+            ControlFlow::Continue(())
+        }
+
+        fn constraint(&mut self, constraint: &Constraint<'a>) -> ControlFlow<()> {
+            self.0.push(constraint.name().to_string());
+            ControlFlow::Continue(())
+        }
+
+        fn variable(&mut self, name: &'a str, _: &VariableType) -> ControlFlow<()> {
+            self.0.push(name.to_string());
+            ControlFlow::Continue(())
+        }
+    }
+
+    #[derive(Default)]
+    struct Counter {
+        sections: Vec<(SectionKind, usize)>,
+        objectives: usize,
+        constraints: usize,
+        variables: usize,
+        limit: Option<usize>,
+    }
+
+    impl<'a> ParseVisitor<'a> for Counter {
+        fn section(&mut self, kind: SectionKind, offset: usize) -> ControlFlow<()> {
+            self.sections.push((kind, offset));
+            ControlFlow::Continue(())
+        }
+
+        fn objective(&mut self, _: &Objective<'a>) -> ControlFlow<()> {
+            self.objectives += 1;
+            ControlFlow::Continue(())
+        }
+
+        fn constraint(&mut self, _: &Constraint<'a>) -> ControlFlow<()> {
+            self.constraints += 1;
+            match self.limit {
+                Some(limit) if self.constraints >= limit => ControlFlow::Break(()),
+                _ => ControlFlow::Continue(()),
+            }
+        }
+
+        fn variable(&mut self, _: &'a str, _: &VariableType) -> ControlFlow<()> {
+            self.variables += 1;
+            ControlFlow::Continue(())
+        }
+    }
+
+    #[test]
+    fn test_parse_visitor() {
+        let input = "minimize\nobj: x + y\nsubject to\nc1: x + y >= 1\nc2: x <= 4\nbounds\ny <= 3\nintegers\nx\nend";
+        let mut counter = Counter::default();
+        let problem = LpProblem::parse_with_visitor(input, &mut counter).expect("test case not to fail");
+        assert_eq!(problem, LpProblem::parse(input).expect("test case not to fail"));
+        assert_eq!((counter.objectives, counter.constraints, counter.variables), (1, 2, 2));
+        assert_eq!(
+            counter.sections,
+            [(SectionKind::Objective, 0), (SectionKind::Constraints, 20), (SectionKind::Bounds, 57), (SectionKind::Integers, 71)]
+        );
+
+        let mut counter = Counter { limit: Some(1), ..Counter::default() };
+        let problem = LpProblem::parse_with_visitor(input, &mut counter).expect("test case not to fail");
+        assert_eq!(counter.constraints, 1);
+        assert_eq!(problem.constraint_count(), 2);
+        assert!(counter.sections.iter().all(|(kind, _)| *kind != SectionKind::Bounds));
+        assert!(!problem.variables["y"].has_bound_type());
+    }
+    #[test]
+    fn test_parse_visitor_source_order() {
+        let input = "minimize\nobj3: x\nobj1: y\nobj2: z\nsubject to\nk: x >= 1\nb: y >= 1\nz1: z >= 1\na: x + y >= 1\nm: y + z >= 1\nc: x + z >= 1\n\
+            semi-continuous\nz\nsos\ns2: S1:: x:1 y:2\ns1: S1:: y:1 z:2\nend";
+        let mut names = Names::default();
+        LpProblem::parse_with_visitor(input, &mut names).expect("test case not to fail");
+        assert_eq!(names.0, ["obj3", "obj1", "obj2", "k", "b", "z1", "a", "m", "c", "z", "s2", "s1"]);
+    }
+}
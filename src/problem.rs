@@ -7,28 +7,64 @@
 
 use std::{
     borrow::Cow,
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap, HashSet},
 };
 
 use nom::{combinator::opt, error::Error, sequence::tuple, Err};
 
 use crate::{
+    diff::{
+        build_diff_report, hash_of,
+        summary::{constraint_fingerprint, objective_fingerprint, variable_fingerprint},
+        DiffOptions,
+    },
     is_binary_section, is_bounds_section, is_generals_section, is_integers_section, is_semi_section, is_sos_section,
-    model::{Constraint, Objective, Sense, Variable, VariableType},
+    model::{
+        BoundChange, Coefficient, ComparisonOp, Constraint, Objective, SOSType, Sense, Solution, SolutionDifference, SolutionViolation,
+        Variable, VariableType,
+    },
     parsers::{
-        constraint::{parse_constraint_header, parse_constraints},
-        objective::parse_objectives,
+        constraint::{parse_constraint_header, parse_constraint_list},
+        objective::parse_objective_list,
         problem_name::parse_problem_name,
         sense::parse_sense,
-        sos_constraint::parse_sos_section,
+        sos_constraint::parse_sos_list,
         variable::{parse_binary_section, parse_bounds_section, parse_generals_section, parse_integer_section, parse_semi_section},
+        visitor::ParseVisitor,
     },
+    syntax::cst::{SectionKind, SyntaxTree},
     take_until_parser, ALL_BOUND_HEADERS, BINARY_HEADERS, CONSTRAINT_HEADERS, END_HEADER, GENERAL_HEADERS, INTEGER_HEADERS, SEMI_HEADERS,
     SOS_HEADERS,
 };
 
+/// The version of the serialized `LpProblem` layout, written to its `schema_version` field.
+///
+/// Documents without the field predate versioning and share the layout of version `1`.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The absolute tolerance used by `LpProblem::check_solution`.
+const DEFAULT_SOLUTION_TOLERANCE: f64 = 1e-6;
+
+/// The number of rows and terms printed by the alternate `Display` form, `{:#}`.
+pub const DEFAULT_ALGEBRAIC_LIMIT: usize = 50;
+
+/// The maximum number of passes over the constraints made by `LpProblem::tighten_bounds`.
+const MAX_PROPAGATION_ROUNDS: usize = 20;
+
+/// The relative improvement below which `LpProblem::tighten_bounds` ignores a tighter bound.
+const PROPAGATION_TOLERANCE: f64 = 1e-9;
+
+/// Returns the smallest and largest value of `coefficient * x` for `x` in `[lower, upper]`.
+#[inline]
+fn term_range(coefficient: f64, (lower, upper): (f64, f64)) -> (f64, f64) {
+    if coefficient > 0.0 {
+        (coefficient * lower, coefficient * upper)
+    } else {
+        (coefficient * upper, coefficient * lower)
+    }
+}
+
 #[cfg_attr(feature = "diff", derive(diff::Diff), diff(attr(#[derive(Debug, PartialEq)])))]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[derive(Debug, Default, PartialEq)]
 /// Represents a Linear Programming (LP) problem.
 ///
@@ -42,6 +78,13 @@ use crate::{
 /// * `#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]`:
 ///   Enables serialization and deserialization of `LpProblem` instances when the `serde` feature is active.
 ///
+/// # Serialized Form
+///
+/// The serialized form uses the snake case field names below and records the
+/// layout in a `schema_version` field, currently `SCHEMA_VERSION`. Field names
+/// only change together with a new version, and deserialization accepts every
+/// earlier version, including documents written before the field existed.
+///
 pub struct LpProblem<'a> {
     /// An optional reference to a string slice representing the name of the LP problem.
     pub name: Option<Cow<'a, str>>,
@@ -119,6 +162,153 @@ impl<'a> LpProblem<'a> {
         TryFrom::try_from(input)
     }
 
+    /// Parse a `Self` from a string slice, reporting its parts to `visitor` as they are parsed.
+    ///
+    /// Each section is parsed in full before its objectives, constraints and
+    /// declarations are reported, in source order.
+    ///
+    /// Parsing stops early when the visitor returns `ControlFlow::Break`, and the
+    /// problem then holds the sections parsed so far.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not a valid LP problem.
+    pub fn parse_with_visitor<V: ParseVisitor<'a> + ?Sized>(source: &'a str, visitor: &mut V) -> Result<Self, Err<Error<&'a str>>> {
+        let offset = |rest: &str| source.len() - rest.len();
+        macro_rules! visit {
+            ($problem:ident, $flow:expr) => {
+                if $flow.is_break() {
+                    return Ok($problem);
+                }
+            };
+        }
+        macro_rules! visit_types {
+            ($problem:ident, $names:expr, $var_type:expr) => {
+                for name in $names {
+                    visit!($problem, visitor.variable(name, &$var_type));
+                }
+            };
+        }
+
+        // Problem name and Sense
+        let mut problem = Self::new();
+        visit!(problem, visitor.section(SectionKind::Objective, 0));
+        let (input, (name, sense, obj_section)) = tuple((parse_problem_name, parse_sense, take_until_parser(&CONSTRAINT_HEADERS)))(source)?;
+        let (_, (objectives, variables)) = parse_objective_list(obj_section)?;
+        problem.name = name;
+        problem.sense = sense;
+        problem.variables = variables;
+        let flow = objectives.iter().try_for_each(|objective| visitor.objective(objective));
+        problem.objectives.extend(objectives.into_iter().map(|objective| (objective.name.clone(), objective)));
+        visit!(problem, flow);
+
+        // Constraints
+        visit!(problem, visitor.section(SectionKind::Constraints, offset(input)));
+        let (input, ()) = parse_constraint_header(input)?;
+        let (mut input, constraint_str) = take_until_parser(&ALL_BOUND_HEADERS)(input)?;
+        let (_, (constraints, constraint_vars)) = parse_constraint_list(constraint_str)?;
+        problem.variables.extend(constraint_vars);
+        let flow = constraints.iter().try_for_each(|constraint| visitor.constraint(constraint));
+        problem.constraints.extend(constraints.into_iter().map(|constraint| (Cow::Owned(constraint.name().to_string()), constraint)));
+        visit!(problem, flow);
+
+        // Bound
+        if is_bounds_section(input).is_ok() {
+            visit!(problem, visitor.section(SectionKind::Bounds, offset(input)));
+            let (rem_input, bound_str) = take_until_parser(&INTEGER_HEADERS)(input)?;
+            let (_, bounds) = parse_bounds_section(bound_str)?;
+
+            for (name, var_type) in bounds {
+                visit!(problem, visitor.variable(name, &var_type));
+                let variable = problem.variables.entry(name).or_insert_with(|| Variable::new(name));
+                // Record an explicit `free`, which `VariableType::Free` alone cannot tell apart from an undeclared variable
+                variable.declared_bounds = (var_type == VariableType::Free).then_some(VariableType::Free);
+                variable.set_var_type(var_type);
+            }
+
+            input = rem_input;
+        }
+
+        // Integer
+        if is_integers_section(input).is_ok() {
+            visit!(problem, visitor.section(SectionKind::Integers, offset(input)));
+            if let Ok((rem_input, Some(integer_str))) = opt(take_until_parser(&GENERAL_HEADERS))(input) {
+                if let Ok((_, integer_vars)) = parse_integer_section(integer_str) {
+                    visit_types!(problem, integer_vars.iter().copied(), VariableType::Integer);
+                    set_var_types(&mut problem.variables, integer_vars, VariableType::Integer);
+                }
+                input = rem_input;
+            }
+        }
+
+        // General
+        if is_generals_section(input).is_ok() {
+            visit!(problem, visitor.section(SectionKind::Generals, offset(input)));
+            if let Ok((rem_input, Some(generals_str))) = opt(take_until_parser(&BINARY_HEADERS))(input) {
+                if let Ok((_, general_vars)) = parse_generals_section(generals_str) {
+                    visit_types!(problem, general_vars.iter().copied(), VariableType::General);
+                    set_var_types(&mut problem.variables, general_vars, VariableType::General);
+                }
+                input = rem_input;
+            }
+        }
+
+        // Binary
+        if is_binary_section(input).is_ok() {
+            visit!(problem, visitor.section(SectionKind::Binaries, offset(input)));
+            if let Ok((rem_input, Some(binary_str))) = opt(take_until_parser(&SEMI_HEADERS))(input) {
+                if let Ok((_, binary_vars)) = parse_binary_section(binary_str) {
+                    visit_types!(problem, binary_vars.iter().copied(), VariableType::Binary);
+                    set_var_types(&mut problem.variables, binary_vars, VariableType::Binary);
+                }
+                input = rem_input;
+            }
+        }
+
+        // Semi-continuous
+        if is_semi_section(input).is_ok() {
+            visit!(problem, visitor.section(SectionKind::SemiContinuous, offset(input)));
+            if let Ok((rem_input, Some(semi_str))) = opt(take_until_parser(&SOS_HEADERS))(input) {
+                if let Ok((_, semi_vars)) = parse_semi_section(semi_str) {
+                    visit_types!(problem, semi_vars.iter().copied(), VariableType::SemiContinuous);
+                    set_var_types(&mut problem.variables, semi_vars, VariableType::SemiContinuous);
+                }
+                input = rem_input;
+            }
+        }
+
+        // SOS constraint
+        if is_sos_section(input).is_ok() {
+            visit!(problem, visitor.section(SectionKind::Sos, offset(input)));
+            if let Ok((rem_input, Some(sos_str))) = opt(take_until_parser(&END_HEADER))(input) {
+                if let Ok((_, Some((sos_constraints, constraint_vars)))) = opt(parse_sos_list)(sos_str) {
+                    problem.variables.extend(constraint_vars);
+                    let flow = sos_constraints.iter().try_for_each(|constraint| visitor.constraint(constraint));
+                    problem
+                        .constraints
+                        .extend(sos_constraints.into_iter().map(|constraint| (Cow::Owned(constraint.name().to_string()), constraint)));
+                    visit!(problem, flow);
+                }
+                input = rem_input;
+            }
+        }
+
+        if input.len() > 3 {
+            log::warn!("Unused input not parsed by `LpProblem`: {input}");
+        }
+
+        Ok(problem)
+    }
+
+    #[inline]
+    /// Parse a `Self` from a string slice together with its lossless `SyntaxTree`.
+    ///
+    /// The tree keeps the comments, layout and exact number text that the model
+    /// drops, for tools that edit the source without rewriting it.
+    pub fn parse_with_syntax(input: &'a str) -> Result<(Self, SyntaxTree<'a>), Err<Error<&'a str>>> {
+        Ok((Self::parse(input)?, SyntaxTree::parse(input)))
+    }
+
     #[inline]
     /// Add a new variable to the problem.
     ///
@@ -167,126 +357,493 @@ impl<'a> LpProblem<'a> {
         let name = objective.name.clone();
         self.objectives.insert(name, objective);
     }
-}
 
-impl std::fmt::Display for LpProblem<'_> {
+    #[must_use]
     #[inline]
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "Problem: {}", self.name.as_ref().unwrap_or(&Cow::Borrowed("unnamed LpProblem")))?;
-        writeln!(f, "Sense: {}", self.sense)?;
-        writeln!(f, "Objectives: {}", self.objectives.len())?;
-        writeln!(f, "Constraints: {}", self.constraints.len())?;
-        writeln!(f, "Variables: {}", self.variables.len())?;
-        Ok(())
+    /// Checks `solution` against the constraints, bounds, integrality and SOS
+    /// restrictions of the problem using an absolute tolerance of `1e-6`.
+    ///
+    /// Returns an empty `Vec` when the solution is feasible.
+    pub fn check_solution(&self, solution: &Solution) -> Vec<SolutionViolation> {
+        self.check_solution_with_tolerance(solution, DEFAULT_SOLUTION_TOLERANCE)
     }
-}
 
-#[inline]
-fn set_var_types<'a>(variables: &mut HashMap<&'a str, Variable<'a>>, vars: Vec<&'a str>, var_type: VariableType) {
-    for name in vars {
-        match variables.entry(name) {
-            Entry::Occupied(mut occupied_entry) => {
-                occupied_entry.get_mut().set_var_type(var_type.clone());
+    #[must_use]
+    /// Checks `solution` against the problem using the absolute `tolerance`.
+    ///
+    /// Variables missing from the solution are treated as `0.0`.
+    pub fn check_solution_with_tolerance(&self, solution: &Solution, tolerance: f64) -> Vec<SolutionViolation> {
+        let mut violations = Vec::new();
+
+        let mut unknown: Vec<&String> = solution.values.keys().filter(|name| !self.variables.contains_key(name.as_str())).collect();
+        unknown.sort_unstable();
+        violations.extend(unknown.into_iter().map(|name| SolutionViolation::UnknownVariable { name: name.clone() }));
+
+        let mut variables: Vec<&Variable<'a>> = self.variables.values().collect();
+        variables.sort_unstable_by(|a, b| a.name.cmp(b.name));
+        for variable in variables {
+            let value = solution.value(variable.name);
+            let (lower, upper) = variable.effective_bounds();
+            let semi_zero = variable.var_type == VariableType::SemiContinuous && value.abs() <= tolerance;
+            if !semi_zero && (value < lower - tolerance || value > upper + tolerance) {
+                violations.push(SolutionViolation::Bound { name: variable.name.to_string(), value, lower, upper });
             }
-            Entry::Vacant(vacant_entry) => {
-                vacant_entry.insert(Variable { name, var_type: var_type.clone() });
+            if variable.var_type.is_integer() && (value - value.round()).abs() > tolerance {
+                violations.push(SolutionViolation::Integrality { name: variable.name.to_string(), value });
             }
         }
-    }
-}
-
-impl<'a> TryFrom<&'a str> for LpProblem<'a> {
-    type Error = Err<Error<&'a str>>;
 
-    #[inline]
-    fn try_from(input: &'a str) -> Result<Self, Self::Error> {
-        // Problem name and Sense
-        let (input, (name, sense, obj_section, ())) =
-            tuple((parse_problem_name, parse_sense, take_until_parser(&CONSTRAINT_HEADERS), parse_constraint_header))(input)?;
-        let (_, (objectives, mut variables)) = parse_objectives(obj_section)?;
-
-        // Constraints
-        let (mut input, constraint_str) = take_until_parser(&ALL_BOUND_HEADERS)(input)?;
-        let (_, (mut constraints, constraint_vars)) = parse_constraints(constraint_str)?;
-        variables.extend(constraint_vars);
-
-        // Bound
-        if is_bounds_section(input).is_ok() {
-            let (rem_input, bound_str) = take_until_parser(&INTEGER_HEADERS)(input)?;
-            let (_, bounds) = parse_bounds_section(bound_str)?;
-
-            for (name, var_type) in bounds {
-                match variables.entry(name) {
-                    Entry::Occupied(mut occupied_entry) => {
-                        occupied_entry.get_mut().set_var_type(var_type);
+        let mut constraints: Vec<&Constraint<'a>> = self.constraints.values().collect();
+        constraints.sort_unstable_by(|a, b| a.name().cmp(&b.name()));
+        for constraint in constraints {
+            match constraint {
+                Constraint::Standard { name, coefficients, operator, rhs, .. } => {
+                    let activity: f64 = coefficients.iter().map(|c| c.coefficient * solution.value(c.var_name)).sum();
+                    let satisfied = match operator {
+                        ComparisonOp::LT | ComparisonOp::LTE => activity <= rhs + tolerance,
+                        ComparisonOp::GT | ComparisonOp::GTE => activity >= rhs - tolerance,
+                        ComparisonOp::EQ => (activity - rhs).abs() <= tolerance,
+                    };
+                    if !satisfied {
+                        violations.push(SolutionViolation::Constraint {
+                            name: name.to_string(),
+                            activity,
+                            operator: operator.clone(),
+                            rhs: *rhs,
+                        });
                     }
-                    Entry::Vacant(vacant_entry) => {
-                        vacant_entry.insert(Variable { name, var_type });
+                }
+                Constraint::SOS { name, sos_type, weights, .. } => {
+                    let mut members: Vec<_> = weights.iter().collect();
+                    members.sort_by(|a, b| a.coefficient.total_cmp(&b.coefficient));
+                    let non_zero: Vec<usize> =
+                        members.iter().enumerate().filter(|(_, m)| solution.value(m.var_name).abs() > tolerance).map(|(i, _)| i).collect();
+                    let satisfied = match sos_type {
+                        SOSType::S1 => non_zero.len() <= 1,
+                        SOSType::S2 => non_zero.len() <= 1 || (non_zero.len() == 2 && non_zero[1] == non_zero[0] + 1),
+                    };
+                    if !satisfied {
+                        violations.push(SolutionViolation::SOS {
+                            name: name.to_string(),
+                            sos_type: *sos_type,
+                            non_zero: non_zero.into_iter().map(|i| members[i].var_name.to_string()).collect(),
+                        });
                     }
                 }
             }
-
-            input = rem_input;
         }
 
-        // Integer
-        if is_integers_section(input).is_ok() {
-            if let Ok((rem_input, Some(integer_str))) = opt(take_until_parser(&GENERAL_HEADERS))(input) {
-                if let Ok((_, integer_vars)) = parse_integer_section(integer_str) {
-                    set_var_types(&mut variables, integer_vars, VariableType::Integer);
+        violations
+    }
+
+    #[must_use]
+    /// Evaluates the left-hand side of each standard constraint at `solution`, keyed by constraint name.
+    ///
+    /// Variables missing from the solution are treated as `0.0`.
+    pub fn activities(&self, solution: &Solution) -> BTreeMap<String, f64> {
+        self.constraints
+            .iter()
+            .filter_map(|(name, constraint)| match constraint {
+                Constraint::Standard { coefficients, .. } => {
+                    Some((name.to_string(), coefficients.iter().map(|c| c.coefficient * solution.value(c.var_name)).sum()))
                 }
-                input = rem_input;
+                Constraint::SOS { .. } => None,
+            })
+            .collect()
+    }
+
+    #[must_use]
+    /// Compares `solution` of this problem with `other_solution` of `other`, reporting
+    /// the status, objective value, variable values and constraint activities that
+    /// differ by more than the absolute `tolerance`.
+    ///
+    /// Variables and constraints are matched by name. Those found in only one problem
+    /// are always reported, and values are only compared when both statuses carry them.
+    pub fn compare_solutions(
+        &self,
+        solution: &Solution,
+        other: &LpProblem<'_>,
+        other_solution: &Solution,
+        tolerance: f64,
+    ) -> Vec<SolutionDifference> {
+        let differs = |left: Option<f64>, right: Option<f64>| match (left, right) {
+            (Some(left), Some(right)) => (left - right).abs() > tolerance,
+            (None, None) => false,
+            _ => true,
+        };
+
+        let mut differences = Vec::new();
+        if solution.status != other_solution.status {
+            differences.push(SolutionDifference::Status { left: solution.status, right: other_solution.status });
+        }
+        if differs(solution.objective, other_solution.objective) {
+            differences.push(SolutionDifference::Objective { left: solution.objective, right: other_solution.objective });
+        }
+        if !solution.status.has_values() || !other_solution.status.has_values() {
+            return differences;
+        }
+
+        let names: BTreeSet<&str> = self.variables.keys().chain(other.variables.keys()).copied().collect();
+        for name in names {
+            let left = self.variables.contains_key(name).then(|| solution.value(name));
+            let right = other.variables.contains_key(name).then(|| other_solution.value(name));
+            if differs(left, right) {
+                differences.push(SolutionDifference::Variable { name: name.to_string(), left, right });
             }
         }
 
-        // General
-        if is_generals_section(input).is_ok() {
-            if let Ok((rem_input, Some(generals_str))) = opt(take_until_parser(&BINARY_HEADERS))(input) {
-                if let Ok((_, general_vars)) = parse_generals_section(generals_str) {
-                    set_var_types(&mut variables, general_vars, VariableType::General);
-                }
-                input = rem_input;
+        let (left_activities, right_activities) = (self.activities(solution), other.activities(other_solution));
+        let names: BTreeSet<&String> = left_activities.keys().chain(right_activities.keys()).collect();
+        for name in names {
+            let (left, right) = (left_activities.get(name).copied(), right_activities.get(name).copied());
+            if differs(left, right) {
+                differences.push(SolutionDifference::Activity { name: name.clone(), left, right });
             }
         }
 
-        // Binary
-        if is_binary_section(input).is_ok() {
-            if let Ok((rem_input, Some(binary_str))) = opt(take_until_parser(&SEMI_HEADERS))(input) {
-                if let Ok((_, binary_vars)) = parse_binary_section(binary_str) {
-                    set_var_types(&mut variables, binary_vars, VariableType::Binary);
+        differences
+    }
+
+    /// Tightens the bounds of continuous variables by propagating the standard constraints.
+    ///
+    /// Each pass derives, for every term of every constraint, the bound that the
+    /// other terms' bounds imply, and repeats until no bound improves. Propagation
+    /// starts from `Variable::effective_bounds`, so variables declared `free` are
+    /// unbounded below. The bounds of integer and semi-continuous variables, and of
+    /// members of SOS constraints whatever their type, are used but not changed, and
+    /// a semi-continuous variable also admits zero. Bounds
+    /// that would cross are left alone, leaving the infeasibility for a solver to report.
+    ///
+    /// Returns the variables whose bounds changed, sorted by name.
+    pub fn tighten_bounds(&mut self) -> Vec<BoundChange> {
+        let mut bounds: HashMap<&'a str, (f64, f64)> = self
+            .variables
+            .iter()
+            .map(|(name, variable)| {
+                let (lower, upper) = variable.effective_bounds();
+                if variable.var_type == VariableType::SemiContinuous {
+                    (*name, (lower.min(0.0), upper.max(0.0)))
+                } else {
+                    (*name, (lower, upper))
+                }
+            })
+            .collect();
+        let original = bounds.clone();
+        let sos_members: HashSet<&str> = self
+            .constraints
+            .values()
+            .filter_map(|constraint| match constraint {
+                Constraint::SOS { weights, .. } => Some(weights.iter().map(|weight| weight.var_name)),
+                Constraint::Standard { .. } => None,
+            })
+            .flatten()
+            .collect();
+        let tightenable = |variable: &Variable| {
+            !sos_members.contains(variable.name)
+                && matches!(
+                    variable.var_type,
+                    VariableType::Free | VariableType::LowerBound(_) | VariableType::UpperBound(_) | VariableType::DoubleBound(..)
+                )
+        };
+
+        let mut constraints: Vec<(&Cow<'a, str>, &Constraint<'a>)> = self.constraints.iter().collect();
+        constraints.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        let mut rows: Vec<(Vec<(&'a str, f64)>, f64)> = Vec::new();
+        for (_, constraint) in constraints {
+            if let Constraint::Standard { coefficients, operator, rhs, .. } = constraint {
+                let terms: Vec<(&'a str, f64)> =
+                    coefficients.iter().filter(|c| c.coefficient != 0.0).map(|c| (c.var_name, c.coefficient)).collect();
+                // Every row is rewritten as `a . x <= b`; an equality contributes both halves.
+                if matches!(operator, ComparisonOp::LT | ComparisonOp::LTE | ComparisonOp::EQ) {
+                    rows.push((terms.clone(), *rhs));
+                }
+                if matches!(operator, ComparisonOp::GT | ComparisonOp::GTE | ComparisonOp::EQ) {
+                    rows.push((terms.into_iter().map(|(variable, c)| (variable, -c)).collect(), -rhs));
                 }
-                input = rem_input;
             }
         }
 
-        // Semi-continuous
-        if is_semi_section(input).is_ok() {
-            if let Ok((rem_input, Some(semi_str))) = opt(take_until_parser(&SOS_HEADERS))(input) {
-                if let Ok((_, semi_vars)) = parse_semi_section(semi_str) {
-                    set_var_types(&mut variables, semi_vars, VariableType::SemiContinuous);
+        for _ in 0..MAX_PROPAGATION_ROUNDS {
+            let mut changed = false;
+            for (terms, rhs) in &rows {
+                let ranges: Vec<(f64, f64)> = terms
+                    .iter()
+                    .map(|(variable, c)| term_range(*c, bounds.get(variable).copied().unwrap_or((0.0, f64::INFINITY))))
+                    .collect();
+                let infinite = ranges.iter().filter(|(min, _)| min.is_infinite()).count();
+                let finite_min: f64 = ranges.iter().map(|(min, _)| *min).filter(|min| min.is_finite()).sum();
+                for ((variable, coefficient), (min, _)) in terms.iter().zip(&ranges) {
+                    if !self.variables.get(variable).map_or(false, tightenable) {
+                        continue;
+                    }
+                    // The smallest activity of the other terms, which must be finite to imply anything.
+                    let rest = match (infinite, min.is_infinite()) {
+                        (0, _) => finite_min - min,
+                        (1, true) => finite_min,
+                        _ => continue,
+                    };
+                    let limit = (rhs - rest) / coefficient;
+                    let (lower, upper) = bounds[variable];
+                    let tightened = if *coefficient > 0.0 {
+                        (lower, if limit < upper - PROPAGATION_TOLERANCE * (1.0 + limit.abs()) { limit } else { upper })
+                    } else {
+                        (if limit > lower + PROPAGATION_TOLERANCE * (1.0 + limit.abs()) { limit } else { lower }, upper)
+                    };
+                    if tightened != (lower, upper) && tightened.0 <= tightened.1 {
+                        bounds.insert(variable, tightened);
+                        changed = true;
+                    }
                 }
-                input = rem_input;
+            }
+            if !changed {
+                break;
             }
         }
 
-        // SOS constraint
-        if is_sos_section(input).is_ok() {
-            if let Ok((rem_input, Some(sos_str))) = opt(take_until_parser(&END_HEADER))(input) {
-                if let Ok((_, Some((sos_constraints, constraint_vars)))) = opt(parse_sos_section)(sos_str) {
-                    variables.extend(constraint_vars);
-                    for (name, constraint) in sos_constraints {
-                        constraints.insert(name, constraint);
+        let mut changes: Vec<BoundChange> = bounds
+            .into_iter()
+            .filter(|(name, new)| original[name] != *new)
+            .map(|(name, new)| BoundChange { name: name.to_string(), old: original[name], new })
+            .collect();
+        changes.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        for change in &changes {
+            let (lower, upper) = change.new;
+            let var_type = if upper == f64::INFINITY {
+                VariableType::LowerBound(lower)
+            } else if lower == 0.0 {
+                VariableType::UpperBound(upper)
+            } else {
+                VariableType::DoubleBound(lower, upper)
+            };
+            if let Some(variable) = self.variables.get_mut(change.name.as_str()) {
+                variable.var_type = var_type;
+                variable.declared_bounds = None;
+            }
+        }
+        changes
+    }
+
+    #[must_use]
+    #[inline]
+    /// Returns `true` if both problems define the same model.
+    ///
+    /// Objectives, constraints and variables are compared by name, ignoring
+    /// map iteration order and the order of terms within an expression. The
+    /// problem name is not compared.
+    pub fn semantic_eq(&self, other: &LpProblem) -> bool {
+        build_diff_report(self, other, &DiffOptions::default()).is_empty()
+    }
+
+    #[must_use]
+    /// Returns a hash of the model that is independent of map iteration order and term order.
+    ///
+    /// Problems that are `semantic_eq` have the same canonical hash. The hash is
+    /// stable within a build of this crate, but is not guaranteed to be stable
+    /// across Rust or crate versions, so it should not be persisted long term.
+    pub fn canonical_hash(&self) -> u64 {
+        fn sorted<K: AsRef<str>, V>(entries: &HashMap<K, V>, fingerprint: impl Fn(&V) -> u64) -> Vec<(&str, u64)> {
+            let mut entries: Vec<(&str, u64)> = entries.iter().map(|(name, value)| (name.as_ref(), fingerprint(value))).collect();
+            entries.sort_unstable();
+            entries
+        }
+
+        hash_of((
+            self.sense.is_minimization(),
+            sorted(&self.objectives, objective_fingerprint),
+            sorted(&self.constraints, constraint_fingerprint),
+            sorted(&self.variables, variable_fingerprint),
+        ))
+    }
+
+    #[must_use]
+    /// Renders the problem in readable mathematical form, as in `c1: 3 x1 + x2 ≤ 30`.
+    ///
+    /// Objectives, constraints and bounds are sorted by name. At most `limit`
+    /// rows are printed per section and `limit` terms per row, with a note of
+    /// how many were left out; pass `usize::MAX` to print everything.
+    pub fn to_algebraic_string(&self, limit: usize) -> String {
+        fn sorted_by_name<'p, V>(entries: impl Iterator<Item = (&'p str, V)>) -> Vec<(&'p str, V)> {
+            let mut entries: Vec<_> = entries.collect();
+            entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+            entries
+        }
+
+        let mut out = String::new();
+        if let Some(name) = &self.name {
+            out.push_str(&format!("Problem: {name}\n"));
+        }
+
+        out.push_str(&format!("{}\n", self.sense));
+        let objectives = sorted_by_name(self.objectives.iter().map(|(name, objective)| (name.as_ref(), objective)));
+        write_rows(&mut out, &objectives, limit, "objectives", |out, (name, objective)| {
+            out.push_str(&format!("{name}: "));
+            write_terms(out, &objective.coefficients, limit);
+        });
+
+        if !self.constraints.is_empty() {
+            out.push_str("Subject To\n");
+            let constraints = sorted_by_name(self.constraints.iter().map(|(name, constraint)| (name.as_ref(), constraint)));
+            write_rows(&mut out, &constraints, limit, "constraints", |out, (name, constraint)| {
+                out.push_str(&format!("{name}: "));
+                match constraint {
+                    Constraint::Standard { coefficients, operator, rhs, .. } => {
+                        write_terms(out, coefficients, limit);
+                        let symbol = match operator {
+                            ComparisonOp::LT => "<",
+                            ComparisonOp::LTE => "≤",
+                            ComparisonOp::EQ => "=",
+                            ComparisonOp::GTE => "≥",
+                            ComparisonOp::GT => ">",
+                        };
+                        out.push_str(&format!(" {symbol} {rhs}"));
+                    }
+                    Constraint::SOS { sos_type, weights, .. } => {
+                        let members: Vec<String> =
+                            weights.iter().take(limit).map(|weight| format!("{}:{}", weight.var_name, weight.coefficient)).collect();
+                        out.push_str(&format!("{sos_type} :: {}", members.join(", ")));
+                        if weights.len() > limit {
+                            out.push_str(&format!(", … ({} more members)", weights.len() - limit));
+                        }
                     }
                 }
-                input = rem_input;
+            });
+        }
+
+        let variables = sorted_by_name(self.variables.iter().map(|(name, variable)| (*name, variable)));
+        let bounds: Vec<String> = variables
+            .iter()
+            .filter_map(|(name, variable)| {
+                let bounds = if variable.has_bound_type() { Some(&variable.var_type) } else { variable.declared_bounds.as_ref() };
+                let rendered = match bounds? {
+                    VariableType::LowerBound(lower) => format!("{name} ≥ {lower}"),
+                    VariableType::UpperBound(upper) => format!("{name} ≤ {upper}"),
+                    VariableType::DoubleBound(lower, upper) if lower == upper => format!("{name} = {lower}"),
+                    VariableType::DoubleBound(lower, upper) => format!("{lower} ≤ {name} ≤ {upper}"),
+                    VariableType::Free => format!("{name} free"),
+                    _ => return None,
+                };
+                Some(rendered)
+            })
+            .collect();
+        if !bounds.is_empty() {
+            out.push_str("Bounds\n");
+            write_rows(&mut out, &bounds, limit, "bounds", |out, rendered| out.push_str(rendered));
+        }
+
+        for (header, var_type) in [
+            ("Generals", VariableType::General),
+            ("Integers", VariableType::Integer),
+            ("Binaries", VariableType::Binary),
+            ("Semi-Continuous", VariableType::SemiContinuous),
+        ] {
+            let names: Vec<&str> = variables.iter().filter(|(_, variable)| variable.var_type == var_type).map(|(name, _)| *name).collect();
+            if !names.is_empty() {
+                out.push_str(&format!("{header}\n  {}", names.iter().take(limit).copied().collect::<Vec<_>>().join(", ")));
+                if names.len() > limit {
+                    out.push_str(&format!(", … ({} more)", names.len() - limit));
+                }
+                out.push('\n');
             }
         }
+        out
+    }
+}
 
-        if input.len() > 3 {
-            log::warn!("Unused input not parsed by `LpProblem`: {input}");
+/// Appends up to `limit` of `rows` as indented lines, noting how many were left out.
+fn write_rows<T>(out: &mut String, rows: &[T], limit: usize, noun: &str, write: impl Fn(&mut String, &T)) {
+    for row in rows.iter().take(limit) {
+        out.push_str("  ");
+        write(out, row);
+        out.push('\n');
+    }
+    if rows.len() > limit {
+        out.push_str(&format!("  … {} more {noun}\n", rows.len() - limit));
+    }
+}
+
+/// Appends up to `limit` terms as a signed sum, such as `3 x1 - x2`.
+fn write_terms(out: &mut String, coefficients: &[Coefficient<'_>], limit: usize) {
+    if coefficients.is_empty() {
+        out.push('0');
+    }
+    for (index, term) in coefficients.iter().take(limit).enumerate() {
+        let magnitude = term.coefficient.abs();
+        match (index, term.coefficient < 0.0) {
+            (0, true) => out.push('-'),
+            (0, false) => {}
+            (_, true) => out.push_str(" - "),
+            (_, false) => out.push_str(" + "),
+        }
+        if magnitude != 1.0 {
+            out.push_str(&format!("{magnitude} "));
+        }
+        out.push_str(term.var_name);
+    }
+    if coefficients.len() > limit {
+        out.push_str(&format!(" + … ({} more terms)", coefficients.len() - limit));
+    }
+}
+
+/// Prints a summary of counts or, in the alternate form `{:#}`, the problem
+/// as `LpProblem::to_algebraic_string` with `DEFAULT_ALGEBRAIC_LIMIT`.
+impl std::fmt::Display for LpProblem<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            return f.write_str(&self.to_algebraic_string(DEFAULT_ALGEBRAIC_LIMIT));
+        }
+        writeln!(f, "Problem: {}", self.name.as_ref().unwrap_or(&Cow::Borrowed("unnamed LpProblem")))?;
+        writeln!(f, "Sense: {}", self.sense)?;
+        writeln!(f, "Objectives: {}", self.objectives.len())?;
+        writeln!(f, "Constraints: {}", self.constraints.len())?;
+        writeln!(f, "Variables: {}", self.variables.len())?;
+        Ok(())
+    }
+}
+
+#[inline]
+fn set_var_types<'a>(variables: &mut HashMap<&'a str, Variable<'a>>, vars: Vec<&'a str>, var_type: VariableType) {
+    for name in vars {
+        match variables.entry(name) {
+            Entry::Occupied(mut occupied_entry) => {
+                let variable = occupied_entry.get_mut();
+                if variable.has_bound_type() {
+                    variable.declared_bounds = Some(variable.var_type.clone());
+                }
+                variable.set_var_type(var_type.clone());
+            }
+            Entry::Vacant(vacant_entry) => {
+                vacant_entry.insert(Variable::new(name).with_var_type(var_type.clone()));
+            }
         }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for LpProblem<'a> {
+    type Error = Err<Error<&'a str>>;
+
+    #[inline]
+    fn try_from(input: &'a str) -> Result<Self, Self::Error> {
+        Self::parse_with_visitor(input, &mut ())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for LpProblem<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct as _;
 
-        Ok(LpProblem { name, sense, objectives, constraints, variables })
+        let mut state = serializer.serialize_struct("LpProblem", 6)?;
+        state.serialize_field("schema_version", &SCHEMA_VERSION)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("sense", &self.sense)?;
+        state.serialize_field("objectives", &self.objectives)?;
+        state.serialize_field("constraints", &self.constraints)?;
+        state.serialize_field("variables", &self.variables)?;
+        state.end()
     }
 }
 
@@ -303,6 +860,8 @@ impl<'de: 'a, 'a> serde::Deserialize<'de> for LpProblem<'a> {
             Constraints,
             Name,
             Objectives,
+            #[serde(rename = "schema_version")]
+            SchemaVersion,
             Sense,
             Variables,
         }
@@ -326,6 +885,14 @@ impl<'de: 'a, 'a> serde::Deserialize<'de> for LpProblem<'a> {
 
                 while let Some(key) = map.next_key()? {
                     match key {
+                        Field::SchemaVersion => {
+                            let version: u32 = map.next_value()?;
+                            if version > SCHEMA_VERSION {
+                                return Err(serde::de::Error::custom(format!(
+                                    "unsupported schema_version {version}, expected at most {SCHEMA_VERSION}"
+                                )));
+                            }
+                        }
                         Field::Name => {
                             if name.is_some() {
                                 return Err(serde::de::Error::duplicate_field("name"));
@@ -369,7 +936,7 @@ impl<'de: 'a, 'a> serde::Deserialize<'de> for LpProblem<'a> {
             }
         }
 
-        const FIELDS: &[&str] = &["name", "sense", "objectives", "constraints", "variables"];
+        const FIELDS: &[&str] = &["schema_version", "name", "sense", "objectives", "constraints", "variables"];
         deserializer.deserialize_struct("LpProblem", FIELDS, LpProblemVisitor(std::marker::PhantomData))
     }
 }
@@ -379,7 +946,10 @@ mod test {
     use std::borrow::Cow;
 
     use crate::{
-        model::{Coefficient, ComparisonOp, Constraint, Objective, Sense, Variable, VariableType},
+        model::{
+            Attributes, Coefficient, ComparisonOp, Constraint, Objective, SOSType, Sense, Solution, SolutionDifference, SolutionStatus,
+            SolutionViolation, Variable, VariableType,
+        },
         problem::LpProblem,
     };
 
@@ -473,6 +1043,73 @@ End";
         let _: LpProblem<'_> = serde_json::from_str(&serialized_problem).expect("test case not to fail");
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialization_infinite_bounds() {
+        let input = "minimize\nobj: x + y\nsubject to\nc1: x + y >= 1\nbounds\n-inf <= x <= 5\n-inf <= y <= 3\nintegers\ny\nend";
+        let problem = LpProblem::try_from(input).expect("test case not to fail");
+
+        let serialized = serde_json::to_string(&problem).expect("test case not to fail");
+        assert!(serialized.contains(r#""DoubleBound":["-inf",5.0]"#));
+        let deserialized: LpProblem<'_> = serde_json::from_str(&serialized).expect("test case not to fail");
+        assert_eq!(deserialized, problem);
+        assert_eq!(deserialized.variables["y"].effective_bounds(), (f64::NEG_INFINITY, 3.0));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_schema_version() {
+        let problem = LpProblem::try_from(SMALL_INPUT).expect("test case not to fail");
+        let serialized = serde_json::to_string(&problem).expect("test case not to fail");
+        assert!(serialized.starts_with(&format!("{{\"schema_version\":{},", crate::problem::SCHEMA_VERSION)));
+
+        // Documents written before the field existed
+        let legacy = serialized.replacen("\"schema_version\":1,", "", 1);
+        let deserialized: LpProblem<'_> = serde_json::from_str(&legacy).expect("test case not to fail");
+        assert_eq!(deserialized, problem);
+
+        let future = serialized.replacen("\"schema_version\":1,", "\"schema_version\":2,", 1);
+        let error = serde_json::from_str::<LpProblem<'_>>(&future).unwrap_err();
+        assert!(error.to_string().starts_with("unsupported schema_version 2"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_attributes() {
+        use crate::model::AttrValue;
+
+        let mut problem = LpProblem::try_from(SMALL_INPUT).expect("test case not to fail");
+        let constraint = problem.constraints.get_mut("c1").expect("test case not to fail");
+        constraint.attributes_mut().insert("owner".to_string(), AttrValue::from("planning"));
+        constraint.attributes_mut().insert("priority".to_string(), AttrValue::from(2));
+        problem.objectives.get_mut("obj1").expect("test case not to fail").attributes.insert("scale".to_string(), AttrValue::from(0.5));
+        problem.add_variable(Variable::new("x1").with_attribute("fixed", true));
+
+        let serialized = serde_json::to_string(&problem).expect("test case not to fail");
+        assert!(serialized.contains("\"attributes\":{\"owner\":\"planning\",\"priority\":2}"));
+        let deserialized: LpProblem<'_> = serde_json::from_str(&serialized).expect("test case not to fail");
+        assert_eq!(deserialized, problem);
+        assert_eq!(deserialized.constraints["c1"].attributes()["priority"], AttrValue::Integer(2));
+        assert_eq!(deserialized.variables["x1"].attributes["fixed"].to_string(), "true");
+        assert!(deserialized.constraints["c2"].attributes().is_empty());
+    }
+
+    #[test]
+    fn test_algebraic_string() {
+        let input = "\\demo\nmaximize\nprofit: 3 x1 + x2 - 2.5 x3\nsubject to\nc2: -x1 + 4 x2 >= -2\nc1: 3 x1 + x2 + x3 <= 30\nc3: x1 - x3 = 0\nbounds\nx1 <= 4\n1 <= x2 <= 8\nintegers\nx2\nend";
+        let problem = LpProblem::parse(input).expect("test case not to fail");
+        assert_eq!(
+            problem.to_algebraic_string(usize::MAX),
+            "Problem: demo\nMaximize\n  profit: 3 x1 + x2 - 2.5 x3\nSubject To\n  c1: 3 x1 + x2 + x3 ≤ 30\n  c2: -x1 + 4 x2 ≥ -2\n  c3: x1 - x3 = 0\nBounds\n  x1 ≤ 4\n  1 ≤ x2 ≤ 8\nIntegers\n  x2\n"
+        );
+        assert_eq!(
+            problem.to_algebraic_string(2),
+            "Problem: demo\nMaximize\n  profit: 3 x1 + x2 + … (1 more terms)\nSubject To\n  c1: 3 x1 + x2 + … (1 more terms) ≤ 30\n  c2: -x1 + 4 x2 ≥ -2\n  … 1 more constraints\nBounds\n  x1 ≤ 4\n  1 ≤ x2 ≤ 8\nIntegers\n  x2\n"
+        );
+        assert_eq!(format!("{problem:#}"), problem.to_algebraic_string(crate::problem::DEFAULT_ALGEBRAIC_LIMIT));
+        assert!(problem.to_string().starts_with("Problem: demo\nSense: Maximize\n"));
+    }
+
     #[test]
     fn test_add_variable() {
         let mut problem = LpProblem::new();
@@ -491,6 +1128,7 @@ End";
             coefficients: vec![Coefficient { var_name: "x1", coefficient: 1.0 }, Coefficient { var_name: "x2", coefficient: 2.0 }],
             operator: ComparisonOp::LTE,
             rhs: 5.0,
+            attributes: Attributes::new(),
         };
 
         problem.add_constraint(constraint);
@@ -501,13 +1139,159 @@ End";
     #[test]
     fn test_add_objective() {
         let mut problem = LpProblem::new().with_sense(Sense::Minimize).with_problem_name(Cow::Borrowed("test"));
-        let objective = Objective {
-            name: Cow::Borrowed("obj1"),
-            coefficients: vec![Coefficient { var_name: "x1", coefficient: 1.0 }, Coefficient { var_name: "x2", coefficient: -1.0 }],
-        };
+        let objective = Objective::new(
+            Cow::Borrowed("obj1"),
+            vec![Coefficient { var_name: "x1", coefficient: 1.0 }, Coefficient { var_name: "x2", coefficient: -1.0 }],
+        );
 
         problem.add_objective(objective);
         assert_eq!(problem.objective_count(), 1);
         assert_eq!(problem.variable_count(), 2);
     }
+
+    #[test]
+    fn test_check_solution() {
+        let problem = LpProblem::try_from(COMPLETE_INPUT).expect("test case not to fail");
+
+        let values = [("x1", 1.0), ("X31", 0.5), ("V1", 1.0), ("V3", 1.0), ("unknown", 1.0)];
+        let solution = Solution::new(SolutionStatus::Feasible).with_values(values.into_iter().map(|(k, v)| (k.to_string(), v)).collect());
+        let violations = problem.check_solution(&solution);
+
+        assert!(violations.contains(&SolutionViolation::UnknownVariable { name: "unknown".to_string() }));
+        assert!(violations.contains(&SolutionViolation::Integrality { name: "X31".to_string(), value: 0.5 }));
+        assert!(violations.iter().any(|v| matches!(v, SolutionViolation::Bound { name, .. } if name == "x2")));
+        assert!(violations.iter().any(|v| matches!(v, SolutionViolation::Constraint { name, .. } if name == "c1")));
+        assert!(violations.iter().any(|v| matches!(v, SolutionViolation::Constraint { name, .. } if name == "c2")));
+        assert!(violations.iter().any(|v| matches!(v, SolutionViolation::SOS { name, .. } if name == "csos1")));
+        assert!(!violations.iter().any(|v| matches!(v, SolutionViolation::Constraint { name, .. } if name == "c3")));
+
+        let problem = LpProblem::try_from("max\n obj: x + y\nst\n c1: x + y <= 4\nbounds\n y <= 3\nEnd").expect("test case not to fail");
+        let solution =
+            Solution::new(SolutionStatus::Optimal).with_objective(4.0).with_values([("x".to_string(), 1.0), ("y".to_string(), 3.0)].into());
+        assert!(problem.check_solution(&solution).is_empty());
+
+        let problem = LpProblem::parse(
+            "min\n obj: x + y + s\nst\n c1: x >= -3\nbounds\n x free\n 0 <= y <= 10\n 2 <= s <= 5\nintegers\n y\ngenerals\nbinaries\nsemi-continuous\n s\nEnd",
+        )
+        .expect("test case not to fail");
+        let solution = |values: [(&str, f64); 3]| {
+            Solution::new(SolutionStatus::Optimal).with_values(values.iter().map(|(k, v)| (k.to_string(), *v)).collect())
+        };
+        assert!(problem.check_solution(&solution([("x", -3.0), ("y", 10.0), ("s", 0.0)])).is_empty());
+        let violations = problem.check_solution(&solution([("x", -3.0), ("y", 50.0), ("s", 1.0)]));
+        assert_eq!(
+            violations,
+            [
+                SolutionViolation::Bound { name: "s".to_string(), value: 1.0, lower: 2.0, upper: 5.0 },
+                SolutionViolation::Bound { name: "y".to_string(), value: 50.0, lower: 0.0, upper: 10.0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare_solutions() {
+        let left = LpProblem::parse("max\n obj: x + y\nst\n c1: x + y <= 4\n c2: x - y >= 0\nEnd").expect("test case not to fail");
+        let right = LpProblem::parse("max\n obj: x + y + z\nst\n c1: x + y <= 4\n c3: z <= 1\nEnd").expect("test case not to fail");
+        let solution = |status, objective, values: &[(&str, f64)]| {
+            Solution::new(status).with_objective(objective).with_values(values.iter().map(|(k, v)| (k.to_string(), *v)).collect())
+        };
+
+        let a = solution(SolutionStatus::Optimal, 4.0, &[("x", 2.0), ("y", 2.0)]);
+        assert_eq!(left.activities(&a).into_iter().collect::<Vec<_>>(), [("c1".to_string(), 4.0), ("c2".to_string(), 0.0)]);
+        assert!(left
+            .compare_solutions(&a, &left, &solution(SolutionStatus::Optimal, 4.0 + 1e-9, &[("x", 2.0), ("y", 2.0)]), 1e-6)
+            .is_empty());
+
+        let b = solution(SolutionStatus::Optimal, 5.0, &[("x", 4.0), ("z", 1.0)]);
+        let differences: Vec<String> = left.compare_solutions(&a, &right, &b, 1e-6).iter().map(ToString::to_string).collect();
+        assert_eq!(
+            differences,
+            [
+                "Objective: 4 -> 5",
+                "Variable x: 2 -> 4",
+                "Variable y: 2 -> 0",
+                "Variable z: - -> 1",
+                "Constraint c2 activity: 0 -> -",
+                "Constraint c3 activity: - -> 1",
+            ]
+        );
+
+        let infeasible = Solution::new(SolutionStatus::Infeasible);
+        assert_eq!(
+            left.compare_solutions(&a, &right, &infeasible, 1e-6),
+            [
+                SolutionDifference::Status { left: SolutionStatus::Optimal, right: SolutionStatus::Infeasible },
+                SolutionDifference::Objective { left: Some(4.0), right: None },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tighten_bounds() {
+        let mut problem = LpProblem::parse(
+            "max\n obj: x + y + z + g\nst\n c1: x + y + g <= 4\n c2: z - x <= 1\nbounds\n y >= 1\nintegers\ngenerals\n g\nEnd",
+        )
+        .expect("test case not to fail");
+
+        let changes: Vec<_> = problem.tighten_bounds().iter().map(ToString::to_string).collect();
+        assert_eq!(
+            changes,
+            [
+                "Variable x bounds [0, inf] tightened to [0, 3]",
+                "Variable y bounds [1, inf] tightened to [1, 4]",
+                "Variable z bounds [0, inf] tightened to [0, 4]",
+            ]
+        );
+        assert_eq!(problem.variables["x"].var_type, VariableType::UpperBound(3.0));
+        assert_eq!(problem.variables["y"].var_type, VariableType::DoubleBound(1.0, 4.0));
+        assert_eq!(problem.variables["g"].var_type, VariableType::General);
+        assert!(problem.tighten_bounds().is_empty());
+
+        let mut problem = LpProblem::parse(
+            "max\n obj: x + y + z\nst\n c1: y + x <= 5\n c2: z + g <= 4\nbounds\n x free\n 2 <= g <= 3\nintegers\n g\nEnd",
+        )
+        .expect("test case not to fail");
+        let changes: Vec<_> = problem.tighten_bounds().iter().map(ToString::to_string).collect();
+        assert_eq!(changes, ["Variable x bounds [-inf, inf] tightened to [-inf, 5]", "Variable z bounds [0, inf] tightened to [0, 2]"]);
+        assert_eq!(problem.variables["x"].effective_bounds(), (f64::NEG_INFINITY, 5.0));
+        assert_eq!(problem.variables["y"].var_type, VariableType::Free);
+
+        // SOS members keep their declared bounds.
+        let mut problem = LpProblem::parse("max\n obj: x + y\nst\n c1: x + y <= 4\nbounds\n x <= 10\nEnd").expect("test case not to fail");
+        problem.add_constraint(Constraint::SOS {
+            name: Cow::Borrowed("s1"),
+            sos_type: SOSType::S1,
+            weights: vec![Coefficient { var_name: "x", coefficient: 1.0 }, Coefficient { var_name: "z", coefficient: 2.0 }],
+            attributes: Attributes::new(),
+        });
+        let changes: Vec<_> = problem.tighten_bounds().iter().map(ToString::to_string).collect();
+        assert_eq!(changes, ["Variable y bounds [0, inf] tightened to [0, 4]"]);
+        assert_eq!(problem.variables["x"].var_type, VariableType::UpperBound(10.0));
+    }
+
+    #[test]
+    fn test_semantic_eq() {
+        let problem = LpProblem::parse(COMPLETE_INPUT).expect("test case not to fail");
+        let reordered = LpProblem::parse("max\n obj: 2y + x\nst\n c2: y - x >= -1\n c1: y + x <= 4\nbounds\n y <= 3\nEnd")
+            .expect("test case not to fail");
+        let original = LpProblem::parse("max\n obj: x + 2y\nst\n c1: x + y <= 4\n c2: -x + y >= -1\nbounds\n y <= 3\nEnd")
+            .expect("test case not to fail");
+
+        assert!(original.semantic_eq(&reordered));
+        assert_eq!(original.canonical_hash(), reordered.canonical_hash());
+        assert!(!original.semantic_eq(&problem));
+        assert_ne!(original.canonical_hash(), problem.canonical_hash());
+
+        let renamed = LpProblem::parse("max\n obj: x + 2y\nst\n c1: x + y <= 4\n c3: -x + y >= -1\nbounds\n y <= 3\nEnd")
+            .expect("test case not to fail");
+        assert!(!original.semantic_eq(&renamed));
+        assert_ne!(original.canonical_hash(), renamed.canonical_hash());
+
+        let integer =
+            LpProblem::parse("max\n obj: x\nst\n c1: x >= 0\nbounds\n 0 <= x <= 10\nintegers\n x\nEnd").expect("test case not to fail");
+        let widened =
+            LpProblem::parse("max\n obj: x\nst\n c1: x >= 0\nbounds\n 0 <= x <= 20\nintegers\n x\nEnd").expect("test case not to fail");
+        assert!(!integer.semantic_eq(&widened));
+        assert_ne!(integer.canonical_hash(), widened.canonical_hash());
+    }
 }
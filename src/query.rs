@@ -0,0 +1,488 @@
+//! A small selector language for extracting data from a problem.
+//!
+//! A query names a collection, narrows it with filters and optionally picks
+//! one field of each match:
+//!
+//! ```text
+//! constraints[operator=">="][rhs>100].name
+//! variables[integer=true][upper<=1]
+//! objectives.coefficients.x
+//! ```
+//!
+//! - Collections are `objectives`, `constraints` and `variables`, visited in name order.
+//! - Filters compare a field with `=`, `!=`, `<`, `<=`, `>` or `>=` against a
+//!   number, `true`, `false` or a string, quoted with `"` or `'` when it holds
+//!   brackets or operators. Entities without the field never match.
+//! - Fields are listed by `Collection::fields`. `coefficients.NAME` and `attributes.NAME`
+//!   reach into the terms and metadata of an entity.
+//!
+//! Results are `QueryValue`s, which print as JSON.
+//!
+
+use std::{cmp::Ordering, fmt::Write};
+
+use crate::{
+    model::{AttrValue, Attributes, Coefficient, ComparisonOp, Constraint, Variable, VariableType},
+    problem::LpProblem,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The reasons a query cannot be parsed.
+pub enum QueryError {
+    /// The query does not follow the selector syntax.
+    Syntax { position: usize, message: String },
+    /// The collection is not `objectives`, `constraints` or `variables`.
+    UnknownCollection { name: String },
+    /// The collection has no such field.
+    UnknownField { collection: String, field: String },
+}
+
+impl std::fmt::Display for QueryError {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Syntax { position, message } => write!(f, "Cannot parse query at byte {position}: {message}"),
+            Self::UnknownCollection { name } => {
+                write!(f, "Cannot query {name}: expected objectives, constraints or variables")
+            }
+            Self::UnknownField { collection, field } => write!(f, "Cannot query {collection}: they have no field {field}"),
+        }
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A value selected by a query.
+pub enum QueryValue {
+    /// A field the entity does not have.
+    Null,
+    /// A flag.
+    Bool(bool),
+    /// A number.
+    Number(f64),
+    /// A name or label.
+    Text(String),
+    /// Fields in a fixed order, such as a whole entity or its coefficients.
+    Object(Vec<(String, QueryValue)>),
+    /// The results of a query.
+    List(Vec<QueryValue>),
+}
+
+impl QueryValue {
+    /// Returns the value of `field` in an object.
+    fn get(&self, field: &str) -> Option<&Self> {
+        match self {
+            Self::Object(fields) => fields.iter().find(|(name, _)| name == field).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Compares the value with a filter literal, numerically when both are numbers.
+    fn compare(&self, literal: &Literal) -> Option<Ordering> {
+        match (self, literal) {
+            (Self::Number(value), Literal::Number(other)) => value.partial_cmp(other),
+            (Self::Bool(value), Literal::Bool(other)) => Some(value.cmp(other)),
+            (Self::Text(value), Literal::Text(other)) => Some(value.as_str().cmp(other)),
+            _ => None,
+        }
+    }
+}
+
+/// Writes `text` as a JSON string.
+fn write_json_string(f: &mut std::fmt::Formatter<'_>, text: &str) -> std::fmt::Result {
+    f.write_char('"')?;
+    for c in text.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", u32::from(c))?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+/// Prints the value as JSON. Infinite numbers, which JSON cannot hold, print as the strings `"inf"` and `"-inf"`.
+impl std::fmt::Display for QueryValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Null => f.write_str("null"),
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Number(value) if value.is_finite() => write!(f, "{value}"),
+            Self::Number(value) if value.is_nan() => f.write_str("null"),
+            Self::Number(value) => f.write_str(if *value > 0.0 { "\"inf\"" } else { "\"-inf\"" }),
+            Self::Text(value) => write_json_string(f, value),
+            Self::Object(fields) => {
+                f.write_char('{')?;
+                for (index, (name, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_json_string(f, name)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_char('}')
+            }
+            Self::List(values) => {
+                f.write_char('[')?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_char(']')
+            }
+        }
+    }
+}
+
+impl From<&AttrValue> for QueryValue {
+    #[inline]
+    fn from(value: &AttrValue) -> Self {
+        match value {
+            AttrValue::Bool(value) => Self::Bool(*value),
+            AttrValue::Integer(value) => Self::Number(*value as f64),
+            AttrValue::Number(value) => Self::Number(*value),
+            AttrValue::Text(value) => Self::Text(value.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The kinds of entity a query can select.
+pub enum Collection {
+    /// `objectives`
+    Objectives,
+    /// `constraints`, including SOS constraints
+    Constraints,
+    /// `variables`
+    Variables,
+}
+
+impl Collection {
+    #[must_use]
+    /// Returns the fields of an entity in this collection.
+    pub const fn fields(self) -> &'static [&'static str] {
+        match self {
+            Self::Objectives => &["name", "terms", "coefficients", "attributes"],
+            Self::Constraints => &["name", "kind", "operator", "rhs", "sos_type", "terms", "coefficients", "attributes"],
+            Self::Variables => &["name", "type", "integer", "lower", "upper", "attributes"],
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Objectives => "objectives",
+            Self::Constraints => "constraints",
+            Self::Variables => "variables",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A literal on the right of a filter.
+enum Literal {
+    Number(f64),
+    Bool(bool),
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A condition such as `rhs>100`.
+struct Filter {
+    path: Vec<String>,
+    operator: ComparisonOp,
+    negated: bool,
+    literal: Literal,
+}
+
+impl Filter {
+    fn matches(&self, record: &QueryValue) -> bool {
+        let ordering = match lookup(record, &self.path).and_then(|value| value.compare(&self.literal)) {
+            Some(ordering) => ordering,
+            None => return false,
+        };
+        let holds = match self.operator {
+            ComparisonOp::LT => ordering.is_lt(),
+            ComparisonOp::LTE => ordering.is_le(),
+            ComparisonOp::EQ => ordering.is_eq(),
+            ComparisonOp::GTE => ordering.is_ge(),
+            ComparisonOp::GT => ordering.is_gt(),
+        };
+        holds != self.negated
+    }
+}
+
+/// Follows `path` through nested objects.
+fn lookup<'v>(record: &'v QueryValue, path: &[String]) -> Option<&'v QueryValue> {
+    path.iter().try_fold(record, |value, field| value.get(field))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A parsed query.
+pub struct Query {
+    collection: Collection,
+    filters: Vec<Filter>,
+    projection: Vec<String>,
+}
+
+/// Reads query text from left to right.
+struct Cursor<'q> {
+    text: &'q str,
+    position: usize,
+}
+
+impl<'q> Cursor<'q> {
+    fn rest(&self) -> &'q str {
+        &self.text[self.position..]
+    }
+
+    fn error(&self, message: impl Into<String>) -> QueryError {
+        QueryError::Syntax { position: self.position, message: message.into() }
+    }
+
+    fn eat(&mut self, prefix: &str) -> bool {
+        let found = self.rest().starts_with(prefix);
+        if found {
+            self.position += prefix.len();
+        }
+        found
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.position += self.rest().len() - self.rest().trim_start().len();
+    }
+
+    /// Reads a name made of anything but delimiters.
+    fn name(&mut self) -> Result<&'q str, QueryError> {
+        let rest = self.rest();
+        let len = rest.find(|c: char| matches!(c, '.' | '[' | ']' | '=' | '!' | '<' | '>') || c.is_whitespace()).unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("expected a name"));
+        }
+        self.position += len;
+        Ok(&rest[..len])
+    }
+
+    /// Reads a dotted field path such as `coefficients.x`.
+    fn path(&mut self) -> Result<Vec<String>, QueryError> {
+        let mut path = vec![self.name()?.to_string()];
+        while self.eat(".") {
+            path.push(self.name()?.to_string());
+        }
+        Ok(path)
+    }
+
+    fn literal(&mut self) -> Result<Literal, QueryError> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        if let Some(quote) = rest.chars().next().filter(|c| matches!(c, '"' | '\'')) {
+            let len = rest[1..].find(quote).ok_or_else(|| self.error("unterminated string"))?;
+            self.position += len + 2;
+            return Ok(Literal::Text(rest[1..=len].to_string()));
+        }
+        let len = rest.find(']').unwrap_or(rest.len());
+        let text = rest[..len].trim();
+        if text.is_empty() {
+            return Err(self.error("expected a value"));
+        }
+        self.position += len;
+        Ok(match text {
+            "true" => Literal::Bool(true),
+            "false" => Literal::Bool(false),
+            _ => text.parse().map_or_else(|_| Literal::Text(text.to_string()), Literal::Number),
+        })
+    }
+}
+
+impl Query {
+    /// Parses `text` as a query.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `QueryError` if the text is malformed or names an unknown collection or field.
+    pub fn parse(text: &str) -> Result<Self, QueryError> {
+        let mut cursor = Cursor { text: text.trim(), position: 0 };
+        let collection = match cursor.name()? {
+            "objectives" => Collection::Objectives,
+            "constraints" => Collection::Constraints,
+            "variables" => Collection::Variables,
+            name => return Err(QueryError::UnknownCollection { name: name.to_string() }),
+        };
+        let check = |path: &[String]| match path.first() {
+            Some(field) if collection.fields().contains(&field.as_str()) => Ok(()),
+            _ => Err(QueryError::UnknownField { collection: collection.name().to_string(), field: path.join(".") }),
+        };
+
+        let mut filters = Vec::new();
+        while cursor.eat("[") {
+            cursor.skip_whitespace();
+            let path = cursor.path()?;
+            check(&path)?;
+            cursor.skip_whitespace();
+            let (operator, negated) = [
+                ("!=", ComparisonOp::EQ, true),
+                ("<=", ComparisonOp::LTE, false),
+                (">=", ComparisonOp::GTE, false),
+                ("<", ComparisonOp::LT, false),
+                (">", ComparisonOp::GT, false),
+                ("=", ComparisonOp::EQ, false),
+            ]
+            .into_iter()
+            .find(|(symbol, ..)| cursor.eat(symbol))
+            .map(|(_, operator, negated)| (operator, negated))
+            .ok_or_else(|| cursor.error("expected one of =, !=, <, <=, >, >="))?;
+            let literal = cursor.literal()?;
+            cursor.skip_whitespace();
+            if !cursor.eat("]") {
+                return Err(cursor.error("expected ]"));
+            }
+            filters.push(Filter { path, operator, negated, literal });
+        }
+
+        let projection = if cursor.eat(".") { cursor.path()? } else { Vec::new() };
+        if !projection.is_empty() {
+            check(&projection)?;
+        }
+        if !cursor.rest().is_empty() {
+            return Err(cursor.error("unexpected text"));
+        }
+        Ok(Self { collection, filters, projection })
+    }
+
+    #[must_use]
+    /// Runs the query, returning the selected field of each match, or the whole entity without a projection.
+    pub fn run(&self, problem: &LpProblem<'_>) -> Vec<QueryValue> {
+        let mut records: Vec<(&str, QueryValue)> = match self.collection {
+            Collection::Objectives => problem
+                .objectives
+                .iter()
+                .map(|(name, objective)| (name.as_ref(), entity(name, None, &objective.coefficients, &objective.attributes)))
+                .collect(),
+            Collection::Constraints => {
+                problem.constraints.iter().map(|(name, constraint)| (name.as_ref(), constraint_record(constraint))).collect()
+            }
+            Collection::Variables => problem.variables.iter().map(|(name, variable)| (*name, variable_record(name, variable))).collect(),
+        };
+        records.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        records
+            .into_iter()
+            .filter(|(_, record)| self.filters.iter().all(|filter| filter.matches(record)))
+            .map(
+                |(_, record)| {
+                    if self.projection.is_empty() {
+                        record
+                    } else {
+                        lookup(&record, &self.projection).cloned().unwrap_or(QueryValue::Null)
+                    }
+                },
+            )
+            .collect()
+    }
+}
+
+/// Builds the record of an objective or constraint, inserting `extra` fields after the name.
+fn entity(name: &str, extra: Option<Vec<(String, QueryValue)>>, coefficients: &[Coefficient<'_>], attributes: &Attributes) -> QueryValue {
+    let mut fields = vec![("name".to_string(), QueryValue::Text(name.to_string()))];
+    fields.extend(extra.into_iter().flatten());
+    fields.push(("terms".to_string(), QueryValue::Number(coefficients.len() as f64)));
+    fields.push((
+        "coefficients".to_string(),
+        QueryValue::Object(coefficients.iter().map(|term| (term.var_name.to_string(), QueryValue::Number(term.coefficient))).collect()),
+    ));
+    fields.push(("attributes".to_string(), attribute_record(attributes)));
+    QueryValue::Object(fields)
+}
+
+fn attribute_record(attributes: &Attributes) -> QueryValue {
+    QueryValue::Object(attributes.iter().map(|(key, value)| (key.clone(), QueryValue::from(value))).collect())
+}
+
+fn constraint_record(constraint: &Constraint<'_>) -> QueryValue {
+    let text = |value: String| QueryValue::Text(value);
+    match constraint {
+        Constraint::Standard { name, coefficients, operator, rhs, attributes } => {
+            let extra = vec![
+                ("kind".to_string(), text("standard".to_string())),
+                ("operator".to_string(), text(operator.to_string())),
+                ("rhs".to_string(), QueryValue::Number(*rhs)),
+            ];
+            entity(name, Some(extra), coefficients, attributes)
+        }
+        Constraint::SOS { name, sos_type, weights, attributes } => {
+            let extra = vec![("kind".to_string(), text("sos".to_string())), ("sos_type".to_string(), text(sos_type.to_string()))];
+            entity(name, Some(extra), weights, attributes)
+        }
+    }
+}
+
+fn variable_record(name: &str, variable: &Variable<'_>) -> QueryValue {
+    let kind = match variable.var_type {
+        VariableType::Free => "free",
+        VariableType::General => "general",
+        VariableType::LowerBound(_) | VariableType::UpperBound(_) | VariableType::DoubleBound(..) => "bounded",
+        VariableType::Binary => "binary",
+        VariableType::Integer => "integer",
+        VariableType::SemiContinuous => "semi-continuous",
+        VariableType::SOS => "sos",
+    };
+    let (lower, upper) = variable.effective_bounds();
+    QueryValue::Object(vec![
+        ("name".to_string(), QueryValue::Text(name.to_string())),
+        ("type".to_string(), QueryValue::Text(kind.to_string())),
+        ("integer".to_string(), QueryValue::Bool(variable.var_type.is_integer())),
+        ("lower".to_string(), QueryValue::Number(lower)),
+        ("upper".to_string(), QueryValue::Number(upper)),
+        ("attributes".to_string(), attribute_record(&variable.attributes)),
+    ])
+}
+
+impl LpProblem<'_> {
+    /// Runs the query `text`, such as `constraints[rhs>100].name`, and returns the results as a list.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `QueryError` if the query cannot be parsed.
+    pub fn query(&self, text: &str) -> Result<QueryValue, QueryError> {
+        Ok(QueryValue::List(Query::parse(text)?.run(self)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        problem::LpProblem,
+        query::{Query, QueryError, QueryValue},
+    };
+
+    #[test]
+    fn test_query() {
+        let input =
+            "minimize\nobj: 2 x + 3 y\nsubject to\nc1: x + y >= 150\nc2: x - y <= 20\nc3: 4 x + y >= 80\nbounds\ny <= 40\nintegers\nx\nend";
+        let problem = LpProblem::parse(input).expect("test case not to fail");
+
+        assert_eq!(problem.query("constraints[operator=\">=\"][rhs>100].name").expect("test case not to fail").to_string(), "[\"c1\"]");
+        assert_eq!(problem.query("constraints[coefficients.y<0].rhs").expect("test case not to fail").to_string(), "[20]");
+        assert_eq!(problem.query("constraints[ operator != '>=' ].name").expect("test case not to fail").to_string(), "[\"c2\"]");
+        assert_eq!(problem.query("variables[integer=true].name").expect("test case not to fail").to_string(), "[\"x\"]");
+        assert_eq!(problem.query("objectives.coefficients").expect("test case not to fail").to_string(), "[{\"x\":2,\"y\":3}]");
+        assert_eq!(
+            problem.query("variables[name=y]").expect("test case not to fail").to_string(),
+            "[{\"name\":\"y\",\"type\":\"bounded\",\"integer\":false,\"lower\":0,\"upper\":40,\"attributes\":{}}]"
+        );
+        assert_eq!(Query::parse("constraints[rhs>1]").expect("test case not to fail").run(&problem).len(), 3);
+        assert_eq!(problem.query("objectives.coefficients.z").expect("test case not to fail"), QueryValue::List(vec![QueryValue::Null]));
+
+        assert_eq!(problem.query("rows").unwrap_err(), QueryError::UnknownCollection { name: "rows".to_string() });
+        assert_eq!(
+            problem.query("variables.rhs").unwrap_err(),
+            QueryError::UnknownField { collection: "variables".to_string(), field: "rhs".to_string() }
+        );
+        assert!(matches!(problem.query("constraints[rhs]"), Err(QueryError::Syntax { position: 15, .. })));
+    }
+}
@@ -0,0 +1,309 @@
+//! A lossless concrete syntax tree.
+//!
+//! The tree groups the tokens of a file into sections and statements without
+//! dropping anything: whitespace, comments and numbers keep their exact text,
+//! so printing the tree reproduces the source byte for byte. Building the tree
+//! never fails; text the semantic parser would reject still ends up in a
+//! statement.
+//!
+//! Trivia between statements belongs to the enclosing section, so a statement
+//! starts at its first significant token and ends at its last one.
+//!
+
+use std::ops::Range;
+
+use crate::syntax::lexer::{tokenize, Token, TokenKind};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// The kind of a section, determined by its header.
+pub enum SectionKind {
+    /// Text before the first section header, usually comments.
+    Preamble,
+    /// The objectives, introduced by `Minimize` or `Maximize`.
+    Objective,
+    /// The constraints, introduced by `Subject To`.
+    Constraints,
+    /// The `Bounds` section.
+    Bounds,
+    /// The `Generals` section.
+    Generals,
+    /// The `Integers` section.
+    Integers,
+    /// The `Binaries` section.
+    Binaries,
+    /// The `Semi-Continuous` section.
+    SemiContinuous,
+    /// The `SOS` section.
+    Sos,
+    /// The `End` marker and anything after it.
+    End,
+}
+
+impl SectionKind {
+    #[must_use]
+    /// Returns the kind of section introduced by `header`, ignoring case.
+    pub fn from_header(header: &str) -> Self {
+        let header = header.to_ascii_lowercase();
+        match header.as_str() {
+            "minimize" | "minimum" | "min" | "maximize" | "maximum" | "max" => Self::Objective,
+            "bounds" | "bound" => Self::Bounds,
+            "generals" | "general" | "gen" => Self::Generals,
+            "integers" | "integer" => Self::Integers,
+            "binaries" | "binary" | "bin" => Self::Binaries,
+            "semi-continuous" | "semis" | "semi" => Self::SemiContinuous,
+            "sos" => Self::Sos,
+            "end" => Self::End,
+            _ => Self::Constraints,
+        }
+    }
+
+    /// Returns `true` if every statement of this kind of section ends at a line break.
+    const fn is_line_based(self) -> bool {
+        !matches!(self, Self::Objective | Self::Constraints)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// The kind of a node.
+pub enum NodeKind {
+    /// The whole file.
+    File,
+    /// A section, starting at its header.
+    Section(SectionKind),
+    /// A single objective, constraint, bound, declaration or SOS.
+    Statement,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A child of a node.
+pub enum SyntaxElement<'a> {
+    /// A section or statement.
+    Node(SyntaxNode<'a>),
+    /// A token, including trivia.
+    Token(Token<'a>),
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A node of the tree and its children, in source order.
+pub struct SyntaxNode<'a> {
+    /// The kind of the node.
+    pub kind: NodeKind,
+    /// The child nodes and tokens.
+    pub children: Vec<SyntaxElement<'a>>,
+}
+
+impl<'a> SyntaxNode<'a> {
+    #[must_use]
+    #[inline]
+    fn new(kind: NodeKind) -> Self {
+        Self { kind, children: Vec::new() }
+    }
+
+    #[must_use]
+    /// Returns every token under this node, in source order.
+    pub fn tokens(&self) -> Vec<&Token<'a>> {
+        let mut tokens = Vec::new();
+        for child in &self.children {
+            match child {
+                SyntaxElement::Node(node) => tokens.extend(node.tokens()),
+                SyntaxElement::Token(token) => tokens.push(token),
+            }
+        }
+        tokens
+    }
+
+    #[inline]
+    /// Returns the direct child nodes.
+    pub fn nodes(&self) -> impl Iterator<Item = &SyntaxNode<'a>> {
+        self.children.iter().filter_map(|child| match child {
+            SyntaxElement::Node(node) => Some(node),
+            SyntaxElement::Token(_) => None,
+        })
+    }
+
+    #[must_use]
+    /// Returns the byte range covered by this node.
+    pub fn span(&self) -> Range<usize> {
+        let tokens = self.tokens();
+        match (tokens.first(), tokens.last()) {
+            (Some(first), Some(last)) => first.span.start..last.span.end,
+            _ => 0..0,
+        }
+    }
+
+    #[must_use]
+    /// Returns the name a statement is labelled with, as in `c1: x + y <= 4`.
+    pub fn label(&self) -> Option<&'a str> {
+        let mut significant = self.tokens().into_iter().filter(|token| !token.kind.is_trivia());
+        match (significant.next(), significant.next()) {
+            (Some(name), Some(colon)) if name.kind == TokenKind::Identifier && colon.kind == TokenKind::Colon => Some(name.text),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for SyntaxNode<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.tokens().into_iter().try_for_each(|token| f.write_str(token.text))
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The concrete syntax tree of an LP file.
+pub struct SyntaxTree<'a> {
+    /// The `File` node, whose children are the sections.
+    pub root: SyntaxNode<'a>,
+}
+
+/// Accumulates the tokens of the current section and statement.
+struct Builder<'a> {
+    root: SyntaxNode<'a>,
+    section: SyntaxNode<'a>,
+    statement: Vec<Token<'a>>,
+}
+
+impl<'a> Builder<'a> {
+    fn section_kind(&self) -> SectionKind {
+        match self.section.kind {
+            NodeKind::Section(kind) => kind,
+            NodeKind::File | NodeKind::Statement => SectionKind::Preamble,
+        }
+    }
+
+    /// Moves the current statement into the section, leaving its trailing trivia outside of it.
+    fn finish_statement(&mut self) {
+        let end = self.statement.iter().rposition(|token| !token.kind.is_trivia()).map_or(0, |index| index + 1);
+        let trailing = self.statement.split_off(end);
+        if !self.statement.is_empty() {
+            let children = self.statement.drain(..).map(SyntaxElement::Token).collect();
+            self.section.children.push(SyntaxElement::Node(SyntaxNode { kind: NodeKind::Statement, children }));
+        }
+        self.section.children.extend(trailing.into_iter().map(SyntaxElement::Token));
+    }
+
+    fn finish_section(&mut self, next: NodeKind) {
+        self.finish_statement();
+        let section = std::mem::replace(&mut self.section, SyntaxNode::new(next));
+        if !section.children.is_empty() {
+            self.root.children.push(SyntaxElement::Node(section));
+        }
+    }
+
+    /// Returns `true` if a line break ends the current statement.
+    fn ends_at_newline(&self) -> bool {
+        let mut significant = self.statement.iter().rev().filter(|token| !token.kind.is_trivia());
+        match self.section_kind() {
+            kind if kind.is_line_based() => true,
+            SectionKind::Constraints => {
+                significant.next().map_or(false, |last| last.kind == TokenKind::Number)
+                    && significant.any(|token| token.kind == TokenKind::Operator)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<'a> SyntaxTree<'a> {
+    #[must_use]
+    /// Builds the syntax tree of `source`.
+    pub fn parse(source: &'a str) -> Self {
+        let tokens = tokenize(source);
+        let significant: Vec<usize> = (0..tokens.len()).filter(|index| !tokens[*index].kind.is_trivia()).collect();
+        let mut labels = vec![false; tokens.len()];
+        for pair in significant.windows(2) {
+            labels[pair[0]] = tokens[pair[0]].kind == TokenKind::Identifier && tokens[pair[1]].kind == TokenKind::Colon;
+        }
+
+        let mut builder = Builder {
+            root: SyntaxNode::new(NodeKind::File),
+            section: SyntaxNode::new(NodeKind::Section(SectionKind::Preamble)),
+            statement: Vec::new(),
+        };
+        for (index, token) in tokens.into_iter().enumerate() {
+            let has_content = builder.statement.iter().any(|token| !token.kind.is_trivia());
+            match token.kind {
+                TokenKind::SectionHeader => {
+                    builder.finish_section(NodeKind::Section(SectionKind::from_header(token.text)));
+                    builder.section.children.push(SyntaxElement::Token(token));
+                }
+                TokenKind::Newline if has_content && builder.ends_at_newline() => {
+                    builder.finish_statement();
+                    builder.section.children.push(SyntaxElement::Token(token));
+                }
+                kind if kind.is_trivia() && !has_content => builder.section.children.push(SyntaxElement::Token(token)),
+                _ => {
+                    let line_based = builder.section_kind().is_line_based();
+                    if labels[index] && has_content && !line_based {
+                        builder.finish_statement();
+                    }
+                    builder.statement.push(token);
+                }
+            }
+        }
+        builder.finish_section(NodeKind::File);
+        Self { root: builder.root }
+    }
+
+    #[inline]
+    /// Returns the sections of the file, in source order.
+    pub fn sections(&self) -> impl Iterator<Item = (SectionKind, &SyntaxNode<'a>)> {
+        self.root.nodes().filter_map(|node| match node.kind {
+            NodeKind::Section(kind) => Some((kind, node)),
+            NodeKind::File | NodeKind::Statement => None,
+        })
+    }
+
+    #[must_use]
+    /// Returns the statement labelled `name` in the objective, constraint or SOS sections.
+    pub fn statement(&self, name: &str) -> Option<&SyntaxNode<'a>> {
+        self.sections()
+            .filter(|(kind, _)| matches!(kind, SectionKind::Objective | SectionKind::Constraints | SectionKind::Sos))
+            .flat_map(|(_, section)| section.nodes())
+            .find(|statement| statement.label() == Some(name))
+    }
+}
+
+impl std::fmt::Display for SyntaxTree<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.root.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::syntax::cst::{SectionKind, SyntaxTree};
+
+    #[test]
+    fn test_syntax_tree() {
+        let source = "\\ Problem name: demo\nMaximize\n obj: 3x + 2.50y \\ profit\nSubject To\n c1: x + y\n   <= 4\n c2: x + 3y <= 6e0\n -x + y >= -1\nBounds\n x <= 3\n y free\nGenerals\n x y\nEnd\n";
+        let tree = SyntaxTree::parse(source);
+        assert_eq!(tree.to_string(), source);
+
+        let kinds: Vec<_> = tree.sections().map(|(kind, section)| (kind, section.nodes().count())).collect();
+        assert_eq!(
+            kinds,
+            [
+                (SectionKind::Preamble, 0),
+                (SectionKind::Objective, 1),
+                (SectionKind::Constraints, 3),
+                (SectionKind::Bounds, 2),
+                (SectionKind::Generals, 1),
+                (SectionKind::End, 0),
+            ]
+        );
+
+        let c1 = tree.statement("c1").expect("test case not to fail");
+        assert_eq!(c1.to_string(), "c1: x + y\n   <= 4");
+        assert_eq!(&source[c1.span()], "c1: x + y\n   <= 4");
+        assert_eq!(tree.statement("obj").expect("test case not to fail").to_string(), "obj: 3x + 2.50y");
+        assert_eq!(tree.statement("c2").expect("test case not to fail").to_string(), "c2: x + 3y <= 6e0");
+        assert!(tree.statement("c3").is_none());
+    }
+}
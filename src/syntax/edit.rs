@@ -0,0 +1,396 @@
+//! Format-preserving edits of LP source text.
+//!
+//! `SourceEditor` turns changes to the model, such as a new right-hand side,
+//! into a list of `TextEdit`s that touch only the tokens involved. Comments,
+//! layout and the spelling of every other number are left as they were, so a
+//! regenerated file diffs cleanly against the original.
+//!
+
+use std::ops::Range;
+
+use crate::syntax::{
+    cst::{SectionKind, SyntaxNode, SyntaxTree},
+    Token, TokenKind,
+};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A replacement of a byte range of the source.
+pub struct TextEdit {
+    /// The byte range to replace; empty for an insertion.
+    pub span: Range<usize>,
+    /// The text to put in its place.
+    pub replacement: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The reasons an edit can be rejected.
+pub enum EditError {
+    /// No objective, constraint or SOS with the name exists.
+    NotFound { name: String },
+    /// The statement has no comparison operator followed by a number.
+    MissingRhs { name: String },
+    /// The variable does not appear in the statement.
+    UnknownTerm { name: String, variable: String },
+    /// The edit overlaps an earlier edit of different text.
+    Conflict { span: Range<usize> },
+}
+
+impl std::fmt::Display for EditError {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound { name } => write!(f, "Cannot edit {name}: no objective, constraint or SOS has that name"),
+            Self::MissingRhs { name } => write!(f, "Cannot edit {name}: it has no right-hand side"),
+            Self::UnknownTerm { name, variable } => write!(f, "Cannot edit {name}: it has no term for {variable}"),
+            Self::Conflict { span } => write!(f, "Cannot edit bytes {}..{}: they overlap an earlier edit", span.start, span.end),
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
+
+#[derive(Debug, Clone)]
+/// Collects format-preserving edits to an LP source.
+pub struct SourceEditor<'a> {
+    source: &'a str,
+    tree: SyntaxTree<'a>,
+    edits: Vec<TextEdit>,
+}
+
+impl<'a> SourceEditor<'a> {
+    #[must_use]
+    #[inline]
+    /// Creates an editor for `source` without any edits.
+    pub fn new(source: &'a str) -> Self {
+        Self { source, tree: SyntaxTree::parse(source), edits: Vec::new() }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Returns the edits made so far, sorted by position.
+    pub fn edits(&self) -> &[TextEdit] {
+        &self.edits
+    }
+
+    #[must_use]
+    /// Returns the source with every edit applied.
+    pub fn apply(&self) -> String {
+        let mut output = String::with_capacity(self.source.len());
+        let mut position = 0;
+        for edit in &self.edits {
+            output.push_str(&self.source[position..edit.span.start]);
+            output.push_str(&edit.replacement);
+            position = edit.span.end;
+        }
+        output.push_str(&self.source[position..]);
+        output
+    }
+
+    /// Records an edit, replacing an earlier edit of exactly the same range.
+    fn push(&mut self, span: Range<usize>, replacement: String) -> Result<(), EditError> {
+        let index = self.edits.partition_point(|edit| (edit.span.start, edit.span.end) < (span.start, span.end));
+        if let Some(existing) = self.edits.get_mut(index).filter(|edit| edit.span == span) {
+            existing.replacement = replacement;
+            return Ok(());
+        }
+        if self.edits.iter().any(|edit| edit.span.start < span.end && span.start < edit.span.end) {
+            return Err(EditError::Conflict { span });
+        }
+        self.edits.insert(index, TextEdit { span, replacement });
+        Ok(())
+    }
+
+    fn statement(&self, name: &str) -> Result<&SyntaxNode<'a>, EditError> {
+        self.tree.statement(name).ok_or_else(|| EditError::NotFound { name: name.to_string() })
+    }
+
+    /// Sets the right-hand side of constraint `name`, keeping the rest of the statement as written.
+    ///
+    /// For a range constraint the bound after the last operator is replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EditError` if there is no such constraint or it has no right-hand side.
+    pub fn set_rhs(&mut self, name: &str, rhs: f64) -> Result<(), EditError> {
+        let tokens = significant_tokens(self.statement(name)?);
+        let rhs_tokens = tokens.iter().rposition(|token| token.kind == TokenKind::Operator).map(|index| &tokens[index + 1..]);
+        let span = match rhs_tokens {
+            Some([sign, number]) if sign.kind == TokenKind::Sign && number.kind == TokenKind::Number => sign.span.start..number.span.end,
+            Some([number]) if number.kind == TokenKind::Number => number.span.clone(),
+            _ => return Err(EditError::MissingRhs { name: name.to_string() }),
+        };
+        self.push(span, format!("{rhs}"))
+    }
+
+    /// Sets the coefficient of `variable` in the objective or constraint `name`.
+    ///
+    /// Only the sign and number in front of the term are rewritten. A term
+    /// written without a coefficient gains one unless the new value is `1` or
+    /// `-1`, and an explicit sign is kept in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EditError` if there is no such objective or constraint, or the variable is not one of its terms.
+    pub fn set_coefficient(&mut self, name: &str, variable: &str, coefficient: f64) -> Result<(), EditError> {
+        let statement = self.statement(name)?;
+        let tokens = significant_tokens(statement);
+        let first = if statement.label().is_some() { 2 } else { 0 };
+        let last = tokens.iter().position(|token| token.kind == TokenKind::Operator).unwrap_or(tokens.len());
+        let index = (first..last)
+            .find(|index| tokens[*index].kind == TokenKind::Identifier && tokens[*index].text == variable)
+            .ok_or_else(|| EditError::UnknownTerm { name: name.to_string(), variable: variable.to_string() })?;
+
+        let term = tokens[index];
+        let (sign, number) = term_prefix(&tokens, first, index);
+
+        let mut prefix = String::new();
+        match (sign, number) {
+            (Some(sign), next) => {
+                prefix.push(if coefficient < 0.0 { '-' } else { '+' });
+                prefix.push_str(&self.source[sign.span.end..next.unwrap_or(term).span.start]);
+            }
+            (None, _) if coefficient < 0.0 => prefix.push('-'),
+            (None, _) => {}
+        }
+        if coefficient.abs() != 1.0 {
+            let gap = number.map_or(" ", |number| &self.source[number.span.end..term.span.start]);
+            prefix.push_str(&format!("{}{gap}", coefficient.abs()));
+        }
+        let start = sign.or(number).unwrap_or(term).span.start;
+        self.push(start..term.span.start, prefix)
+    }
+
+    /// Renames every occurrence of the objective, constraint or variable `old` to `new`.
+    ///
+    /// Returns the number of occurrences renamed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EditError` if an occurrence overlaps an earlier edit.
+    pub fn rename(&mut self, old: &str, new: &str) -> Result<usize, EditError> {
+        let spans: Vec<Range<usize>> = self
+            .tree
+            .root
+            .tokens()
+            .into_iter()
+            .filter(|token| token.kind == TokenKind::Identifier && token.text == old)
+            .map(|token| token.span.clone())
+            .collect();
+        for span in &spans {
+            self.push(span.clone(), new.to_string())?;
+        }
+        Ok(spans.len())
+    }
+
+    /// Removes the objective, constraint or SOS `name`, together with its line if nothing else is on it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EditError` if there is no such statement or it overlaps an earlier edit.
+    pub fn remove(&mut self, name: &str) -> Result<(), EditError> {
+        let span = self.statement(name)?.span();
+        self.remove_statement(span)
+    }
+
+    /// Replaces the repeated terms for `variable` in the objective or constraint `name` with one term holding their sum.
+    ///
+    /// The first term is rewritten as in `set_coefficient` and the others are
+    /// removed; if the sum is zero, every term for `variable` is removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EditError` if there is no such objective or constraint, the variable is not one of
+    /// its terms, or a term overlaps an earlier edit.
+    pub fn merge_terms(&mut self, name: &str, variable: &str) -> Result<(), EditError> {
+        let statement = self.statement(name)?;
+        let tokens = significant_tokens(statement);
+        let first = if statement.label().is_some() { 2 } else { 0 };
+        let last = tokens.iter().position(|token| token.kind == TokenKind::Operator).unwrap_or(tokens.len());
+
+        let mut terms = Vec::new();
+        for index in (first..last).filter(|index| tokens[*index].kind == TokenKind::Identifier && tokens[*index].text == variable) {
+            let (sign, number) = term_prefix(&tokens, first, index);
+            let magnitude = number.map_or(1.0, |number| number.text.parse().unwrap_or(1.0));
+            let coefficient = if sign.map_or(false, |sign| sign.text == "-") { -magnitude } else { magnitude };
+            terms.push((sign.or(number).unwrap_or(tokens[index]).span.start..tokens[index].span.end, coefficient));
+        }
+        if terms.is_empty() {
+            return Err(EditError::UnknownTerm { name: name.to_string(), variable: variable.to_string() });
+        }
+
+        let sum: f64 = terms.iter().map(|(_, coefficient)| coefficient).sum();
+        let kept = usize::from(sum != 0.0);
+        for (span, _) in &terms[kept..] {
+            self.push(self.spaces_before(span.start)..span.end, String::new())?;
+        }
+        if kept == 1 {
+            self.set_coefficient(name, variable, sum)?;
+        }
+        Ok(())
+    }
+
+    /// Removes the bounds and type declarations of `variable`.
+    ///
+    /// Bound lines are removed whole, while a variable listed alongside others
+    /// in a type section is removed from the list. Returns the number of
+    /// declarations removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EditError` if a declaration overlaps an earlier edit.
+    pub fn remove_variable(&mut self, variable: &str) -> Result<usize, EditError> {
+        let mut statements = Vec::new();
+        let mut names = Vec::new();
+        for (kind, section) in self.tree.sections() {
+            if !matches!(
+                kind,
+                SectionKind::Bounds | SectionKind::Generals | SectionKind::Integers | SectionKind::Binaries | SectionKind::SemiContinuous
+            ) {
+                continue;
+            }
+            for statement in section.nodes() {
+                let tokens = significant_tokens(statement);
+                let matching: Vec<Range<usize>> = tokens
+                    .iter()
+                    .filter(|token| token.kind == TokenKind::Identifier && token.text == variable)
+                    .map(|token| token.span.clone())
+                    .collect();
+                if matching.is_empty() {
+                    continue;
+                }
+                if kind == SectionKind::Bounds || matching.len() == tokens.len() {
+                    statements.push(statement.span());
+                } else {
+                    names.extend(matching);
+                }
+            }
+        }
+
+        let removed = statements.len() + names.len();
+        for span in statements {
+            self.remove_statement(span)?;
+        }
+        for span in names {
+            let bytes = self.source.as_bytes();
+            let (mut start, mut end) = (self.spaces_before(span.start), span.end);
+            // A name at the start of a line takes the spaces after it instead
+            if start == 0 || bytes[start - 1] == b'\n' {
+                start = span.start;
+                while matches!(bytes.get(end), Some(b' ' | b'\t')) {
+                    end += 1;
+                }
+            }
+            self.push(start..end, String::new())?;
+        }
+        Ok(removed)
+    }
+
+    /// Returns the position of the first space or tab in the run directly before `position`.
+    fn spaces_before(&self, position: usize) -> usize {
+        let bytes = self.source.as_bytes();
+        let mut start = position;
+        while start > 0 && matches!(bytes[start - 1], b' ' | b'\t') {
+            start -= 1;
+        }
+        start
+    }
+
+    /// Removes the statement at `span`, together with its line if nothing else is on it.
+    fn remove_statement(&mut self, span: Range<usize>) -> Result<(), EditError> {
+        let bytes = self.source.as_bytes();
+        let start = self.spaces_before(span.start);
+        let mut end = span.end;
+        while matches!(bytes.get(end), Some(b' ' | b'\t')) {
+            end += 1;
+        }
+        let whole_line = start == 0 || bytes[start - 1] == b'\n';
+        let line_end = match bytes.get(end) {
+            Some(b'\n') => Some(end + 1),
+            Some(b'\r') if bytes.get(end + 1) == Some(&b'\n') => Some(end + 2),
+            None => Some(end),
+            Some(_) => None,
+        };
+        match line_end.filter(|_| whole_line) {
+            Some(line_end) => self.push(start..line_end, String::new()),
+            None => self.push(span, String::new()),
+        }
+    }
+}
+
+/// Returns the sign and number written in front of the term at `index`, looking no further back than `first`.
+fn term_prefix<'n, 'a>(tokens: &[&'n Token<'a>], first: usize, index: usize) -> (Option<&'n Token<'a>>, Option<&'n Token<'a>>) {
+    let preceding = |index: usize, kind: TokenKind| {
+        Some(index).filter(|index| *index > first).map(|index| tokens[index - 1]).filter(|token| token.kind == kind)
+    };
+    let number = preceding(index, TokenKind::Number);
+    let sign = preceding(index - usize::from(number.is_some()), TokenKind::Sign);
+    (sign, number)
+}
+
+/// Returns the tokens of `node` other than whitespace, line breaks and comments.
+fn significant_tokens<'n, 'a>(node: &'n SyntaxNode<'a>) -> Vec<&'n Token<'a>> {
+    node.tokens().into_iter().filter(|token| !token.kind.is_trivia()).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        problem::LpProblem,
+        syntax::edit::{EditError, SourceEditor},
+    };
+
+    #[test]
+    fn test_source_editor_fixes() {
+        let source = "minimize\nobj: 2 x + y - x \\ cost\nsubject to\nc1: x + y + y >= 2\nc2: z - z + w <= 3\nbounds\n0 <= v <= 4\nx <= 10\ngenerals\nv x\nbinaries\nv\nend\n";
+        let mut editor = SourceEditor::new(source);
+        editor.merge_terms("obj", "x").expect("test case not to fail");
+        editor.merge_terms("c1", "y").expect("test case not to fail");
+        editor.merge_terms("c2", "z").expect("test case not to fail");
+        assert_eq!(editor.remove_variable("v"), Ok(3));
+        assert_eq!(editor.remove_variable("u"), Ok(0));
+        assert_eq!(editor.merge_terms("c1", "u"), Err(EditError::UnknownTerm { name: "c1".to_string(), variable: "u".to_string() }));
+
+        let edited = editor.apply();
+        assert_eq!(
+            edited,
+            "minimize\nobj: x + y \\ cost\nsubject to\nc1: x + 2 y >= 2\nc2: + w <= 3\nbounds\nx <= 10\ngenerals\nx\nbinaries\nend\n"
+        );
+        assert!(LpProblem::parse(&edited).is_ok());
+    }
+
+    #[test]
+    fn test_source_editor() {
+        let source =
+            "\\ model\nminimize\n obj: 2 x + y \\ cost\nsubject to\n c1: x + 3.0 y >= 10\n c2: - x + y\n   <= -4e0\n c3: x <= 8\nend\n";
+        let mut editor = SourceEditor::new(source);
+        editor.set_rhs("c1", 12.5).expect("test case not to fail");
+        editor.set_rhs("c2", 5.0).expect("test case not to fail");
+        editor.set_coefficient("obj", "x", -3.0).expect("test case not to fail");
+        editor.set_coefficient("obj", "y", 4.0).expect("test case not to fail");
+        editor.set_coefficient("c1", "y", 1.0).expect("test case not to fail");
+        editor.set_coefficient("c2", "x", 2.0).expect("test case not to fail");
+        editor.remove("c3").expect("test case not to fail");
+        assert_eq!(editor.rename("y", "z").expect("test case not to fail"), 3);
+
+        let edited = editor.apply();
+        assert_eq!(edited, "\\ model\nminimize\n obj: -3 x + 4 z \\ cost\nsubject to\n c1: x + z >= 12.5\n c2: + 2 x + z\n   <= 5\nend\n");
+        assert!(LpProblem::parse(&edited).is_ok());
+        assert_eq!(editor.edits().len(), 10);
+        assert_eq!(editor.set_coefficient("obj", "x", 3.0), Ok(()));
+        assert!(editor.apply().contains("obj: 3 x + 4 z"));
+        assert!(matches!(editor.remove("c1"), Err(EditError::Conflict { .. })));
+
+        assert_eq!(editor.set_rhs("c9", 1.0), Err(EditError::NotFound { name: "c9".to_string() }));
+        assert_eq!(editor.set_rhs("obj", 1.0), Err(EditError::MissingRhs { name: "obj".to_string() }));
+        assert_eq!(
+            editor.set_coefficient("c1", "w", 1.0),
+            Err(EditError::UnknownTerm { name: "c1".to_string(), variable: "w".to_string() })
+        );
+
+        let source = "minimize\nobj: x + y\nsubject to\nc1: x + y >= 10 \\ demand\nend";
+        let mut editor = SourceEditor::new(source);
+        editor.set_rhs("c1", 12.0).expect("test case not to fail");
+        assert_eq!(editor.apply(), source.replace(">= 10", ">= 12"));
+    }
+}
@@ -0,0 +1,163 @@
+//! Semantic classification of LP source text for editors.
+//!
+//! Tokens alone cannot tell a constraint name from a variable, or an SOS type
+//! from a variable called `S1`. `classify` uses the syntax tree to tell them
+//! apart, producing the spans behind syntax highlighting and an LSP
+//! `semanticTokens` response. Whitespace and unrecognised characters are left
+//! unclassified.
+//!
+
+use std::ops::Range;
+
+use crate::syntax::{
+    cst::{SectionKind, SyntaxElement, SyntaxNode, SyntaxTree},
+    Token, TokenKind,
+};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// What a span of source text means.
+pub enum SemanticKind {
+    /// A section header such as `Subject To`.
+    SectionKeyword,
+    /// The name of an objective, constraint or SOS.
+    ConstraintName,
+    /// A variable.
+    Variable,
+    /// A number, including `inf`.
+    Number,
+    /// A comparison operator, sign or colon.
+    Operator,
+    /// A comment.
+    Comment,
+    /// The `free` keyword or an SOS type such as `S1`.
+    Keyword,
+}
+
+impl SemanticKind {
+    #[must_use]
+    #[inline]
+    /// Returns a lowercase name for the kind, suitable for a highlighting scope or token legend.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::SectionKeyword => "section",
+            Self::ConstraintName => "constraint",
+            Self::Variable => "variable",
+            Self::Number => "number",
+            Self::Operator => "operator",
+            Self::Comment => "comment",
+            Self::Keyword => "keyword",
+        }
+    }
+}
+
+impl std::fmt::Display for SemanticKind {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A classified span of the source.
+pub struct SemanticToken {
+    /// What the text means.
+    pub kind: SemanticKind,
+    /// The byte range of the text in the source.
+    pub span: Range<usize>,
+}
+
+/// Returns the classification of a token that does not depend on its context.
+const fn token_kind(kind: TokenKind) -> Option<SemanticKind> {
+    match kind {
+        TokenKind::SectionHeader => Some(SemanticKind::SectionKeyword),
+        TokenKind::Comment => Some(SemanticKind::Comment),
+        TokenKind::Identifier => Some(SemanticKind::Variable),
+        TokenKind::Number => Some(SemanticKind::Number),
+        TokenKind::Operator | TokenKind::Sign | TokenKind::Colon | TokenKind::DoubleColon => Some(SemanticKind::Operator),
+        TokenKind::Keyword => Some(SemanticKind::Keyword),
+        _ => None,
+    }
+}
+
+/// Classifies the tokens of a statement in a section of kind `section`.
+fn classify_statement(section: SectionKind, statement: &SyntaxNode<'_>, output: &mut Vec<SemanticToken>) {
+    let tokens = statement.tokens();
+    let significant: Vec<&Token<'_>> = tokens.iter().copied().filter(|token| !token.kind.is_trivia()).collect();
+    let named = statement.label().is_some() && matches!(section, SectionKind::Objective | SectionKind::Constraints | SectionKind::Sos);
+    let label = significant.first().filter(|_| named).map(|token| token.span.start);
+    let sos_types: Vec<usize> = significant
+        .windows(2)
+        .filter(|pair| pair[0].kind == TokenKind::Identifier && pair[1].kind == TokenKind::DoubleColon)
+        .map(|pair| pair[0].span.start)
+        .collect();
+
+    for token in tokens {
+        let kind = match token_kind(token.kind) {
+            Some(_) if label == Some(token.span.start) => SemanticKind::ConstraintName,
+            Some(_) if sos_types.contains(&token.span.start) => SemanticKind::Keyword,
+            Some(kind) => kind,
+            None => continue,
+        };
+        output.push(SemanticToken { kind, span: token.span.clone() });
+    }
+}
+
+#[must_use]
+/// Classifies the spans of `source`, in source order.
+///
+/// # Arguments
+///
+/// * `source` - The LP source text, which need not be a valid problem.
+///
+pub fn classify(source: &str) -> Vec<SemanticToken> {
+    let tree = SyntaxTree::parse(source);
+    let mut output = Vec::new();
+    for (section, node) in tree.sections() {
+        for child in &node.children {
+            match child {
+                SyntaxElement::Node(statement) => classify_statement(section, statement, &mut output),
+                SyntaxElement::Token(token) => {
+                    if let Some(kind) = token_kind(token.kind) {
+                        output.push(SemanticToken { kind, span: token.span.clone() });
+                    }
+                }
+            }
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use crate::syntax::highlight::{classify, SemanticKind};
+
+    #[test]
+    fn test_classify() {
+        let source = "\\ demo\nMinimize\n obj: 2 x + y\nSubject To\n c1: x + y >= 1\nBounds\n y free\nsos\n s1: S1:: x:1 y:2\nEnd";
+        let classified: Vec<(SemanticKind, &str)> = classify(source).into_iter().map(|token| (token.kind, &source[token.span])).collect();
+
+        assert_eq!(
+            classified[..8],
+            [
+                (SemanticKind::Comment, "\\ demo"),
+                (SemanticKind::SectionKeyword, "Minimize"),
+                (SemanticKind::ConstraintName, "obj"),
+                (SemanticKind::Operator, ":"),
+                (SemanticKind::Number, "2"),
+                (SemanticKind::Variable, "x"),
+                (SemanticKind::Operator, "+"),
+                (SemanticKind::Variable, "y"),
+            ]
+        );
+        assert!(classified.contains(&(SemanticKind::ConstraintName, "c1")));
+        assert!(classified.contains(&(SemanticKind::Operator, ">=")));
+        assert!(classified.contains(&(SemanticKind::Keyword, "free")));
+        assert!(classified.contains(&(SemanticKind::ConstraintName, "s1")));
+        assert!(classified.contains(&(SemanticKind::Keyword, "S1")));
+        assert_eq!(classified.last(), Some(&(SemanticKind::SectionKeyword, "End")));
+        assert_eq!(classified.iter().filter(|(kind, _)| *kind == SemanticKind::Variable).count(), 7);
+        assert_eq!(SemanticKind::ConstraintName.to_string(), "constraint");
+    }
+}
@@ -0,0 +1,212 @@
+//! Lossless tokenization of LP source text.
+//!
+//! Every byte of the input belongs to exactly one token, including
+//! whitespace, line breaks and comments, so concatenating the token texts
+//! reproduces the source.
+//!
+
+use std::ops::Range;
+
+use crate::VALID_LP_FILE_CHARS;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+/// The kind of a token.
+pub enum TokenKind {
+    /// Spaces and tabs.
+    Whitespace,
+    /// A line break, `\n` or `\r\n`.
+    Newline,
+    /// A comment, from `\` to the end of the line.
+    Comment,
+    /// A section header such as `Minimize`, `Subject To` or `End`, at the start of a line.
+    SectionHeader,
+    /// A variable, constraint or objective name.
+    Identifier,
+    /// A number as written, including `inf` and `infinity`.
+    Number,
+    /// The `free` keyword.
+    Keyword,
+    /// A comparison operator: `<=`, `=<`, `>=`, `=>`, `<`, `>` or `=`.
+    Operator,
+    /// A `+` or `-` sign.
+    Sign,
+    /// A `:` after a name or SOS weight.
+    Colon,
+    /// The `::` after an SOS type.
+    DoubleColon,
+    /// Any other character.
+    Unknown,
+}
+
+impl TokenKind {
+    #[must_use]
+    #[inline]
+    /// Returns `true` for whitespace, line breaks and comments.
+    pub const fn is_trivia(self) -> bool {
+        matches!(self, Self::Whitespace | Self::Newline | Self::Comment)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A token and the text it covers.
+pub struct Token<'a> {
+    /// The kind of the token.
+    pub kind: TokenKind,
+    /// The exact source text of the token.
+    pub text: &'a str,
+    /// The byte range of the token in the source.
+    pub span: Range<usize>,
+}
+
+/// Section headers, longest first so that `semi-continuous` is preferred over `semi`.
+const SECTION_HEADERS: [&str; 24] = [
+    "semi-continuous",
+    "subject to",
+    "such that",
+    "maximize",
+    "minimize",
+    "binaries",
+    "generals",
+    "integers",
+    "maximum",
+    "minimum",
+    "general",
+    "integer",
+    "binary",
+    "bounds",
+    "bound",
+    "semis",
+    "s.t.",
+    "semi",
+    "max",
+    "min",
+    "bin",
+    "gen",
+    "sos",
+    "end",
+];
+
+/// The bare `st` header, which is only a header when nothing else follows on the line.
+const SHORT_CONSTRAINT_HEADER: &str = "st";
+
+#[inline]
+fn is_name_char(c: char) -> bool {
+    c != '\\' && (c.is_alphanumeric() || VALID_LP_FILE_CHARS.contains(&c))
+}
+
+/// Returns the length of the section header at the start of `rest`, if any.
+fn section_header(rest: &str) -> Option<usize> {
+    let lower = rest.chars().take(16).collect::<String>().to_ascii_lowercase();
+    let ends_word = |len: usize| rest[len..].chars().next().map_or(true, |c| c.is_whitespace() || c == ':' || c == '\\');
+    if let Some(header) = SECTION_HEADERS.iter().find(|header| lower.starts_with(*header) && ends_word(header.len())) {
+        return Some(header.len());
+    }
+    let line = rest.lines().next().unwrap_or_default();
+    let line = line.split('\\').next().unwrap_or_default().trim_end().trim_end_matches(':').trim_end();
+    line.eq_ignore_ascii_case(SHORT_CONSTRAINT_HEADER).then_some(SHORT_CONSTRAINT_HEADER.len())
+}
+
+/// Returns the length of the number at the start of `rest`, if any.
+fn number(rest: &str) -> Option<usize> {
+    let bytes = rest.as_bytes();
+    let digits = |from: usize| bytes[from..].iter().take_while(|b| b.is_ascii_digit()).count();
+    let mut len = digits(0);
+    if bytes.get(len) == Some(&b'.') {
+        len += 1 + digits(len + 1);
+    }
+    if len == 0 || (len == 1 && bytes[0] == b'.') {
+        return None;
+    }
+    if matches!(bytes.get(len), Some(b'e' | b'E')) {
+        let sign = usize::from(matches!(bytes.get(len + 1), Some(b'+' | b'-')));
+        let exponent = digits(len + 1 + sign);
+        if exponent > 0 {
+            len += 1 + sign + exponent;
+        }
+    }
+    Some(len)
+}
+
+#[must_use]
+/// Splits `source` into tokens covering every byte of it.
+pub fn tokenize(source: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut line_start = true;
+    while let Some(c) = source[start..].chars().next() {
+        let rest = &source[start..];
+        let (kind, len) = match c {
+            '\n' => (TokenKind::Newline, 1),
+            '\r' if rest.starts_with("\r\n") => (TokenKind::Newline, 2),
+            ' ' | '\t' => (TokenKind::Whitespace, rest.find(|c| !matches!(c, ' ' | '\t')).unwrap_or(rest.len())),
+            '\r' => (TokenKind::Whitespace, 1),
+            '\\' => (TokenKind::Comment, rest.find(['\r', '\n']).unwrap_or(rest.len())),
+            _ if line_start && section_header(rest).is_some() => (TokenKind::SectionHeader, section_header(rest).unwrap_or_default()),
+            '<' | '>' | '=' => {
+                let len = if rest[1..].starts_with(['<', '>', '=']) && rest[..2] != *"<>" && rest[..2] != *"><" { 2 } else { 1 };
+                (TokenKind::Operator, len)
+            }
+            '+' | '-' => (TokenKind::Sign, 1),
+            ':' if rest.starts_with("::") => (TokenKind::DoubleColon, 2),
+            ':' => (TokenKind::Colon, 1),
+            _ if number(rest).is_some() => (TokenKind::Number, number(rest).unwrap_or_default()),
+            c if is_name_char(c) => {
+                let len = rest.find(|c| !is_name_char(c)).unwrap_or(rest.len());
+                let word = &rest[..len];
+                let kind = if word.eq_ignore_ascii_case("inf") || word.eq_ignore_ascii_case("infinity") {
+                    TokenKind::Number
+                } else if word.eq_ignore_ascii_case("free") {
+                    TokenKind::Keyword
+                } else {
+                    TokenKind::Identifier
+                };
+                (kind, len)
+            }
+            c => (TokenKind::Unknown, c.len_utf8()),
+        };
+        tokens.push(Token { kind, text: &rest[..len], span: start..start + len });
+        line_start = kind == TokenKind::Newline || (line_start && kind == TokenKind::Whitespace);
+        start += len;
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod test {
+    use crate::syntax::lexer::{tokenize, TokenKind};
+
+    #[test]
+    fn test_tokenize() {
+        let source =
+            "Minimize \\ cost\r\n obj: 2.5x1 - 1e-3 y\nSubject To\n c1: x1 + y >= -inf\nbounds\n x1 free\nsos\n s1: S1:: x1:1 y:2\nEnd";
+        let tokens = tokenize(source);
+        assert_eq!(tokens.iter().map(|token| token.text).collect::<String>(), source);
+        assert!(tokens.iter().all(|token| source[token.span.clone()] == *token.text));
+
+        let significant: Vec<_> = tokens.iter().filter(|token| !token.kind.is_trivia()).map(|token| (token.kind, token.text)).collect();
+        assert_eq!(
+            significant[..8],
+            [
+                (TokenKind::SectionHeader, "Minimize"),
+                (TokenKind::Identifier, "obj"),
+                (TokenKind::Colon, ":"),
+                (TokenKind::Number, "2.5"),
+                (TokenKind::Identifier, "x1"),
+                (TokenKind::Sign, "-"),
+                (TokenKind::Number, "1e-3"),
+                (TokenKind::Identifier, "y"),
+            ]
+        );
+        assert!(significant.contains(&(TokenKind::SectionHeader, "Subject To")));
+        assert!(significant.contains(&(TokenKind::Operator, ">=")));
+        assert!(significant.contains(&(TokenKind::Number, "inf")));
+        assert!(significant.contains(&(TokenKind::Keyword, "free")));
+        assert!(significant.contains(&(TokenKind::DoubleColon, "::")));
+        assert_eq!(significant.last(), Some(&(TokenKind::SectionHeader, "End")));
+        assert_eq!(tokens[2].kind, TokenKind::Comment);
+        assert_eq!(tokens[3].text, "\r\n");
+    }
+}
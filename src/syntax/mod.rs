@@ -0,0 +1,23 @@
+//! Lossless syntax of LP files.
+//!
+//! The semantic parser in `parsers` discards comments, layout and the exact
+//! spelling of numbers. This module keeps all of them, for tools such as
+//! formatters and refactoring aids that must not alter untouched text.
+//!
+//! - `cst`: A lossless concrete syntax tree of sections and statements.
+//! - `edit`: Format-preserving edits that produce minimal textual patches.
+//! - `highlight`: Semantic classification of spans for editor highlighting.
+//! - `lexer`: Lossless tokenization of LP source text.
+//!
+//! The token stream is public so that external tools can highlight or analyse
+//! LP files without going through the tree. A token is its kind, its exact
+//! text and its byte range in the source; new kinds may be added, so matches on
+//! `TokenKind` need a wildcard arm.
+//!
+
+pub mod cst;
+pub mod edit;
+pub mod highlight;
+pub mod lexer;
+
+pub use lexer::{tokenize, Token, TokenKind};
@@ -0,0 +1,384 @@
+//! Indexed templates that expand into families of similar rows.
+//!
+//! A template is LP text in which a line may be repeated over integer ranges.
+//! A name followed by a domain, such as `c{i in 1..10}`, repeats its line once
+//! for every value, and a name followed by indices, such as `x{i}` or
+//! `x{i,j+1}`, becomes the concrete name `x_3` or `x_3_5`. Names registered as
+//! tables are replaced by their values instead, and `sum{j in 1..4}` repeats
+//! the term that follows it:
+//!
+//! ```text
+//! Maximize
+//!  obj: sum{i in 1..3} profit{i} x{i}
+//! Subject To
+//!  cap{i in 1..3}: x{i} + y{i} <= limit{i}
+//!  link{i in 1..2}: x{i} - x{i+1} >= 0
+//! Bounds
+//!  y{i in 1..3} <= 5
+//! End
+//! ```
+//!
+//! `Template::expand` returns plain LP text, ready for `LpProblem::parse`.
+//! Each template row must fit on one line, ranges include both ends and may
+//! refer to indices declared before them.
+//!
+
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The reasons a template cannot be expanded.
+pub enum TemplateError {
+    /// The line does not follow the template syntax.
+    Syntax { line: usize, message: String },
+    /// An index is used without being declared by a domain.
+    Unbound { name: String, line: usize },
+    /// A table has no value at the index.
+    MissingValue { table: String, index: Vec<i64>, line: usize },
+}
+
+impl std::fmt::Display for TemplateError {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Syntax { line, message } => write!(f, "Cannot expand line {line}: {message}"),
+            Self::Unbound { name, line } => write!(f, "Cannot expand line {line}: index {name} is not declared"),
+            Self::MissingValue { table, index, line } => {
+                let index: Vec<String> = index.iter().map(ToString::to_string).collect();
+                write!(f, "Cannot expand line {line}: table {table} has no value at {{{}}}", index.join(","))
+            }
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// An index expression: a literal, or an index plus an offset.
+enum Expr<'a> {
+    Literal(i64),
+    Index(&'a str, i64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A declaration such as `i in 1..10`.
+struct Domain<'a> {
+    name: &'a str,
+    from: Expr<'a>,
+    to: Expr<'a>,
+}
+
+/// The values of the indices in scope, innermost last.
+type Env<'a> = [(&'a str, i64)];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A part of a template line.
+enum Piece<'a> {
+    /// Text copied as written.
+    Text(&'a str),
+    /// An indexed name or table entry.
+    Indexed { name: &'a str, index: Vec<Expr<'a>> },
+    /// A term repeated over its domains.
+    Sum { domains: Vec<Domain<'a>>, term: Vec<Piece<'a>> },
+}
+
+/// Returns `true` for characters that cannot be part of a name.
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '+' | '-' | '<' | '>' | '=' | ':' | '{' | '}' | '\\')
+}
+
+/// Parses `text` as a template expression.
+fn parse_expr(text: &str) -> Option<Expr<'_>> {
+    let text = text.trim();
+    if let Ok(value) = text.parse() {
+        return Some(Expr::Literal(value));
+    }
+    match text.find(['+', '-']) {
+        Some(position) => {
+            let offset: i64 = text[position + 1..].trim().parse().ok()?;
+            let name = text[..position].trim();
+            (!name.is_empty()).then_some(Expr::Index(name, if text[position..].starts_with('-') { -offset } else { offset }))
+        }
+        None => (!text.is_empty() && !text.contains(is_delimiter)).then_some(Expr::Index(text, 0)),
+    }
+}
+
+/// Parses the contents of a `{...}` group as domains, or returns `None` if it holds indices.
+fn parse_domains(text: &str) -> Option<Result<Vec<Domain<'_>>, String>> {
+    if !text.split_whitespace().any(|word| word == "in") {
+        return None;
+    }
+    Some(
+        text.split(',')
+            .map(|domain| {
+                let invalid = || format!("expected `name in from..to`, found `{}`", domain.trim());
+                let (name, range) = domain.split_once(" in ").ok_or_else(invalid)?;
+                let (from, to) = range.split_once("..").ok_or_else(invalid)?;
+                match (name.trim(), parse_expr(from), parse_expr(to)) {
+                    (name, Some(from), Some(to)) if !name.is_empty() && !name.contains(is_delimiter) => Ok(Domain { name, from, to }),
+                    _ => Err(invalid()),
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Returns the length of the term starting at the beginning of `text`, up to the next sign or operator.
+fn term_len(text: &str) -> usize {
+    let mut depth = 0_usize;
+    let mut previous = ['\0', '\0'];
+    for (position, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            '+' | '-' if depth == 0 && matches!(previous[1], 'e' | 'E') && (previous[0].is_ascii_digit() || previous[0] == '.') => {}
+            '+' | '-' | '<' | '>' | '=' if depth == 0 && position > 0 => return text[..position].trim_end().len(),
+            _ => {}
+        }
+        previous = [previous[1], c];
+    }
+    text.trim_end().len()
+}
+
+/// Splits a template line into pieces, collecting the domains that repeat the whole line.
+fn parse_pieces<'a>(text: &'a str, domains: &mut Vec<Domain<'a>>) -> Result<Vec<Piece<'a>>, String> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    while let Some(open) = text[start..].find('{').map(|position| start + position) {
+        let close = text[open..].find('}').map(|position| open + position).ok_or("`{` is never closed")?;
+        let name_start = text[..open].rfind(is_delimiter).map_or(0, |position| position + 1);
+        let name = text[name_start..open].trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+        if name.is_empty() {
+            return Err(format!("`{}` does not follow a name", &text[open..=close]));
+        }
+        pieces.push(Piece::Text(&text[start..open - name.len()]));
+
+        let group = &text[open + 1..close];
+        start = close + 1;
+        match parse_domains(group).transpose()? {
+            Some(group_domains) if name.eq_ignore_ascii_case("sum") => {
+                start += text[start..].len() - text[start..].trim_start().len();
+                let term = &text[start..start + term_len(&text[start..])];
+                let mut nested = Vec::new();
+                let term_pieces = parse_pieces(term, &mut nested)?;
+                if !nested.is_empty() {
+                    return Err("a summed term cannot declare domains".to_string());
+                }
+                pieces.push(Piece::Sum { domains: group_domains, term: term_pieces });
+                start += term.len();
+            }
+            Some(group_domains) => {
+                let index = group_domains.iter().map(|domain| Expr::Index(domain.name, 0)).collect();
+                domains.extend(group_domains);
+                pieces.push(Piece::Indexed { name, index });
+            }
+            None => {
+                let index = group.split(',').map(|expr| parse_expr(expr).ok_or_else(|| format!("invalid index `{}`", expr.trim())));
+                pieces.push(Piece::Indexed { name, index: index.collect::<Result<_, _>>()? });
+            }
+        }
+    }
+    pieces.push(Piece::Text(&text[start..]));
+    Ok(pieces)
+}
+
+/// Folds a sign followed by a negative number, as in `+ -2 x`, into a single sign.
+fn fold_signs(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(position) = rest.find(['+', '-']) {
+        let after = &rest[position + 1..];
+        let gap = after.len() - after.trim_start().len();
+        let negative =
+            after[gap..].strip_prefix('-').filter(|number| number.starts_with(|c: char| c.is_ascii_digit() || c == '.' || c == 'i'));
+        output.push_str(&rest[..position]);
+        match negative {
+            Some(number) => {
+                output.push(if rest[position..].starts_with('-') { '+' } else { '-' });
+                output.push_str(&after[..gap]);
+                rest = number;
+            }
+            None => {
+                output.push_str(&rest[position..=position]);
+                rest = after;
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// Expands indexed templates into LP text, with tables of values for indexed data.
+pub struct Template {
+    tables: BTreeMap<String, HashMap<Vec<i64>, f64>>,
+}
+
+impl Template {
+    #[must_use]
+    #[inline]
+    /// Creates a template expander without any tables.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    #[inline]
+    /// Registers the table `name`, whose entries map an index such as `[2]` or `[1, 3]` to a value.
+    pub fn with_table(mut self, name: &str, entries: impl IntoIterator<Item = (Vec<i64>, f64)>) -> Self {
+        self.tables.insert(name.to_string(), entries.into_iter().collect());
+        self
+    }
+
+    /// Expands every template line of `source`, leaving other lines unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TemplateError` if a line is malformed, uses an undeclared
+    /// index or refers to a missing table entry.
+    pub fn expand(&self, source: &str) -> Result<String, TemplateError> {
+        let mut output = String::with_capacity(source.len());
+        for (index, line) in source.split_inclusive('\n').enumerate() {
+            let number = index + 1;
+            let content = line.trim_end_matches(['\r', '\n']);
+            let (code, comment) = content.find('\\').map_or((content, ""), |position| content.split_at(position));
+            if !code.contains('{') {
+                output.push_str(line);
+                continue;
+            }
+            let trimmed = code.trim_end();
+
+            let mut domains = Vec::new();
+            let pieces = parse_pieces(trimmed, &mut domains).map_err(|message| TemplateError::Syntax { line: number, message })?;
+            let mut rows = Vec::new();
+            self.each_binding(&domains, &mut Vec::new(), number, &mut |env| {
+                let mut row = String::new();
+                self.render(&pieces, env, number, &mut row)?;
+                rows.push(fold_signs(&row));
+                Ok(())
+            })?;
+            if rows.is_empty() {
+                continue;
+            }
+            let line_break = &line[content.len()..];
+            output.push_str(&rows.join(if line_break.is_empty() { "\n" } else { line_break }));
+            output.push_str(&code[trimmed.len()..]);
+            output.push_str(comment);
+            output.push_str(line_break);
+        }
+        Ok(output)
+    }
+
+    /// Calls `f` with every combination of values of `domains`, in order.
+    fn each_binding<'a>(
+        &self,
+        domains: &[Domain<'a>],
+        env: &mut Vec<(&'a str, i64)>,
+        line: usize,
+        f: &mut dyn FnMut(&Env<'a>) -> Result<(), TemplateError>,
+    ) -> Result<(), TemplateError> {
+        let (domain, rest) = match domains.split_first() {
+            Some(split) => split,
+            None => return f(env),
+        };
+        let (from, to) = (evaluate(domain.from, env, line)?, evaluate(domain.to, env, line)?);
+        for value in from..=to {
+            env.push((domain.name, value));
+            let result = self.each_binding(rest, env, line, f);
+            env.pop();
+            result?;
+        }
+        Ok(())
+    }
+
+    /// Appends `pieces` with the indices in `env` substituted.
+    fn render<'a>(&self, pieces: &[Piece<'a>], env: &Env<'a>, line: usize, output: &mut String) -> Result<(), TemplateError> {
+        for piece in pieces {
+            match piece {
+                Piece::Text(text) => output.push_str(text),
+                Piece::Indexed { name, index } => {
+                    let index = index.iter().map(|expr| evaluate(*expr, env, line)).collect::<Result<Vec<i64>, _>>()?;
+                    match self.tables.get(*name) {
+                        Some(table) => {
+                            let value =
+                                table.get(&index).ok_or_else(|| TemplateError::MissingValue { table: name.to_string(), index, line })?;
+                            output.push_str(&value.to_string());
+                        }
+                        None => {
+                            output.push_str(name);
+                            index.iter().for_each(|value| output.push_str(&format!("_{value}")));
+                        }
+                    }
+                }
+                Piece::Sum { domains, term } => {
+                    let sign = if output.trim_end().ends_with('-') { " - " } else { " + " };
+                    let mut terms = 0;
+                    let mut env = env.to_vec();
+                    self.each_binding(domains, &mut env, line, &mut |env| {
+                        if terms > 0 {
+                            output.push_str(sign);
+                        }
+                        terms += 1;
+                        self.render(term, env, line, output)
+                    })?;
+                    if terms == 0 {
+                        return Err(TemplateError::Syntax { line, message: "sum over an empty range".to_string() });
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Evaluates `expr` with the indices in `env`.
+fn evaluate(expr: Expr<'_>, env: &Env<'_>, line: usize) -> Result<i64, TemplateError> {
+    match expr {
+        Expr::Literal(value) => Ok(value),
+        Expr::Index(name, offset) => env
+            .iter()
+            .rev()
+            .find(|(bound, _)| *bound == name)
+            .map(|(_, value)| value + offset)
+            .ok_or_else(|| TemplateError::Unbound { name: name.to_string(), line }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        problem::LpProblem,
+        template::{Template, TemplateError},
+    };
+
+    #[test]
+    fn test_template() {
+        let source = "maximize\n obj: sum{i in 1..3} profit{i} x{i}\nsubject to\n cap{i in 1..3}: x{i} + y{i} <= limit{i}\n link{i in 1..2}: x{i} - 2 x{i+1} >= 0\n pair{i in 1..2, j in i+1..3}: - sum{k in i..j} w{k} y{k} + z <= 1\nbounds\n y{i in 1..3} <= 5\nend\n";
+        let template = Template::new()
+            .with_table("profit", [(vec![1], 3.0), (vec![2], -2.0), (vec![3], 1.5)])
+            .with_table("limit", (1..=3).map(|i| (vec![i], 10.0 * i as f64)))
+            .with_table("w", (1..=3).map(|i| (vec![i], -(i as f64))));
+
+        let expanded = template.expand(source).expect("test case not to fail");
+        assert_eq!(
+            expanded,
+            "maximize\n obj: 3 x_1 - 2 x_2 + 1.5 x_3\nsubject to\n cap_1: x_1 + y_1 <= 10\n cap_2: x_2 + y_2 <= 20\n cap_3: x_3 + y_3 <= 30\n link_1: x_1 - 2 x_2 >= 0\n link_2: x_2 - 2 x_3 >= 0\n pair_1_2: + 1 y_1 + 2 y_2 + z <= 1\n pair_1_3: + 1 y_1 + 2 y_2 + 3 y_3 + z <= 1\n pair_2_3: + 2 y_2 + 3 y_3 + z <= 1\nbounds\n y_1 <= 5\n y_2 <= 5\n y_3 <= 5\nend\n"
+        );
+        let problem = LpProblem::parse(&expanded).expect("test case not to fail");
+        assert_eq!((problem.constraint_count(), problem.variable_count()), (8, 7));
+
+        assert_eq!(
+            Template::new()
+                .with_table("profit", [(vec![2], 1.0)])
+                .expand(source.split("subject").next().expect("test case not to fail"))
+                .unwrap_err(),
+            TemplateError::MissingValue { table: "profit".to_string(), index: vec![1], line: 2 }
+        );
+        assert_eq!(
+            template.expand("c{i in 1..2}: x{i} >= 1 \\ demand\n").expect("test case not to fail"),
+            "c_1: x_1 >= 1\nc_2: x_2 >= 1 \\ demand\n"
+        );
+        assert_eq!(template.expand("c{i in 1..2}: x{j} >= 1\n").unwrap_err(), TemplateError::Unbound { name: "j".to_string(), line: 1 });
+        assert!(matches!(template.expand("c{i in 1..}: x{i} >= 1\n"), Err(TemplateError::Syntax { line: 1, .. })));
+        assert_eq!(template.expand("minimize\nobj: x\n").expect("test case not to fail"), "minimize\nobj: x\n");
+    }
+}
@@ -0,0 +1,723 @@
+//! Lint engine for checking LP problems.
+//!
+//! Every check is a `LintRule` with a stable code (for example `LP001`), a
+//! default `Severity` and optional parameters. A `ValidationContext` holds the
+//! registered rules and their configuration, runs them over a problem and
+//! collects the resulting `Diagnostic`s. Rules can be disabled, have their
+//! severity overridden or their parameters changed, and custom rules can be
+//! registered alongside the built-in ones.
+//!
+//! The parsed model does not retain source positions, so diagnostics point at
+//! the entity (objective, constraint, variable or term) that triggered them.
+//! Findings with an unambiguous remedy carry a `Fix`, which
+//! `ValidationContext::apply_fixes` applies to the problem and `Fix::edit`
+//! applies to the source text through a `syntax::edit::SourceEditor`.
+//!
+//! - `rules`: The built-in lint rules.
+//!
+
+pub mod rules;
+
+use std::{collections::HashMap, str::FromStr};
+
+use crate::{
+    model::{Coefficient, Constraint},
+    problem::LpProblem,
+    syntax::edit::{EditError, SourceEditor},
+};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// How serious a diagnostic is.
+pub enum Severity {
+    /// Informational; the problem is likely fine.
+    Info,
+    /// The problem is probably not what was intended.
+    Warning,
+    /// The problem is invalid or cannot be solved as written.
+    Error,
+}
+
+impl std::fmt::Display for Severity {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Info => write!(f, "info"),
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// The entity a diagnostic refers to.
+pub enum Location {
+    /// The problem as a whole.
+    Problem,
+    /// An objective, optionally narrowed to the term for one variable.
+    Objective { name: String, variable: Option<String> },
+    /// A constraint, optionally narrowed to the term for one variable.
+    Constraint { name: String, variable: Option<String> },
+    /// A variable declaration.
+    Variable { name: String },
+    /// A line of the LP source, counted from one.
+    Line { line: usize },
+}
+
+impl Location {
+    #[must_use]
+    #[inline]
+    /// Returns the location of an objective.
+    pub fn objective(name: &str) -> Self {
+        Self::Objective { name: name.to_string(), variable: None }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Returns the location of a constraint.
+    pub fn constraint(name: &str) -> Self {
+        Self::Constraint { name: name.to_string(), variable: None }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Returns the location of a variable.
+    pub fn variable(name: &str) -> Self {
+        Self::Variable { name: name.to_string() }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Narrows an objective or constraint location to the term for `variable`.
+    pub fn with_term(self, variable: &str) -> Self {
+        match self {
+            Self::Objective { name, .. } => Self::Objective { name, variable: Some(variable.to_string()) },
+            Self::Constraint { name, .. } => Self::Constraint { name, variable: Some(variable.to_string()) },
+            other => other,
+        }
+    }
+}
+
+impl std::fmt::Display for Location {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Problem => write!(f, "problem"),
+            Self::Objective { name, variable: None } => write!(f, "objective {name}"),
+            Self::Objective { name, variable: Some(variable) } => write!(f, "objective {name}, term {variable}"),
+            Self::Constraint { name, variable: None } => write!(f, "constraint {name}"),
+            Self::Constraint { name, variable: Some(variable) } => write!(f, "constraint {name}, term {variable}"),
+            Self::Variable { name } => write!(f, "variable {name}"),
+            Self::Line { line } => write!(f, "line {line}"),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// A finding reported by a lint rule.
+pub struct Diagnostic {
+    /// The code of the rule that reported the finding, for example `LP001`.
+    pub code: String,
+    /// The name of the rule that reported the finding, for example `empty-constraint`.
+    pub rule: String,
+    /// The severity of the finding.
+    pub severity: Severity,
+    /// The entity the finding refers to.
+    pub location: Location,
+    /// A human readable description of the finding.
+    pub message: String,
+    /// A machine-applicable remedy, if the finding has one.
+    pub fix: Option<Fix>,
+}
+
+impl std::fmt::Display for Diagnostic {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}[{}] {}: {}", self.severity, self.code, self.location, self.message)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A machine-applicable remedy for a finding.
+pub enum Fix {
+    /// Remove a variable declaration.
+    RemoveVariable { name: String },
+    /// Remove a constraint.
+    RemoveConstraint { name: String },
+    /// Replace the repeated terms for `variable` in an objective with a single term holding their sum.
+    MergeObjectiveTerms { objective: String, variable: String },
+    /// Replace the repeated terms for `variable` in a constraint with a single term holding their sum.
+    MergeConstraintTerms { constraint: String, variable: String },
+}
+
+impl std::fmt::Display for Fix {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RemoveVariable { name } => write!(f, "remove variable {name}"),
+            Self::RemoveConstraint { name } => write!(f, "remove constraint {name}"),
+            Self::MergeObjectiveTerms { objective, variable } => write!(f, "merge the terms for {variable} in objective {objective}"),
+            Self::MergeConstraintTerms { constraint, variable } => write!(f, "merge the terms for {variable} in constraint {constraint}"),
+        }
+    }
+}
+
+/// Replaces the terms for `variable` with one term holding their sum, dropping it if the sum is zero.
+///
+/// Returns `false` if `variable` does not appear more than once.
+fn merge_terms(coefficients: &mut Vec<Coefficient<'_>>, variable: &str) -> bool {
+    let first = match coefficients.iter().position(|term| term.var_name == variable) {
+        Some(first) => first,
+        None => return false,
+    };
+    if coefficients.iter().filter(|term| term.var_name == variable).count() < 2 {
+        return false;
+    }
+
+    let total = coefficients.iter().filter(|term| term.var_name == variable).map(|term| term.coefficient).sum();
+    coefficients[first].coefficient = total;
+    let mut index = 0;
+    coefficients.retain(|term| {
+        let keep = term.var_name != variable || index == first;
+        index += 1;
+        keep
+    });
+    if total == 0.0 {
+        coefficients.remove(first);
+    }
+    true
+}
+
+impl Fix {
+    /// Applies the fix to `problem`.
+    ///
+    /// Returns `false` if the fix no longer applies, for example because the
+    /// entity it refers to has already been removed.
+    pub fn apply(&self, problem: &mut LpProblem) -> bool {
+        match self {
+            Self::RemoveVariable { name } => problem.variables.remove(name.as_str()).is_some(),
+            Self::RemoveConstraint { name } => problem.constraints.remove(name.as_str()).is_some(),
+            Self::MergeObjectiveTerms { objective, variable } => {
+                problem.objectives.get_mut(objective.as_str()).map_or(false, |objective| merge_terms(&mut objective.coefficients, variable))
+            }
+            Self::MergeConstraintTerms { constraint, variable } => match problem.constraints.get_mut(constraint.as_str()) {
+                Some(Constraint::Standard { coefficients, .. }) => merge_terms(coefficients, variable),
+                _ => false,
+            },
+        }
+    }
+
+    /// Records the fix as edits of the source text, keeping its comments and layout.
+    ///
+    /// Returns `false` if the source has nothing to change, for example a
+    /// variable with no declaration to remove.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EditError` if the entity cannot be found in the source, as
+    /// for a constraint written without a label, or the edit overlaps an earlier one.
+    pub fn edit(&self, editor: &mut SourceEditor<'_>) -> Result<bool, EditError> {
+        match self {
+            Self::RemoveVariable { name } => Ok(editor.remove_variable(name)? > 0),
+            Self::RemoveConstraint { name } => editor.remove(name).map(|()| true),
+            Self::MergeObjectiveTerms { objective: name, variable } | Self::MergeConstraintTerms { constraint: name, variable } => {
+                editor.merge_terms(name, variable).map(|()| true)
+            }
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+/// The LP file dialect a problem is checked against.
+pub enum Dialect {
+    /// IBM CPLEX.
+    #[default]
+    Cplex,
+    /// Gurobi.
+    Gurobi,
+    /// FICO Xpress.
+    Xpress,
+    /// Mosek.
+    Mosek,
+}
+
+impl std::fmt::Display for Dialect {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cplex => write!(f, "cplex"),
+            Self::Gurobi => write!(f, "gurobi"),
+            Self::Xpress => write!(f, "xpress"),
+            Self::Mosek => write!(f, "mosek"),
+        }
+    }
+}
+
+impl FromStr for Dialect {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "cplex" => Ok(Self::Cplex),
+            "gurobi" => Ok(Self::Gurobi),
+            "xpress" => Ok(Self::Xpress),
+            "mosek" => Ok(Self::Mosek),
+            _ => Err(format!("unknown dialect {s}")),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+/// How strictly findings are treated.
+pub enum Strictness {
+    /// Findings are reported with their configured severity.
+    #[default]
+    Lenient,
+    /// Warnings are reported as errors.
+    Strict,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The configuration of a single lint rule.
+pub struct RuleConfig {
+    /// Whether the rule runs.
+    pub enabled: bool,
+    /// Overrides the default severity of the rule.
+    pub severity: Option<Severity>,
+    /// Rule specific parameters, parsed by the rule when it runs.
+    pub params: HashMap<String, String>,
+}
+
+impl Default for RuleConfig {
+    #[inline]
+    fn default() -> Self {
+        Self { enabled: true, severity: None, params: HashMap::new() }
+    }
+}
+
+impl RuleConfig {
+    #[must_use]
+    #[inline]
+    /// Returns a configuration that disables the rule.
+    pub fn disabled() -> Self {
+        Self { enabled: false, ..Self::default() }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Builder method for overriding the severity of the rule.
+    pub fn with_severity(self, severity: Severity) -> Self {
+        Self { severity: Some(severity), ..self }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Builder method for setting a rule parameter.
+    pub fn with_param(mut self, name: &str, value: impl ToString) -> Self {
+        self.params.insert(name.to_string(), value.to_string());
+        self
+    }
+}
+
+/// A single check run over a problem.
+///
+/// Implement this trait and register it with `ValidationContext::with_rule` to
+/// add custom checks.
+pub trait LintRule: Send + Sync {
+    /// The stable code of the rule, for example `LP001`.
+    fn code(&self) -> &'static str;
+
+    /// The short kebab-case name of the rule, for example `empty-constraint`.
+    fn name(&self) -> &'static str;
+
+    /// A one sentence description of what the rule checks.
+    fn description(&self) -> &'static str;
+
+    /// The severity used unless the configuration overrides it.
+    fn default_severity(&self) -> Severity;
+
+    /// Checks `problem`, reporting findings through `context`.
+    fn check(&self, _problem: &LpProblem, _context: &mut RuleContext<'_>) {}
+
+    /// Checks the LP text `problem` was parsed from, reporting findings through `context`.
+    ///
+    /// Only called by `ValidationContext::validate_source`, for checks that
+    /// need information the parsed model does not keep.
+    fn check_source(&self, _source: &str, _context: &mut RuleContext<'_>) {}
+}
+
+/// The interface through which a running rule reads its parameters and reports findings.
+pub struct RuleContext<'c> {
+    code: &'static str,
+    rule: &'static str,
+    severity: Severity,
+    dialect: Dialect,
+    params: &'c HashMap<String, String>,
+    diagnostics: &'c mut Vec<Diagnostic>,
+}
+
+impl RuleContext<'_> {
+    #[must_use]
+    #[inline]
+    /// Returns the severity findings will be reported with.
+    pub const fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    #[must_use]
+    #[inline]
+    /// Returns the dialect the problem is checked against.
+    pub const fn dialect(&self) -> Dialect {
+        self.dialect
+    }
+
+    #[must_use]
+    /// Returns the parameter `name` parsed as `T`, or `default` if it is unset or cannot be parsed.
+    pub fn param<T: FromStr>(&self, name: &str, default: T) -> T {
+        match self.params.get(name).map(|value| value.parse()) {
+            Some(Ok(value)) => value,
+            Some(Err(_)) => {
+                log::warn!("Ignoring invalid value for parameter {name} of rule {}", self.code);
+                default
+            }
+            None => default,
+        }
+    }
+
+    #[inline]
+    /// Reports a finding at `location`.
+    pub fn report(&mut self, location: Location, message: impl Into<String>) {
+        self.push(location, message.into(), None);
+    }
+
+    #[inline]
+    /// Reports a finding at `location` that `fix` resolves.
+    pub fn report_with_fix(&mut self, location: Location, message: impl Into<String>, fix: Fix) {
+        self.push(location, message.into(), Some(fix));
+    }
+
+    fn push(&mut self, location: Location, message: String, fix: Option<Fix>) {
+        self.diagnostics.push(Diagnostic {
+            code: self.code.to_string(),
+            rule: self.rule.to_string(),
+            severity: self.severity,
+            location,
+            message,
+            fix,
+        });
+    }
+}
+
+/// Runs lint rules over problems and collects their findings.
+pub struct ValidationContext {
+    rules: Vec<Box<dyn LintRule>>,
+    config: HashMap<String, RuleConfig>,
+    dialect: Dialect,
+    strictness: Strictness,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Default for ValidationContext {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for ValidationContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ValidationContext")
+            .field("rules", &self.rules.iter().map(|rule| rule.code()).collect::<Vec<_>>())
+            .field("config", &self.config)
+            .field("dialect", &self.dialect)
+            .field("strictness", &self.strictness)
+            .field("diagnostics", &self.diagnostics)
+            .finish()
+    }
+}
+
+impl ValidationContext {
+    #[must_use]
+    #[inline]
+    /// Initialise a new `ValidationContext` with the built-in rules registered.
+    pub fn new() -> Self {
+        Self { rules: rules::builtin(), ..Self::empty() }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Initialise a new `ValidationContext` without any rules.
+    pub fn empty() -> Self {
+        Self {
+            rules: Vec::new(),
+            config: HashMap::new(),
+            dialect: Dialect::default(),
+            strictness: Strictness::default(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Builder method for setting the dialect rules check against.
+    pub fn with_dialect(self, dialect: Dialect) -> Self {
+        Self { dialect, ..self }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Builder method for setting how strictly findings are treated.
+    pub fn with_strictness(self, strictness: Strictness) -> Self {
+        Self { strictness, ..self }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Builder method for registering an additional rule.
+    pub fn with_rule(mut self, rule: impl LintRule + 'static) -> Self {
+        self.register_rule(Box::new(rule));
+        self
+    }
+
+    #[inline]
+    /// Registers an additional rule, replacing any rule with the same code.
+    pub fn register_rule(&mut self, rule: Box<dyn LintRule>) {
+        self.rules.retain(|existing| existing.code() != rule.code());
+        self.rules.push(rule);
+    }
+
+    #[must_use]
+    #[inline]
+    /// Builder method for configuring the rule with the given code or name.
+    pub fn with_rule_config(mut self, rule: &str, config: RuleConfig) -> Self {
+        self.configure(rule, config);
+        self
+    }
+
+    #[inline]
+    /// Configures the rule with the given code or name.
+    pub fn configure(&mut self, rule: &str, config: RuleConfig) {
+        self.config.insert(rule.to_string(), config);
+    }
+
+    #[inline]
+    /// Returns the configuration of the rule with the given code or name, creating a default one if needed.
+    pub fn rule_config_mut(&mut self, rule: &str) -> &mut RuleConfig {
+        self.config.entry(rule.to_string()).or_default()
+    }
+
+    #[inline]
+    /// Returns the registered rules.
+    pub fn rules(&self) -> impl Iterator<Item = &dyn LintRule> {
+        self.rules.iter().map(AsRef::as_ref)
+    }
+
+    /// Returns the configuration for `rule`, looked up by code and then by name.
+    fn config_for(&self, rule: &dyn LintRule) -> Option<&RuleConfig> {
+        self.config.get(rule.code()).or_else(|| self.config.get(rule.name()))
+    }
+
+    /// Runs every enabled rule over `problem`, replacing any previous findings.
+    ///
+    /// Diagnostics are ordered by rule code and then by location.
+    pub fn validate(&mut self, problem: &LpProblem) -> &[Diagnostic] {
+        self.run(problem, None)
+    }
+
+    /// Runs every enabled rule over `problem` and the LP text it was parsed from, replacing any previous findings.
+    ///
+    /// In addition to the checks run by `validate`, this runs the checks that
+    /// need the source, such as those for the section structure of the file.
+    pub fn validate_source(&mut self, problem: &LpProblem, source: &str) -> &[Diagnostic] {
+        self.run(problem, Some(source))
+    }
+
+    fn run(&mut self, problem: &LpProblem, source: Option<&str>) -> &[Diagnostic] {
+        let empty = HashMap::new();
+        let mut diagnostics = Vec::new();
+        for rule in &self.rules {
+            let config = self.config_for(rule.as_ref());
+            if !config.map_or(true, |config| config.enabled) {
+                continue;
+            }
+            let mut severity = config.and_then(|config| config.severity).unwrap_or_else(|| rule.default_severity());
+            if self.strictness == Strictness::Strict && severity == Severity::Warning {
+                severity = Severity::Error;
+            }
+            let mut context = RuleContext {
+                code: rule.code(),
+                rule: rule.name(),
+                severity,
+                dialect: self.dialect,
+                params: config.map_or(&empty, |config| &config.params),
+                diagnostics: &mut diagnostics,
+            };
+            rule.check(problem, &mut context);
+            if let Some(source) = source {
+                rule.check_source(source, &mut context);
+            }
+        }
+        diagnostics.sort_by(|a, b| a.code.cmp(&b.code).then_with(|| a.location.cmp(&b.location)));
+        self.diagnostics = diagnostics;
+        &self.diagnostics
+    }
+
+    #[must_use]
+    #[inline]
+    /// Returns the findings of the last call to `validate`.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Applies the fixes attached to the findings of the last call to `validate`.
+    ///
+    /// `problem` should be the problem that was validated. Returns the fixes
+    /// that were applied; fixes that no longer apply are skipped. Run
+    /// `validate` again afterwards to refresh the findings.
+    pub fn apply_fixes(&self, problem: &mut LpProblem) -> Vec<&Fix> {
+        self.diagnostics.iter().filter_map(|diagnostic| diagnostic.fix.as_ref()).filter(|fix| fix.apply(problem)).collect()
+    }
+
+    #[must_use]
+    #[inline]
+    /// Returns `true` if the last call to `validate` reported any errors.
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics.iter().any(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        model::{Coefficient, Constraint},
+        problem::LpProblem,
+        syntax::edit::SourceEditor,
+        validation::{LintRule, Location, RuleConfig, RuleContext, Severity, ValidationContext},
+    };
+
+    struct LongNames;
+
+    impl LintRule for LongNames {
+        fn code(&self) -> &'static str {
+            "X001"
+        }
+
+        fn name(&self) -> &'static str {
+            "long-names"
+        }
+
+        fn description(&self) -> &'static str {
+            "Variable names longer than a limit"
+        }
+
+        fn default_severity(&self) -> Severity {
+            Severity::Info
+        }
+
+        fn check(&self, problem: &LpProblem, context: &mut RuleContext<'_>) {
+            let limit: usize = context.param("max_length", 3);
+            for name in problem.variables.keys().filter(|name| name.len() > limit) {
+                context.report(Location::variable(name), format!("name is longer than {limit} characters"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_rule() {
+        let problem =
+            LpProblem::parse("minimize\nobj: x + long_name\nsubject to\nc1: x + long_name >= 1\nend").expect("test case not to fail");
+
+        let mut context = ValidationContext::empty().with_rule(LongNames);
+        let diagnostics = context.validate(&problem);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].to_string(), "info[X001] variable long_name: name is longer than 3 characters");
+
+        let mut context = ValidationContext::empty()
+            .with_rule(LongNames)
+            .with_rule_config("long-names", RuleConfig::default().with_severity(Severity::Error).with_param("max_length", 20));
+        assert!(context.validate(&problem).is_empty());
+        assert!(!context.has_errors());
+
+        let mut context = ValidationContext::empty().with_rule(LongNames).with_rule_config("X001", RuleConfig::disabled());
+        assert!(context.validate(&problem).is_empty());
+    }
+
+    #[test]
+    fn test_builtin_rules() {
+        let problem = LpProblem::parse("minimize\nobj: x + y\nsubject to\nc1: x + x >= 1\nc2: y >= 1\nbounds\n5 <= y <= 2\nz <= 4\nend")
+            .expect("test case not to fail");
+
+        let mut context = ValidationContext::new();
+        let codes: Vec<_> = context
+            .validate(&problem)
+            .iter()
+            .filter(|d| d.code.starts_with("LP0"))
+            .map(|d| (d.code.as_str(), d.location.to_string()))
+            .collect();
+        assert_eq!(
+            codes,
+            [("LP002", "variable z".to_string()), ("LP003", "constraint c1, term x".to_string()), ("LP005", "variable y".to_string())]
+        );
+        assert!(context.has_errors());
+
+        let mut context = ValidationContext::new().with_rule_config("LP003", RuleConfig::default().with_severity(Severity::Error));
+        assert!(context.validate(&problem).iter().any(|d| d.code == "LP003" && d.severity == Severity::Error));
+    }
+
+    #[test]
+    fn test_apply_fixes() {
+        let mut problem = LpProblem::parse("minimize\nobj: x + y + 2x\nsubject to\nc1: x + y - y >= 1\nc2: 0 x >= -1\nbounds\nz <= 4\nend")
+            .expect("test case not to fail");
+        if let Some(Constraint::Standard { coefficients, .. }) = problem.constraints.get_mut("c2") {
+            coefficients.clear();
+        }
+
+        let mut context = ValidationContext::new();
+        context.validate(&problem);
+        let applied: Vec<_> = context.apply_fixes(&mut problem).into_iter().map(ToString::to_string).collect();
+        assert_eq!(
+            applied,
+            [
+                "remove constraint c2",
+                "remove variable z",
+                "merge the terms for x in objective obj",
+                "merge the terms for y in constraint c1"
+            ]
+        );
+
+        assert_eq!(
+            problem.objectives["obj"].coefficients,
+            [Coefficient { var_name: "x", coefficient: 3.0 }, Coefficient { var_name: "y", coefficient: 1.0 }]
+        );
+        match &problem.constraints["c1"] {
+            Constraint::Standard { coefficients, .. } => assert_eq!(coefficients, &[Coefficient { var_name: "x", coefficient: 1.0 }]),
+            Constraint::SOS { .. } => panic!("c1 should be a standard constraint"),
+        }
+        assert!(!problem.constraints.contains_key("c2"));
+        assert!(!problem.variables.contains_key("z"));
+        assert!(context.validate(&problem).iter().all(|diagnostic| diagnostic.fix.is_none()));
+    }
+
+    #[test]
+    fn test_edit_fixes() {
+        let source = "minimize\nobj: x + y + 2x\nsubject to\nc1: x + y - y >= 1 \\ demand\nbounds\nz <= 4\nend\n";
+        let problem = LpProblem::parse(source).expect("test case not to fail");
+
+        let mut context = ValidationContext::new();
+        context.validate_source(&problem, source);
+        let mut editor = SourceEditor::new(source);
+        for fix in context.diagnostics().iter().filter_map(|diagnostic| diagnostic.fix.as_ref()) {
+            assert_eq!(fix.edit(&mut editor), Ok(true));
+        }
+
+        let fixed = editor.apply();
+        assert_eq!(fixed, "minimize\nobj: 3 x + y\nsubject to\nc1: x >= 1 \\ demand\nbounds\nend\n");
+        let problem = LpProblem::parse(&fixed).expect("test case not to fail");
+        assert!(context.validate_source(&problem, &fixed).iter().all(|diagnostic| diagnostic.fix.is_none()));
+    }
+}
@@ -0,0 +1,323 @@
+//! Structural checks that every problem should pass.
+//!
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    diff::coefficient_map,
+    model::{ComparisonOp, Constraint, Variable, VariableType},
+    problem::LpProblem,
+    validation::{
+        rules::{linear_rows, rows},
+        Fix, LintRule, Location, RuleContext, Severity,
+    },
+};
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP001`: A constraint without any terms.
+///
+/// Empty constraints that always hold are fixed by removing them.
+pub struct EmptyConstraint;
+
+impl LintRule for EmptyConstraint {
+    fn code(&self) -> &'static str {
+        "LP001"
+    }
+
+    fn name(&self) -> &'static str {
+        "empty-constraint"
+    }
+
+    fn description(&self) -> &'static str {
+        "Constraints without any terms are either trivially satisfied or trivially infeasible"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, problem: &LpProblem, context: &mut RuleContext<'_>) {
+        for (name, constraint) in &problem.constraints {
+            match constraint {
+                Constraint::Standard { coefficients, operator, rhs, .. } if coefficients.is_empty() => {
+                    let satisfied = match operator {
+                        ComparisonOp::GT | ComparisonOp::GTE => *rhs <= 0.0,
+                        ComparisonOp::EQ => *rhs == 0.0,
+                        ComparisonOp::LT | ComparisonOp::LTE => *rhs >= 0.0,
+                    };
+                    let location = Location::constraint(name);
+                    if satisfied {
+                        let fix = Fix::RemoveConstraint { name: name.to_string() };
+                        context.report_with_fix(location, format!("constraint has no terms (0 {operator} {rhs})"), fix);
+                    } else {
+                        context.report(location, format!("constraint has no terms and cannot be satisfied (0 {operator} {rhs})"));
+                    }
+                }
+                Constraint::SOS { weights, .. } if weights.is_empty() => {
+                    let fix = Fix::RemoveConstraint { name: name.to_string() };
+                    context.report_with_fix(Location::constraint(name), "SOS constraint has no members", fix);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP002`: A variable that no objective or constraint refers to.
+///
+/// Fixed by removing the declaration.
+pub struct UnusedVariable;
+
+impl LintRule for UnusedVariable {
+    fn code(&self) -> &'static str {
+        "LP002"
+    }
+
+    fn name(&self) -> &'static str {
+        "unused-variable"
+    }
+
+    fn description(&self) -> &'static str {
+        "Variables declared in the bounds or type sections but never used"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, problem: &LpProblem, context: &mut RuleContext<'_>) {
+        let used: HashSet<&str> = rows(problem).flat_map(|(_, terms)| terms.iter().map(|term| term.var_name)).collect();
+        for name in problem.variables.keys().filter(|name| !used.contains(*name)) {
+            let fix = Fix::RemoveVariable { name: name.to_string() };
+            context.report_with_fix(Location::variable(name), "variable is not used in any objective or constraint", fix);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP003`: A variable that appears more than once in the same expression.
+///
+/// Fixed by summing the repeated terms. Repeated SOS members are reported by `LP301`.
+pub struct DuplicateTerm;
+
+impl LintRule for DuplicateTerm {
+    fn code(&self) -> &'static str {
+        "LP003"
+    }
+
+    fn name(&self) -> &'static str {
+        "duplicate-term"
+    }
+
+    fn description(&self) -> &'static str {
+        "Variables repeated within one objective or constraint, which solvers sum or reject"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, problem: &LpProblem, context: &mut RuleContext<'_>) {
+        for (location, terms) in linear_rows(problem) {
+            let mut counts: HashMap<&str, usize> = HashMap::new();
+            for term in terms {
+                *counts.entry(term.var_name).or_default() += 1;
+            }
+            for (variable, count) in counts.into_iter().filter(|(_, count)| *count > 1) {
+                let fix = match &location {
+                    Location::Objective { name, .. } => {
+                        Fix::MergeObjectiveTerms { objective: name.clone(), variable: variable.to_string() }
+                    }
+                    Location::Constraint { name, .. } => {
+                        Fix::MergeConstraintTerms { constraint: name.clone(), variable: variable.to_string() }
+                    }
+                    Location::Problem | Location::Variable { .. } | Location::Line { .. } => continue,
+                };
+                context.report_with_fix(location.clone().with_term(variable), format!("variable appears {count} times"), fix);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP004`: A problem without an objective, or an objective without terms.
+pub struct MissingObjective;
+
+impl LintRule for MissingObjective {
+    fn code(&self) -> &'static str {
+        "LP004"
+    }
+
+    fn name(&self) -> &'static str {
+        "missing-objective"
+    }
+
+    fn description(&self) -> &'static str {
+        "Problems without an objective function, which reduce to feasibility problems"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, problem: &LpProblem, context: &mut RuleContext<'_>) {
+        if problem.objectives.is_empty() {
+            context.report(Location::Problem, "problem has no objective");
+        }
+        for (name, objective) in &problem.objectives {
+            if objective.coefficients.is_empty() {
+                context.report(Location::objective(name), "objective has no terms");
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP005`: A variable whose lower bound exceeds its upper bound.
+///
+/// Bounds replaced by a later type section are checked too.
+pub struct InvertedBounds;
+
+impl LintRule for InvertedBounds {
+    fn code(&self) -> &'static str {
+        "LP005"
+    }
+
+    fn name(&self) -> &'static str {
+        "inverted-bounds"
+    }
+
+    fn description(&self) -> &'static str {
+        "Variables whose lower bound exceeds their upper bound, making the problem infeasible"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, problem: &LpProblem, context: &mut RuleContext<'_>) {
+        for (name, variable) in &problem.variables {
+            let declared = if variable.has_bound_type() { Some(&variable.var_type) } else { variable.declared_bounds.as_ref() };
+            if let Some(VariableType::DoubleBound(lower, upper)) = declared {
+                if lower > upper {
+                    context.report(Location::variable(name), format!("lower bound {lower} exceeds upper bound {upper}"));
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP008`: An objective term that can improve without limit.
+///
+/// A variable that no constraint refers to is only limited by its bounds, so
+/// if the bound in the improving direction is infinite the problem is
+/// unbounded. Bounds are taken from `Variable::effective_bounds`, so a
+/// variable declared `x free` is unbounded below while an undeclared variable
+/// keeps the default bounds of `[0, +inf)`.
+pub struct UnboundedObjective;
+
+impl LintRule for UnboundedObjective {
+    fn code(&self) -> &'static str {
+        "LP008"
+    }
+
+    fn name(&self) -> &'static str {
+        "unbounded-objective"
+    }
+
+    fn description(&self) -> &'static str {
+        "Objective terms over unconstrained variables that make the problem unbounded"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, problem: &LpProblem, context: &mut RuleContext<'_>) {
+        let constrained: HashSet<&str> = problem
+            .constraints
+            .values()
+            .filter_map(|constraint| match constraint {
+                Constraint::Standard { coefficients, .. } => Some(coefficients),
+                Constraint::SOS { .. } => None,
+            })
+            .flat_map(|coefficients| coefficients.iter().filter(|term| term.coefficient != 0.0).map(|term| term.var_name))
+            .collect();
+        for (name, objective) in &problem.objectives {
+            for (variable, coefficient) in coefficient_map(&objective.coefficients) {
+                if coefficient == 0.0 || constrained.contains(variable) {
+                    continue;
+                }
+                let (lower, upper) = problem.variables.get(variable).map_or((0.0, f64::INFINITY), Variable::effective_bounds);
+                let increasing = (coefficient > 0.0) != problem.sense.is_minimization();
+                let message = match (increasing, lower, upper) {
+                    (true, _, upper) if upper == f64::INFINITY => "variable is in no constraint and the objective improves as it increases",
+                    (false, lower, _) if lower == f64::NEG_INFINITY => {
+                        "variable is in no constraint and the objective improves as it decreases"
+                    }
+                    _ => continue,
+                };
+                context.report(Location::objective(name).with_term(variable), format!("{message}, so the problem is unbounded"));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+
+    use crate::{
+        model::{Attributes, ComparisonOp, Constraint, Objective},
+        problem::LpProblem,
+        validation::ValidationContext,
+    };
+
+    #[test]
+    fn test_basic_rules() {
+        let mut problem = LpProblem::new();
+        problem.add_constraint(Constraint::Standard {
+            name: Cow::Borrowed("c1"),
+            coefficients: vec![],
+            operator: ComparisonOp::GTE,
+            rhs: 1.0,
+            attributes: Attributes::new(),
+        });
+        problem.add_objective(Objective::new(Cow::Borrowed("obj"), vec![]));
+
+        let mut context = ValidationContext::new();
+        let messages: Vec<_> = context.validate(&problem).iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "warning[LP001] constraint c1: constraint has no terms and cannot be satisfied (0 >= 1)",
+                "warning[LP004] objective obj: objective has no terms"
+            ]
+        );
+
+        let mut context = ValidationContext::new();
+        assert_eq!(context.validate(&LpProblem::new())[0].to_string(), "warning[LP004] problem: problem has no objective");
+    }
+
+    #[test]
+    fn test_unbounded_objective() {
+        let problem = LpProblem::parse(
+            "maximize\nobj: x + y - z + w + 0 v + u - f + g\nsubject to\nc1: x <= 4\nbounds\ny <= 10\nz >= -inf\nw free\nu >= -inf\nf free\n0 <= g <= 3\nintegers\ng\nend",
+        )
+        .expect("test case not to fail");
+
+        let mut context = ValidationContext::new();
+        let messages: Vec<_> = context.validate(&problem).iter().filter(|d| d.code == "LP008").map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "warning[LP008] objective obj, term f: variable is in no constraint and the objective improves as it decreases, so the problem is unbounded",
+                "warning[LP008] objective obj, term u: variable is in no constraint and the objective improves as it increases, so the problem is unbounded",
+                "warning[LP008] objective obj, term w: variable is in no constraint and the objective improves as it increases, so the problem is unbounded",
+                "warning[LP008] objective obj, term z: variable is in no constraint and the objective improves as it decreases, so the problem is unbounded",
+            ]
+        );
+    }
+}
@@ -0,0 +1,241 @@
+//! Numerical conditioning checks.
+//!
+//! Solvers work in floating point, and models mixing very large and very small
+//! magnitudes lose precision, stall or report spurious infeasibility. These
+//! rules flag the usual symptoms. SOS weights only order the members of a set
+//! and are not checked.
+//!
+//! `LP104` reads the source instead of the model, since by the time a literal
+//! is an `f64` any digits it could not hold are already gone.
+//!
+
+use crate::{
+    model::Constraint,
+    problem::LpProblem,
+    syntax::{tokenize, TokenKind},
+    validation::{rules::linear_rows, LintRule, Location, RuleContext, Severity},
+};
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP101`: A row whose largest and smallest non-zero coefficients are too far apart.
+///
+/// Parameters:
+///
+/// * `max_ratio` - The largest acceptable ratio between magnitudes, `1e9` by default.
+pub struct CoefficientRange;
+
+impl LintRule for CoefficientRange {
+    fn code(&self) -> &'static str {
+        "LP101"
+    }
+
+    fn name(&self) -> &'static str {
+        "coefficient-range"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rows whose coefficient magnitudes span too many orders of magnitude"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, problem: &LpProblem, context: &mut RuleContext<'_>) {
+        let max_ratio: f64 = context.param("max_ratio", 1e9);
+        for (location, coefficients) in linear_rows(problem) {
+            let magnitudes = coefficients.iter().map(|c| c.coefficient.abs()).filter(|c| *c > 0.0 && c.is_finite());
+            let (smallest, largest) = magnitudes.fold((f64::INFINITY, 0.0_f64), |(lo, hi), c| (lo.min(c), hi.max(c)));
+            if largest > 0.0 && largest / smallest > max_ratio {
+                context.report(location, format!("coefficient magnitudes range from {smallest:e} to {largest:e}"));
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP102`: A non-zero coefficient small enough to be mistaken for zero.
+///
+/// Parameters:
+///
+/// * `threshold` - Magnitudes below this are reported, `1e-9` by default.
+pub struct TinyCoefficient;
+
+impl LintRule for TinyCoefficient {
+    fn code(&self) -> &'static str {
+        "LP102"
+    }
+
+    fn name(&self) -> &'static str {
+        "tiny-coefficient"
+    }
+
+    fn description(&self) -> &'static str {
+        "Non-zero coefficients below the typical solver zero tolerance"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, problem: &LpProblem, context: &mut RuleContext<'_>) {
+        let threshold: f64 = context.param("threshold", 1e-9);
+        for (location, coefficients) in linear_rows(problem) {
+            for term in coefficients.iter().filter(|term| term.coefficient != 0.0 && term.coefficient.abs() < threshold) {
+                context.report(location.clone().with_term(term.var_name), format!("coefficient {:e} is close to zero", term.coefficient));
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP103`: A constraint with a very large right-hand side.
+///
+/// Parameters:
+///
+/// * `threshold` - Magnitudes above this are reported, `1e9` by default.
+pub struct LargeRhs;
+
+impl LintRule for LargeRhs {
+    fn code(&self) -> &'static str {
+        "LP103"
+    }
+
+    fn name(&self) -> &'static str {
+        "large-rhs"
+    }
+
+    fn description(&self) -> &'static str {
+        "Right-hand sides large enough to swamp the solver tolerances, often a stand-in for infinity"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, problem: &LpProblem, context: &mut RuleContext<'_>) {
+        let threshold: f64 = context.param("threshold", 1e9);
+        for (name, constraint) in &problem.constraints {
+            if let Constraint::Standard { rhs, .. } = constraint {
+                if rhs.is_finite() && rhs.abs() > threshold {
+                    context.report(Location::constraint(name), format!("right-hand side {rhs:e} exceeds {threshold:e}"));
+                }
+            }
+        }
+    }
+}
+
+/// Returns the significant decimal digits of a literal and the power of ten they are scaled by.
+///
+/// Leading and trailing zeros are dropped, so equal values written differently compare equal.
+fn decimal_digits(text: &str) -> Option<(String, i64)> {
+    let (mantissa, exponent) = match text.find(['e', 'E']) {
+        Some(index) => (&text[..index], text[index + 1..].parse::<i64>().ok()?),
+        None => (text, 0),
+    };
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = format!("{whole}{fraction}");
+    let significant = digits.trim_start_matches('0').trim_end_matches('0');
+    let trailing = digits.len() - digits.trim_end_matches('0').len();
+    let scale = exponent - i64::try_from(fraction.len()).ok()? + i64::try_from(trailing).ok()?;
+    Some((significant.to_string(), if significant.is_empty() { 0 } else { scale }))
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP104`: A numeric literal that does not survive conversion to `f64`.
+///
+/// A literal is reported when it overflows to infinity, underflows to zero, or
+/// has more significant digits than the nearest `f64` keeps. Literals such as
+/// `0.1`, whose nearest `f64` prints back as the same decimal, are not reported.
+pub struct InexactLiteral;
+
+impl LintRule for InexactLiteral {
+    fn code(&self) -> &'static str {
+        "LP104"
+    }
+
+    fn name(&self) -> &'static str {
+        "inexact-literal"
+    }
+
+    fn description(&self) -> &'static str {
+        "Numbers that lose digits or overflow when stored as f64"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check_source(&self, source: &str, context: &mut RuleContext<'_>) {
+        let mut line = 1;
+        for token in tokenize(source) {
+            line += token.text.matches('\n').count();
+            if token.kind != TokenKind::Number || token.text.chars().next().map_or(true, |c| !c.is_ascii_digit() && c != '.') {
+                continue;
+            }
+            let value: f64 = match token.text.parse() {
+                Ok(value) => value,
+                Err(_) => continue,
+            };
+            let message = if value.is_infinite() {
+                format!("literal {} exceeds the f64 range and is stored as {value}", token.text)
+            } else if decimal_digits(token.text) == decimal_digits(&format!("{value:e}")) {
+                continue;
+            } else {
+                format!("literal {} is stored as {value}", token.text)
+            };
+            context.report(Location::Line { line }, message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        problem::LpProblem,
+        validation::{RuleConfig, ValidationContext},
+    };
+
+    #[test]
+    fn test_conditioning_rules() {
+        let problem =
+            LpProblem::parse("minimize\nobj: x + 1e-12 y\nsubject to\nc1: 1e10 x + 0.5 y >= 1\nc2: x + y <= 5e12\nc3: x - y >= 0\nend")
+                .expect("test case not to fail");
+
+        let mut context = ValidationContext::new();
+        let messages: Vec<_> = context.validate(&problem).iter().filter(|d| d.code.starts_with("LP1")).map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "warning[LP101] objective obj: coefficient magnitudes range from 1e-12 to 1e0",
+                "warning[LP101] constraint c1: coefficient magnitudes range from 5e-1 to 1e10",
+                "warning[LP102] objective obj, term y: coefficient 1e-12 is close to zero",
+                "warning[LP103] constraint c2: right-hand side 5e12 exceeds 1e9",
+            ]
+        );
+
+        let mut context =
+            ValidationContext::new().with_rule_config("coefficient-range", RuleConfig::default().with_param("max_ratio", 1e11));
+        let diagnostics = context.validate(&problem);
+        assert_eq!(diagnostics.iter().filter(|d| d.code == "LP101").count(), 1);
+    }
+
+    #[test]
+    fn test_inexact_literal() {
+        let source =
+            "minimize\nobj: 0.1 x + 1.0000000000000000001 y\nsubject to\nc1: x + 2.50e1 y >= 1e400\nc2: 1e-400 x + 0.00 y <= 1E+2\nend";
+        let problem = LpProblem::parse(source).expect("test case not to fail");
+
+        let mut context = ValidationContext::new();
+        let messages: Vec<_> =
+            context.validate_source(&problem, source).iter().filter(|d| d.code == "LP104").map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "warning[LP104] line 2: literal 1.0000000000000000001 is stored as 1",
+                "warning[LP104] line 4: literal 1e400 exceeds the f64 range and is stored as inf",
+                "warning[LP104] line 5: literal 1e-400 is stored as 0",
+            ]
+        );
+    }
+}
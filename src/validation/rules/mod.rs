@@ -0,0 +1,100 @@
+//! Built-in lint rules.
+//!
+//! | Code    | Name                          | Default severity |
+//! |---------|-------------------------------|------------------|
+//! | `LP001` | `empty-constraint`            | warning          |
+//! | `LP002` | `unused-variable`             | warning          |
+//! | `LP003` | `duplicate-term`              | warning          |
+//! | `LP004` | `missing-objective`           | warning          |
+//! | `LP005` | `inverted-bounds`             | error            |
+//! | `LP006` | `binary-bound-conflict`       | error            |
+//! | `LP007` | `semi-continuous-upper-bound` | error            |
+//! | `LP008` | `unbounded-objective`         | warning          |
+//! | `LP101` | `coefficient-range`           | warning          |
+//! | `LP102` | `tiny-coefficient`            | warning          |
+//! | `LP103` | `large-rhs`                   | warning          |
+//! | `LP104` | `inexact-literal`             | warning          |
+//! | `LP201` | `bound-implied-constraint`    | info             |
+//! | `LP202` | `dominated-constraint`        | info             |
+//! | `LP203` | `duplicate-row`               | warning          |
+//! | `LP301` | `sos-duplicate-member`        | error            |
+//! | `LP302` | `sos-duplicate-weight`        | error            |
+//! | `LP303` | `sos-weight-order`            | warning          |
+//! | `LP304` | `sos-member-count`            | warning          |
+//! | `LP401` | `naming-convention`           | warning          |
+//! | `LP501` | `missing-end`                 | warning          |
+//! | `LP502` | `empty-section`               | warning          |
+//! | `LP503` | `foreign-section-header`      | warning          |
+//!
+//! - `basic`: Structural checks that every problem should pass.
+//! - `conditioning`: Numerical conditioning checks.
+//! - `naming`: Naming convention checks.
+//! - `redundancy`: Redundant constraint checks.
+//! - `sos`: Special ordered set checks.
+//! - `structure`: Checks on the section structure of the LP source.
+//! - `types`: Checks that variable types agree with their declared bounds.
+//!
+
+pub mod basic;
+pub mod conditioning;
+pub mod naming;
+pub mod redundancy;
+pub mod sos;
+pub mod structure;
+pub mod types;
+
+use crate::{
+    model::{Coefficient, Constraint},
+    problem::LpProblem,
+    validation::{LintRule, Location},
+};
+
+#[must_use]
+/// Returns every built-in rule.
+pub fn builtin() -> Vec<Box<dyn LintRule>> {
+    vec![
+        Box::new(basic::EmptyConstraint),
+        Box::new(basic::UnusedVariable),
+        Box::new(basic::DuplicateTerm),
+        Box::new(basic::MissingObjective),
+        Box::new(basic::InvertedBounds),
+        Box::new(basic::UnboundedObjective),
+        Box::new(types::BinaryBoundConflict),
+        Box::new(types::SemiContinuousUpperBound),
+        Box::new(conditioning::CoefficientRange),
+        Box::new(conditioning::TinyCoefficient),
+        Box::new(conditioning::LargeRhs),
+        Box::new(conditioning::InexactLiteral),
+        Box::new(redundancy::BoundImpliedConstraint),
+        Box::new(redundancy::DominatedConstraint),
+        Box::new(redundancy::DuplicateRow),
+        Box::new(sos::SosDuplicateMember),
+        Box::new(sos::SosDuplicateWeight),
+        Box::new(sos::SosWeightOrder),
+        Box::new(sos::SosMemberCount),
+        Box::new(naming::NamingConvention),
+        Box::new(structure::MissingEnd),
+        Box::new(structure::EmptySection),
+        Box::new(structure::ForeignSectionHeader),
+    ]
+}
+
+/// Returns the location and terms of every objective and constraint, including SOS weights.
+pub(crate) fn rows<'p>(problem: &'p LpProblem) -> impl Iterator<Item = (Location, &'p [Coefficient<'p>])> {
+    let objectives = problem.objectives.iter().map(|(name, objective)| (Location::objective(name), objective.coefficients.as_slice()));
+    let constraints = problem.constraints.iter().map(|(name, constraint)| match constraint {
+        Constraint::Standard { coefficients, .. } => (Location::constraint(name), coefficients.as_slice()),
+        Constraint::SOS { weights, .. } => (Location::constraint(name), weights.as_slice()),
+    });
+    objectives.chain(constraints)
+}
+
+/// Returns the location and terms of every objective and linear constraint.
+pub(crate) fn linear_rows<'p>(problem: &'p LpProblem) -> impl Iterator<Item = (Location, &'p [Coefficient<'p>])> {
+    let objectives = problem.objectives.iter().map(|(name, objective)| (Location::objective(name), objective.coefficients.as_slice()));
+    let constraints = problem.constraints.iter().filter_map(|(name, constraint)| match constraint {
+        Constraint::Standard { coefficients, .. } => Some((Location::constraint(name), coefficients.as_slice())),
+        Constraint::SOS { .. } => None,
+    });
+    objectives.chain(constraints)
+}
@@ -0,0 +1,197 @@
+//! Naming convention checks.
+//!
+//! Generated models often end up with names that one solver accepts and
+//! another rejects, or that drift from a team's conventions. `NamingConvention`
+//! checks every objective, constraint and variable name against the character
+//! set of a target dialect, a maximum length and optional per-kind patterns.
+//! The patterns are regular expressions and need the `regex` feature.
+//!
+
+use crate::{
+    problem::LpProblem,
+    validation::{Dialect, LintRule, Location, RuleContext, Severity},
+};
+
+/// Punctuation the CPLEX LP format allows in names, alongside letters and digits.
+const CPLEX_NAME_CHARS: &str = "!\"#$%&()/,.;?@_`'{}|~";
+
+/// Characters the Gurobi LP format does not allow in names.
+const GUROBI_FORBIDDEN_CHARS: &str = "+-*/^<>=:[]";
+
+/// Returns the first character of `name` that `dialect` does not allow, if any.
+fn invalid_char(dialect: Dialect, name: &str) -> Option<char> {
+    name.chars().find(|&c| match dialect {
+        Dialect::Cplex | Dialect::Xpress | Dialect::Mosek => !c.is_ascii_alphanumeric() && !CPLEX_NAME_CHARS.contains(c),
+        Dialect::Gurobi => c.is_whitespace() || GUROBI_FORBIDDEN_CHARS.contains(c),
+    })
+}
+
+/// Returns `true` if `dialect` does not allow names to start with `c`.
+fn invalid_first_char(dialect: Dialect, c: char) -> bool {
+    match dialect {
+        Dialect::Cplex | Dialect::Xpress | Dialect::Mosek => c.is_ascii_digit() || c == '.',
+        Dialect::Gurobi => c.is_ascii_digit(),
+    }
+}
+
+#[cfg(feature = "regex")]
+type Pattern = regex::Regex;
+
+#[cfg(not(feature = "regex"))]
+/// A pattern, which cannot be compiled without the `regex` feature.
+enum Pattern {}
+
+#[cfg(not(feature = "regex"))]
+impl Pattern {
+    fn is_match(&self, _: &str) -> bool {
+        match *self {}
+    }
+
+    fn as_str(&self) -> &str {
+        match *self {}
+    }
+}
+
+/// Compiles the anchored pattern in parameter `param`, reporting it if it is invalid.
+fn pattern(context: &mut RuleContext<'_>, param: &str) -> Option<Pattern> {
+    let source: String = context.param(param, String::new());
+    if source.is_empty() {
+        return None;
+    }
+    #[cfg(feature = "regex")]
+    match Pattern::new(&format!("^(?:{source})$")) {
+        Ok(regex) => Some(regex),
+        Err(error) => {
+            context.report(Location::Problem, format!("parameter {param} is not a valid pattern: {error}"));
+            None
+        }
+    }
+    #[cfg(not(feature = "regex"))]
+    {
+        context.report(Location::Problem, format!("parameter {param} is ignored: patterns need the `regex` feature"));
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP401`: A name that breaks the configured naming convention.
+///
+/// Parameters:
+///
+/// * `dialect` - The dialect whose character set names must use, the context's dialect by default.
+/// * `max_length` - The longest acceptable name, `255` by default.
+/// * `forbidden_chars` - Additional characters names must not contain.
+/// * `objective_pattern`, `constraint_pattern`, `variable_pattern` - Regular
+///   expressions that whole names of each kind must match (`regex` feature).
+pub struct NamingConvention;
+
+impl LintRule for NamingConvention {
+    fn code(&self) -> &'static str {
+        "LP401"
+    }
+
+    fn name(&self) -> &'static str {
+        "naming-convention"
+    }
+
+    fn description(&self) -> &'static str {
+        "Names that are invalid in the target dialect or break the configured conventions"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, problem: &LpProblem, context: &mut RuleContext<'_>) {
+        let dialect: Dialect = context.param("dialect", context.dialect());
+        let max_length: usize = context.param("max_length", 255);
+        let forbidden: String = context.param("forbidden_chars", String::new());
+        let patterns =
+            (pattern(context, "objective_pattern"), pattern(context, "constraint_pattern"), pattern(context, "variable_pattern"));
+
+        let objectives = problem.objectives.keys().map(|name| (Location::objective(name), name.as_ref(), patterns.0.as_ref()));
+        let constraints = problem.constraints.keys().map(|name| (Location::constraint(name), name.as_ref(), patterns.1.as_ref()));
+        let variables = problem.variables.keys().map(|name| (Location::variable(name), *name, patterns.2.as_ref()));
+        for (location, name, pattern) in objectives.chain(constraints).chain(variables) {
+            if name.chars().count() > max_length {
+                context.report(location.clone(), format!("name is longer than {max_length} characters"));
+            }
+            if let Some(c) = name.chars().next().filter(|c| invalid_first_char(dialect, *c)) {
+                context.report(location.clone(), format!("{dialect} names cannot start with '{c}'"));
+            }
+            if let Some(c) = invalid_char(dialect, name) {
+                context.report(location.clone(), format!("{dialect} names cannot contain '{c}'"));
+            }
+            if let Some(c) = name.chars().find(|c| forbidden.contains(*c)) {
+                context.report(location.clone(), format!("name contains the forbidden character '{c}'"));
+            }
+            if let Some(pattern) = pattern.filter(|pattern| !pattern.is_match(name)) {
+                context.report(location, format!("name does not match the pattern {}", pattern.as_str()));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        problem::LpProblem,
+        validation::{RuleConfig, ValidationContext},
+    };
+
+    const SOURCE: &str = "minimize\nobj: x_1 + y.2 + long_name\nsubject to\nc1: x_1 + y.2 + long_name >= 1\nend";
+
+    #[test]
+    fn test_naming_convention() {
+        let problem = LpProblem::parse(SOURCE).expect("test case not to fail");
+
+        let mut context = ValidationContext::new();
+        assert!(context.validate(&problem).iter().all(|d| d.code != "LP401"));
+
+        let config = RuleConfig::default().with_param("dialect", "gurobi").with_param("max_length", 8).with_param("forbidden_chars", ".");
+        let mut context = ValidationContext::new().with_rule_config("naming-convention", config);
+        let messages: Vec<_> = context.validate(&problem).iter().filter(|d| d.code == "LP401").map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "warning[LP401] variable long_name: name is longer than 8 characters",
+                "warning[LP401] variable y.2: name contains the forbidden character '.'",
+            ]
+        );
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_naming_convention_patterns() {
+        let problem = LpProblem::parse(SOURCE).expect("test case not to fail");
+
+        let config = RuleConfig::default().with_param("variable_pattern", "[a-z]+_[0-9]+").with_param("constraint_pattern", "row_[0-9]+");
+        let mut context = ValidationContext::new().with_rule_config("naming-convention", config);
+        let messages: Vec<_> = context.validate(&problem).iter().filter(|d| d.code == "LP401").map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "warning[LP401] constraint c1: name does not match the pattern ^(?:row_[0-9]+)$",
+                "warning[LP401] variable long_name: name does not match the pattern ^(?:[a-z]+_[0-9]+)$",
+                "warning[LP401] variable y.2: name does not match the pattern ^(?:[a-z]+_[0-9]+)$",
+            ]
+        );
+
+        let mut context = ValidationContext::new().with_rule_config("LP401", RuleConfig::default().with_param("variable_pattern", "[a-z"));
+        let diagnostics = context.validate(&problem);
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.code == "LP401" && d.message.starts_with("parameter variable_pattern is not a valid pattern")));
+    }
+
+    #[cfg(not(feature = "regex"))]
+    #[test]
+    fn test_naming_convention_patterns_unavailable() {
+        let problem = LpProblem::parse(SOURCE).expect("test case not to fail");
+
+        let config = RuleConfig::default().with_param("variable_pattern", "[a-z]+_[0-9]+");
+        let mut context = ValidationContext::new().with_rule_config("LP401", config);
+        let messages: Vec<_> = context.validate(&problem).iter().filter(|d| d.code == "LP401").map(|d| d.message.clone()).collect();
+        assert_eq!(messages, ["parameter variable_pattern is ignored: patterns need the `regex` feature"]);
+    }
+}
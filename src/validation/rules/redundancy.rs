@@ -0,0 +1,310 @@
+//! Redundant constraint checks.
+//!
+//! A constraint is redundant when removing it leaves the feasible region
+//! unchanged. Redundant rows are harmless to most solvers but often point at a
+//! modelling mistake, such as a bound written twice with different values.
+//!
+
+use std::collections::BTreeMap;
+
+use crate::{
+    diff::coefficient_map,
+    model::{ComparisonOp, Constraint, VariableType},
+    problem::LpProblem,
+    validation::{LintRule, Location, RuleContext, Severity},
+};
+
+/// Returns `true` if `a <= b` within a tolerance relative to the magnitude of `b`.
+#[inline]
+fn at_most(a: f64, b: f64, tolerance: f64) -> bool {
+    a <= b + tolerance * (1.0 + b.abs())
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP201`: A constraint that the variable bounds already guarantee.
+///
+/// Parameters:
+///
+/// * `tolerance` - The relative tolerance when comparing activities, `1e-9` by default.
+pub struct BoundImpliedConstraint;
+
+impl LintRule for BoundImpliedConstraint {
+    fn code(&self) -> &'static str {
+        "LP201"
+    }
+
+    fn name(&self) -> &'static str {
+        "bound-implied-constraint"
+    }
+
+    fn description(&self) -> &'static str {
+        "Constraints satisfied by every assignment within the variable bounds"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn check(&self, problem: &LpProblem, context: &mut RuleContext<'_>) {
+        let tolerance: f64 = context.param("tolerance", 1e-9);
+        for (name, constraint) in &problem.constraints {
+            let (coefficients, operator, rhs) = match constraint {
+                Constraint::Standard { coefficients, operator, rhs, .. } if !coefficients.is_empty() => (coefficients, operator, *rhs),
+                _ => continue,
+            };
+
+            let (mut min_activity, mut max_activity) = (0.0, 0.0);
+            for (variable, coefficient) in coefficient_map(coefficients).into_iter().filter(|(_, c)| *c != 0.0) {
+                // Variables declared free are unbounded below, so their rows are never reported
+                let (lower, upper) = problem.variables.get(variable).map_or((0.0, f64::INFINITY), |v| {
+                    let (lower, upper) = v.effective_bounds();
+                    if v.var_type == VariableType::SemiContinuous {
+                        (lower.min(0.0), upper.max(0.0))
+                    } else {
+                        (lower, upper)
+                    }
+                });
+                if coefficient > 0.0 {
+                    min_activity += coefficient * lower;
+                    max_activity += coefficient * upper;
+                } else {
+                    min_activity += coefficient * upper;
+                    max_activity += coefficient * lower;
+                }
+            }
+
+            let message = match operator {
+                ComparisonOp::LT | ComparisonOp::LTE if at_most(max_activity, rhs, tolerance) => {
+                    format!("maximum activity {max_activity} within the bounds never exceeds {rhs}")
+                }
+                ComparisonOp::GT | ComparisonOp::GTE if at_most(rhs, min_activity, tolerance) => {
+                    format!("minimum activity {min_activity} within the bounds is never below {rhs}")
+                }
+                ComparisonOp::EQ if at_most(max_activity, rhs, tolerance) && at_most(rhs, min_activity, tolerance) => {
+                    format!("the bounds fix the activity at {rhs}")
+                }
+                _ => continue,
+            };
+            context.report(Location::constraint(name), message);
+        }
+    }
+}
+
+/// The right-hand side of a constraint rewritten as `a . x <= rhs`, scaled so the largest magnitude in `a` is one.
+struct Halfspace<'p> {
+    name: &'p str,
+    rhs: f64,
+    equality: bool,
+}
+
+/// Quantises a scaled coefficient so that rows differing only by rounding share a key.
+#[inline]
+fn quantise(value: f64) -> i64 {
+    (value * 1e9).round() as i64
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP202`: An inequality implied by a tighter row with the same left-hand side.
+///
+/// Rows are compared after scaling by a positive factor, so `2x + 2y <= 12`
+/// is implied by `x + y <= 4`. An equality implies both of its halves.
+///
+/// Parameters:
+///
+/// * `tolerance` - The relative tolerance when comparing right-hand sides, `1e-9` by default.
+pub struct DominatedConstraint;
+
+impl LintRule for DominatedConstraint {
+    fn code(&self) -> &'static str {
+        "LP202"
+    }
+
+    fn name(&self) -> &'static str {
+        "dominated-constraint"
+    }
+
+    fn description(&self) -> &'static str {
+        "Inequalities implied by a tighter constraint over the same terms"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn check(&self, problem: &LpProblem, context: &mut RuleContext<'_>) {
+        let tolerance: f64 = context.param("tolerance", 1e-9);
+        let mut groups: BTreeMap<Vec<(&str, i64)>, Vec<Halfspace>> = BTreeMap::new();
+        for (name, constraint) in &problem.constraints {
+            let (coefficients, operator, rhs) = match constraint {
+                Constraint::Standard { coefficients, operator, rhs, .. } => (coefficient_map(coefficients), operator, *rhs),
+                Constraint::SOS { .. } => continue,
+            };
+            let scale = coefficients.values().fold(0.0_f64, |max, c| max.max(c.abs()));
+            if scale == 0.0 || !scale.is_finite() {
+                continue;
+            }
+            let signs: &[f64] = match operator {
+                ComparisonOp::LT | ComparisonOp::LTE => &[1.0],
+                ComparisonOp::GT | ComparisonOp::GTE => &[-1.0],
+                ComparisonOp::EQ => &[1.0, -1.0],
+            };
+            for sign in signs {
+                let key = coefficients.iter().filter(|(_, c)| **c != 0.0).map(|(v, c)| (*v, quantise(sign * c / scale))).collect();
+                let halfspace = Halfspace { name, rhs: sign * rhs / scale, equality: *operator == ComparisonOp::EQ };
+                groups.entry(key).or_default().push(halfspace);
+            }
+        }
+
+        for halfspaces in groups.values().filter(|halfspaces| halfspaces.len() > 1) {
+            let tightest = halfspaces
+                .iter()
+                .min_by(|a, b| a.rhs.total_cmp(&b.rhs).then_with(|| b.equality.cmp(&a.equality)).then_with(|| a.name.cmp(b.name)))
+                .expect("group is not empty");
+            for halfspace in halfspaces.iter().filter(|h| !h.equality && !at_most(h.rhs, tightest.rhs, tolerance)) {
+                context.report(Location::constraint(halfspace.name), format!("constraint is implied by {}", tightest.name));
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum RowKind {
+    /// An inequality rewritten as `a . x <= b`.
+    AtMost,
+    /// An equality, with the sign of `a` chosen so its first coefficient is positive.
+    Equal,
+}
+
+/// A constraint's non-zero coefficients and right-hand side, both divided by the Euclidean norm of the coefficients.
+struct UnitRow<'p> {
+    name: &'p str,
+    coefficients: Vec<f64>,
+    rhs: f64,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP203`: Two constraints with identical or proportional terms and matching right-hand sides.
+///
+/// The similarity score is the cosine similarity of the two coefficient
+/// vectors, after rewriting `>=` rows as `<=`. Right-hand sides are compared
+/// after scaling both rows to unit length.
+///
+/// Parameters:
+///
+/// * `min_similarity` - The smallest similarity reported, `0.999999` by default.
+/// * `rhs_tolerance` - The relative tolerance when comparing right-hand sides, `1e-6` by default.
+pub struct DuplicateRow;
+
+impl LintRule for DuplicateRow {
+    fn code(&self) -> &'static str {
+        "LP203"
+    }
+
+    fn name(&self) -> &'static str {
+        "duplicate-row"
+    }
+
+    fn description(&self) -> &'static str {
+        "Constraints that repeat another row up to scaling, usually a generator bug"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, problem: &LpProblem, context: &mut RuleContext<'_>) {
+        let min_similarity: f64 = context.param("min_similarity", 0.999_999);
+        let rhs_tolerance: f64 = context.param("rhs_tolerance", 1e-6);
+
+        let mut groups: BTreeMap<(RowKind, Vec<&str>), Vec<UnitRow>> = BTreeMap::new();
+        for (name, constraint) in &problem.constraints {
+            let (coefficients, operator, rhs) = match constraint {
+                Constraint::Standard { coefficients, operator, rhs, .. } => (coefficient_map(coefficients), operator, *rhs),
+                Constraint::SOS { .. } => continue,
+            };
+            let (variables, values): (Vec<&str>, Vec<f64>) = coefficients.into_iter().filter(|(_, c)| *c != 0.0).unzip();
+            let norm = values.iter().map(|c| c * c).sum::<f64>().sqrt();
+            if norm == 0.0 || !norm.is_finite() {
+                continue;
+            }
+            let (kind, sign) = match operator {
+                ComparisonOp::LT | ComparisonOp::LTE => (RowKind::AtMost, 1.0),
+                ComparisonOp::GT | ComparisonOp::GTE => (RowKind::AtMost, -1.0),
+                ComparisonOp::EQ => (RowKind::Equal, values[0].signum()),
+            };
+            let row = UnitRow { name, coefficients: values.iter().map(|c| sign * c / norm).collect(), rhs: sign * rhs / norm };
+            groups.entry((kind, variables)).or_default().push(row);
+        }
+
+        for rows in groups.values_mut().filter(|rows| rows.len() > 1) {
+            rows.sort_by(|a, b| a.name.cmp(b.name));
+            for (index, row) in rows.iter().enumerate().skip(1) {
+                let duplicate = rows[..index].iter().find_map(|earlier| {
+                    let similarity: f64 = row.coefficients.iter().zip(&earlier.coefficients).map(|(a, b)| a * b).sum();
+                    let rhs_matches = (row.rhs - earlier.rhs).abs() <= rhs_tolerance * (1.0 + row.rhs.abs().max(earlier.rhs.abs()));
+                    (similarity >= min_similarity && rhs_matches).then_some((earlier.name, similarity))
+                });
+                if let Some((original, similarity)) = duplicate {
+                    context
+                        .report(Location::constraint(row.name), format!("constraint duplicates {original} (similarity {similarity:.6})"));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{problem::LpProblem, validation::ValidationContext};
+
+    #[test]
+    fn test_redundancy_rules() {
+        let problem = LpProblem::parse(
+            "minimize\nobj: x + y\nsubject to\nc1: x <= 10\nc2: x + y <= 4\nc3: 2x + 2y <= 12\nc4: x + y >= -1\nc5: x - y = 1\nc6: y - x >= -3\nbounds\nx <= 5\ny <= 5\nend",
+        )
+        .expect("test case not to fail");
+
+        let mut context = ValidationContext::new();
+        let messages: Vec<_> = context.validate(&problem).iter().filter(|d| d.code.starts_with("LP2")).map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "info[LP201] constraint c1: maximum activity 5 within the bounds never exceeds 10",
+                "info[LP201] constraint c4: minimum activity 0 within the bounds is never below -1",
+                "info[LP202] constraint c3: constraint is implied by c2",
+                "info[LP202] constraint c6: constraint is implied by c5",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bound_implied_effective_bounds() {
+        let problem = LpProblem::parse(
+            "minimize\nobj: f + g + s\nsubject to\nc1: f >= -5\nc2: g <= 4\nc3: s >= 1\nbounds\nf free\n0 <= g <= 3\n2 <= s <= 5\nintegers\ng\ngenerals\nbinaries\nsemi-continuous\ns\nend",
+        )
+        .expect("test case not to fail");
+
+        let mut context = ValidationContext::new();
+        let messages: Vec<_> = context.validate(&problem).iter().filter(|d| d.code == "LP201").map(ToString::to_string).collect();
+        assert_eq!(messages, ["info[LP201] constraint c2: maximum activity 3 within the bounds never exceeds 4"]);
+    }
+
+    #[test]
+    fn test_duplicate_row() {
+        let problem = LpProblem::parse(
+            "minimize\nobj: x + y + z\nsubject to\nc1: x + 2y >= 3\nc2: 2x + 4y >= 6\nc3: -x - 2y <= -3\nc4: x + 2y >= 5\nc5: x + y + z = 1\nc6: -2x - 2y - 2z = -2\nend",
+        )
+        .expect("test case not to fail");
+
+        let mut context = ValidationContext::new();
+        let messages: Vec<_> = context.validate(&problem).iter().filter(|d| d.code == "LP203").map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "warning[LP203] constraint c2: constraint duplicates c1 (similarity 1.000000)",
+                "warning[LP203] constraint c3: constraint duplicates c1 (similarity 1.000000)",
+                "warning[LP203] constraint c6: constraint duplicates c5 (similarity 1.000000)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! Special ordered set checks.
+//!
+//! The weights of an SOS define the order of its members, and for `S2` sets
+//! which members count as adjacent. These rules check that the weights define
+//! that order unambiguously and that each set actually restricts its members.
+//!
+
+use std::collections::HashMap;
+
+use crate::{
+    model::{Coefficient, Constraint, SOSType},
+    problem::LpProblem,
+    validation::{LintRule, Location, RuleContext, Severity},
+};
+
+/// Returns the name, type and members of every SOS constraint.
+fn sos_constraints<'p>(problem: &'p LpProblem) -> impl Iterator<Item = (&'p str, SOSType, &'p [Coefficient<'p>])> {
+    problem.constraints.iter().filter_map(|(name, constraint)| match constraint {
+        Constraint::SOS { sos_type, weights, .. } => Some((name.as_ref(), *sos_type, weights.as_slice())),
+        Constraint::Standard { .. } => None,
+    })
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP301`: A variable listed more than once in the same SOS.
+pub struct SosDuplicateMember;
+
+impl LintRule for SosDuplicateMember {
+    fn code(&self) -> &'static str {
+        "LP301"
+    }
+
+    fn name(&self) -> &'static str {
+        "sos-duplicate-member"
+    }
+
+    fn description(&self) -> &'static str {
+        "Variables listed more than once in a special ordered set"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, problem: &LpProblem, context: &mut RuleContext<'_>) {
+        for (name, _, members) in sos_constraints(problem) {
+            let mut counts: HashMap<&str, usize> = HashMap::new();
+            for member in members {
+                *counts.entry(member.var_name).or_default() += 1;
+            }
+            for (variable, count) in counts.into_iter().filter(|(_, count)| *count > 1) {
+                context.report(Location::constraint(name).with_term(variable), format!("member is listed {count} times"));
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP302`: Two members of an SOS sharing a weight, leaving their order undefined.
+pub struct SosDuplicateWeight;
+
+impl LintRule for SosDuplicateWeight {
+    fn code(&self) -> &'static str {
+        "LP302"
+    }
+
+    fn name(&self) -> &'static str {
+        "sos-duplicate-weight"
+    }
+
+    fn description(&self) -> &'static str {
+        "Special ordered set members whose weights are not distinct"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, problem: &LpProblem, context: &mut RuleContext<'_>) {
+        for (name, _, members) in sos_constraints(problem) {
+            for (index, member) in members.iter().enumerate() {
+                let earlier = members[..index]
+                    .iter()
+                    .find(|earlier| earlier.var_name != member.var_name && earlier.coefficient == member.coefficient);
+                if let Some(earlier) = earlier {
+                    let message = format!("weight {} is also used by {}", member.coefficient, earlier.var_name);
+                    context.report(Location::constraint(name).with_term(member.var_name), message);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP303`: An SOS whose members are not listed in increasing weight order.
+///
+/// The weights alone define the order, but some tools use the listing order,
+/// so a mismatch usually means one of the two is wrong.
+pub struct SosWeightOrder;
+
+impl LintRule for SosWeightOrder {
+    fn code(&self) -> &'static str {
+        "LP303"
+    }
+
+    fn name(&self) -> &'static str {
+        "sos-weight-order"
+    }
+
+    fn description(&self) -> &'static str {
+        "Special ordered set members not listed in increasing weight order"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, problem: &LpProblem, context: &mut RuleContext<'_>) {
+        for (name, _, members) in sos_constraints(problem) {
+            let descent = members.windows(2).find(|pair| pair[1].coefficient < pair[0].coefficient);
+            if let Some([previous, member]) = descent {
+                let message =
+                    format!("weight {} follows the larger weight {} of {}", member.coefficient, previous.coefficient, previous.var_name);
+                context.report(Location::constraint(name).with_term(member.var_name), message);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP304`: An SOS with too few members to restrict them.
+///
+/// An `S1` set needs at least two members and an `S2` set at least three.
+pub struct SosMemberCount;
+
+impl LintRule for SosMemberCount {
+    fn code(&self) -> &'static str {
+        "LP304"
+    }
+
+    fn name(&self) -> &'static str {
+        "sos-member-count"
+    }
+
+    fn description(&self) -> &'static str {
+        "Special ordered sets that are always satisfied because of their size"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, problem: &LpProblem, context: &mut RuleContext<'_>) {
+        for (name, sos_type, members) in sos_constraints(problem) {
+            let non_zero = match sos_type {
+                SOSType::S1 => 1,
+                SOSType::S2 => 2,
+            };
+            if !members.is_empty() && members.len() <= non_zero {
+                let message = format!("{sos_type} set with {} members is always satisfied", members.len());
+                context.report(Location::constraint(name), message);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{problem::LpProblem, validation::ValidationContext};
+
+    #[test]
+    fn test_sos_rules() {
+        let problem = LpProblem::parse(
+            "maximize\nobj: x1 + x2 + x3 + x4\nsubject to\nc1: x1 + x2 + x3 + x4 <= 4\nbounds\nx1 <= 1\nintegers\ngenerals\nbinaries\nsemi-continuous\nsos\ns1: S1:: x1:1 x2:2 x1:3\ns2: S2:: x1:1 x2:3 x3:2\ns3: S1:: x1:1 x2:1 x3:2\ns4: S2:: x3:1 x4:2\nend",
+        )
+        .expect("test case not to fail");
+
+        let mut context = ValidationContext::new();
+        let messages: Vec<_> = context.validate(&problem).iter().filter(|d| d.code.starts_with("LP3")).map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "error[LP301] constraint s1, term x1: member is listed 2 times",
+                "error[LP302] constraint s3, term x2: weight 1 is also used by x1",
+                "warning[LP303] constraint s2, term x3: weight 2 follows the larger weight 3 of x2",
+                "warning[LP304] constraint s4: S2 set with 2 members is always satisfied",
+            ]
+        );
+    }
+}
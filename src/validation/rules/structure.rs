@@ -0,0 +1,203 @@
+//! Checks on the section structure of the LP source.
+//!
+//! The parser accepts files that solvers are stricter about, such as a file
+//! without `End` or one using a header from another dialect. These rules only
+//! run from `ValidationContext::validate_source`, since the parsed model does
+//! not record which headers a file used.
+//!
+
+use crate::validation::{Dialect, LintRule, Location, RuleContext, Severity};
+
+const ALL: &[Dialect] = &[Dialect::Cplex, Dialect::Gurobi, Dialect::Xpress, Dialect::Mosek];
+
+/// Every section header the parser recognises, whether a section of that kind
+/// should declare something, and the dialects that accept the header.
+const HEADERS: &[(&str, bool, &[Dialect])] = &[
+    ("minimize", false, ALL),
+    ("minimum", false, ALL),
+    ("min", false, ALL),
+    ("maximize", false, ALL),
+    ("maximum", false, ALL),
+    ("max", false, ALL),
+    ("subject to", false, ALL),
+    ("such that", false, ALL),
+    ("s.t.", false, ALL),
+    ("st", false, ALL),
+    ("bounds", true, ALL),
+    ("bound", true, ALL),
+    ("generals", true, ALL),
+    ("general", true, ALL),
+    ("gen", true, ALL),
+    ("integers", true, &[Dialect::Xpress]),
+    ("integer", true, &[Dialect::Xpress]),
+    ("binaries", true, ALL),
+    ("binary", true, ALL),
+    ("bin", true, ALL),
+    ("semi-continuous", true, ALL),
+    ("semis", true, ALL),
+    ("semi", true, ALL),
+    ("sos", true, ALL),
+    ("end", false, ALL),
+];
+
+/// A non-blank source line with its comment removed.
+struct Line<'s> {
+    number: usize,
+    text: &'s str,
+    header: Option<&'static (&'static str, bool, &'static [Dialect])>,
+}
+
+/// Returns the non-blank lines of `source`, with any line that is only a section header identified.
+fn lines(source: &str) -> impl Iterator<Item = Line<'_>> {
+    source.lines().enumerate().filter_map(|(index, line)| {
+        let text = line.split('\\').next().unwrap_or_default().trim();
+        if text.is_empty() {
+            return None;
+        }
+        let header = HEADERS.iter().find(|(header, ..)| header.eq_ignore_ascii_case(text));
+        Some(Line { number: index + 1, text, header })
+    })
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP501`: A file that does not finish with `End`.
+pub struct MissingEnd;
+
+impl LintRule for MissingEnd {
+    fn code(&self) -> &'static str {
+        "LP501"
+    }
+
+    fn name(&self) -> &'static str {
+        "missing-end"
+    }
+
+    fn description(&self) -> &'static str {
+        "Files without a closing End line, which some solvers reject"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check_source(&self, source: &str, context: &mut RuleContext<'_>) {
+        if lines(source).last().map_or(true, |line| !line.text.eq_ignore_ascii_case("end")) {
+            context.report(Location::Problem, "file does not finish with End");
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP502`: A bounds, type or SOS section header with nothing after it.
+pub struct EmptySection;
+
+impl LintRule for EmptySection {
+    fn code(&self) -> &'static str {
+        "LP502"
+    }
+
+    fn name(&self) -> &'static str {
+        "empty-section"
+    }
+
+    fn description(&self) -> &'static str {
+        "Bounds, type or SOS sections that declare nothing"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check_source(&self, source: &str, context: &mut RuleContext<'_>) {
+        let mut open: Option<Line<'_>> = None;
+        for line in lines(source) {
+            if let Some(section) = open.take() {
+                if line.header.is_some() {
+                    context.report(Location::Line { line: section.number }, format!("{} section is empty", section.text));
+                }
+            }
+            if line.header.map_or(false, |(_, declares, _)| *declares) {
+                open = Some(line);
+            }
+        }
+        if let Some(section) = open {
+            context.report(Location::Line { line: section.number }, format!("{} section is empty", section.text));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP503`: A section header that the selected dialect does not accept.
+///
+/// For example `Integers` is an Xpress header; CPLEX and Gurobi only accept `Generals`.
+pub struct ForeignSectionHeader;
+
+impl LintRule for ForeignSectionHeader {
+    fn code(&self) -> &'static str {
+        "LP503"
+    }
+
+    fn name(&self) -> &'static str {
+        "foreign-section-header"
+    }
+
+    fn description(&self) -> &'static str {
+        "Section headers only valid in another dialect"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check_source(&self, source: &str, context: &mut RuleContext<'_>) {
+        let dialect = context.dialect();
+        for line in lines(source) {
+            if let Some((_, _, dialects)) = line.header.filter(|(_, _, dialects)| !dialects.contains(&dialect)) {
+                let accepted = dialects.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+                let message = format!("{} is not a {dialect} section header, it is only accepted by {accepted}", line.text);
+                context.report(Location::Line { line: line.number }, message);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        problem::LpProblem,
+        validation::{Dialect, Strictness, ValidationContext},
+    };
+
+    #[test]
+    fn test_structure_rules() {
+        let source = "minimize\nobj: x + y\nsubject to\nc1: x + y >= 1\nbounds\n\\ no bounds yet\ninteger\nx\ngenerals\n";
+        let problem = LpProblem::parse(source).expect("test case not to fail");
+
+        let mut context = ValidationContext::new();
+        assert!(context.validate(&problem).iter().all(|d| !d.code.starts_with("LP5")));
+
+        let messages: Vec<_> =
+            context.validate_source(&problem, source).iter().filter(|d| d.code.starts_with("LP5")).map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "warning[LP501] problem: file does not finish with End",
+                "warning[LP502] line 5: bounds section is empty",
+                "warning[LP502] line 9: generals section is empty",
+                "warning[LP503] line 7: integer is not a cplex section header, it is only accepted by xpress",
+            ]
+        );
+
+        let mut context = ValidationContext::new().with_dialect(Dialect::Xpress).with_strictness(Strictness::Strict);
+        let messages: Vec<_> =
+            context.validate_source(&problem, source).iter().filter(|d| d.code.starts_with("LP5")).map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "error[LP501] problem: file does not finish with End",
+                "error[LP502] line 5: bounds section is empty",
+                "error[LP502] line 9: generals section is empty",
+            ]
+        );
+    }
+}
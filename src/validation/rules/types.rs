@@ -0,0 +1,129 @@
+//! Checks that variable types agree with the bounds declared for them.
+//!
+//! The type sections replace a variable's `var_type`, so these rules inspect
+//! the bound kept in `Variable::declared_bounds`.
+//!
+
+use crate::{
+    model::VariableType,
+    problem::LpProblem,
+    validation::{LintRule, Location, RuleContext, Severity},
+};
+
+/// Formats a `(lower, upper)` pair as an interval.
+fn interval((lower, upper): (f64, f64)) -> String {
+    format!("[{lower}, {upper}]")
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP006`: A binary variable whose declared bounds exclude both 0 and 1.
+pub struct BinaryBoundConflict;
+
+impl LintRule for BinaryBoundConflict {
+    fn code(&self) -> &'static str {
+        "LP006"
+    }
+
+    fn name(&self) -> &'static str {
+        "binary-bound-conflict"
+    }
+
+    fn description(&self) -> &'static str {
+        "Binary variables whose bounds leave no value in {0, 1}"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, problem: &LpProblem, context: &mut RuleContext<'_>) {
+        for (name, variable) in &problem.variables {
+            if variable.var_type != VariableType::Binary {
+                continue;
+            }
+            if let Some(declared) = &variable.declared_bounds {
+                let (lower, upper) = declared.bounds();
+                if (lower > 0.0 || upper < 0.0) && (lower > 1.0 || upper < 1.0) {
+                    let message = format!("declared bounds {} exclude both 0 and 1", interval((lower, upper)));
+                    context.report(Location::variable(name), message);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// `LP007`: A semi-continuous variable without a finite upper bound.
+///
+/// Most solvers require one, since it is the upper end of the non-zero range.
+pub struct SemiContinuousUpperBound;
+
+impl LintRule for SemiContinuousUpperBound {
+    fn code(&self) -> &'static str {
+        "LP007"
+    }
+
+    fn name(&self) -> &'static str {
+        "semi-continuous-upper-bound"
+    }
+
+    fn description(&self) -> &'static str {
+        "Semi-continuous variables must declare a finite upper bound"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, problem: &LpProblem, context: &mut RuleContext<'_>) {
+        for (name, variable) in &problem.variables {
+            if variable.var_type != VariableType::SemiContinuous {
+                continue;
+            }
+            match variable.declared_bounds.as_ref().map(|_| variable.effective_bounds()) {
+                None => context.report(Location::variable(name), "semi-continuous variable has no upper bound"),
+                Some((lower, upper)) if !upper.is_finite() => {
+                    let message =
+                        format!("semi-continuous variable has no finite upper bound, declared bounds are {}", interval((lower, upper)));
+                    context.report(Location::variable(name), message);
+                }
+                Some(_) => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{model::VariableType, problem::LpProblem, validation::ValidationContext};
+
+    #[test]
+    fn test_type_rules() {
+        let problem = LpProblem::parse(
+            "minimize\nobj: a + b + c + s + t + u + v\nsubject to\nc1: a + b + c + s + t + u + v >= 1\nbounds\n2 <= a <= 5\n-1 <= b <= 5\n-3 <= c <= -1\ns >= 2\n1 <= t <= 8\n5 <= u <= 2\nintegers\ngenerals\nbinaries\na b c\nsemi-continuous\ns t u v\nend",
+        )
+        .expect("test case not to fail");
+        assert_eq!(problem.variables["t"].declared_bounds, Some(VariableType::DoubleBound(1.0, 8.0)));
+        assert_eq!(problem.variables["t"].effective_bounds(), (1.0, 8.0));
+        assert_eq!(problem.variables["b"].effective_bounds(), (0.0, 1.0));
+        assert_eq!(problem.variables["v"].effective_bounds(), (0.0, f64::INFINITY));
+
+        let mut context = ValidationContext::new();
+        let messages: Vec<_> = context
+            .validate(&problem)
+            .iter()
+            .filter(|d| ["LP005", "LP006", "LP007"].contains(&d.code.as_str()))
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "error[LP005] variable u: lower bound 5 exceeds upper bound 2",
+                "error[LP006] variable a: declared bounds [2, 5] exclude both 0 and 1",
+                "error[LP006] variable c: declared bounds [-3, -1] exclude both 0 and 1",
+                "error[LP007] variable s: semi-continuous variable has no finite upper bound, declared bounds are [2, inf]",
+                "error[LP007] variable v: semi-continuous variable has no upper bound",
+            ]
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! WebAssembly bindings for use from JavaScript.
+//!
+//! Enabled by the `wasm` feature. Every function takes LP content as a string
+//! or bytes, so nothing touches the file system, and returns its result as a
+//! JSON string using the `serde` representation of the crate's types. Errors
+//! are thrown as JavaScript strings.
+//!
+//! The crate is not built as a `cdylib` by default; build the module with
+//! `cargo rustc --lib --target wasm32-unknown-unknown --features wasm --crate-type cdylib`
+//! and generate the JavaScript glue with `wasm-bindgen`.
+//!
+//! ```js
+//! import { parse, validate, diff } from "lp_parser_rs";
+//!
+//! const problem = JSON.parse(parse(content));
+//! const diagnostics = JSON.parse(validate(content, "gurobi"));
+//! const report = JSON.parse(diff(before, after));
+//! ```
+//!
+//! - `parse`: Parses LP content into the JSON form of an `LpProblem`.
+//! - `parseBytes`: Parses UTF-8 encoded LP content.
+//! - `validate`: Runs the built-in lint rules and returns their diagnostics.
+//! - `diff`: Compares two problems and returns a `DiffReport`.
+//!
+
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    diff::{build_diff_report, DiffOptions},
+    problem::LpProblem,
+    validation::{Dialect, ValidationContext},
+};
+
+fn parse_problem(input: &str) -> Result<LpProblem<'_>, String> {
+    LpProblem::parse(input).map_err(|error| format!("failed to parse LP content: {error}"))
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string(value).map_err(|error| error.to_string())
+}
+
+fn parse_json(input: &str) -> Result<String, String> {
+    to_json(&parse_problem(input)?)
+}
+
+fn validate_json(input: &str, dialect: Option<String>) -> Result<String, String> {
+    let problem = parse_problem(input)?;
+    let dialect: Dialect = dialect.as_deref().map_or(Ok(Dialect::default()), str::parse)?;
+    to_json(&ValidationContext::new().with_dialect(dialect).validate_source(&problem, input))
+}
+
+fn diff_json(old: &str, new: &str) -> Result<String, String> {
+    let (old, new) = (parse_problem(old)?, parse_problem(new)?);
+    to_json(&build_diff_report(&old, &new, &DiffOptions::default().with_rename_detection(true)))
+}
+
+#[wasm_bindgen]
+/// Parses LP content and returns the problem as JSON.
+///
+/// # Errors
+///
+/// Throws if the content is not a valid LP problem.
+pub fn parse(input: &str) -> Result<String, JsValue> {
+    parse_json(input).map_err(|error| JsValue::from_str(&error))
+}
+
+#[wasm_bindgen(js_name = parseBytes)]
+/// Parses UTF-8 encoded LP content, such as the contents of a `File`, and returns the problem as JSON.
+///
+/// # Errors
+///
+/// Throws if the bytes are not valid UTF-8 or not a valid LP problem.
+pub fn parse_bytes(input: &[u8]) -> Result<String, JsValue> {
+    let input = std::str::from_utf8(input).map_err(|error| JsValue::from_str(&format!("LP content is not valid UTF-8: {error}")))?;
+    parse(input)
+}
+
+#[wasm_bindgen]
+/// Runs the built-in lint rules over LP content and returns the diagnostics as JSON.
+///
+/// `dialect` is one of `cplex`, `gurobi`, `xpress` or `mosek`, `cplex` when omitted.
+///
+/// # Errors
+///
+/// Throws if the content is not a valid LP problem or the dialect is unknown.
+pub fn validate(input: &str, dialect: Option<String>) -> Result<String, JsValue> {
+    validate_json(input, dialect).map_err(|error| JsValue::from_str(&error))
+}
+
+#[wasm_bindgen]
+/// Compares two LP problems and returns the `DiffReport` as JSON, detecting renamed entities.
+///
+/// # Errors
+///
+/// Throws if either input is not a valid LP problem.
+pub fn diff(old: &str, new: &str) -> Result<String, JsValue> {
+    diff_json(old, new).map_err(|error| JsValue::from_str(&error))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::wasm::{diff_json, parse_json, validate_json};
+
+    #[test]
+    fn test_wasm_bindings() {
+        let input = "minimize\nobj: x + y\nsubject to\nc1: x + y >= 1\nend";
+
+        let problem: serde_json::Value =
+            serde_json::from_str(&parse_json(input).expect("test case not to fail")).expect("test case not to fail");
+        assert_eq!(problem["objectives"]["obj"]["coefficients"].as_array().expect("test case not to fail").len(), 2);
+        assert!(parse_json("not an lp file").is_err());
+
+        let diagnostics: serde_json::Value =
+            serde_json::from_str(&validate_json(input, Some("gurobi".to_string())).expect("test case not to fail"))
+                .expect("test case not to fail");
+        assert!(diagnostics.as_array().expect("test case not to fail").is_empty());
+        assert!(validate_json(input, Some("glpk".to_string())).is_err());
+
+        let report: serde_json::Value =
+            serde_json::from_str(&diff_json(input, &input.replace("x + y >= 1", "x + y >= 2")).expect("test case not to fail"))
+                .expect("test case not to fail");
+        assert_eq!(report["constraints"].as_array().expect("test case not to fail").len(), 1);
+    }
+}
@@ -38,6 +38,25 @@ fn nom_fit2d() {
     });
 }
 
+#[test]
+fn syntax_tree_round_trip() {
+    let mut resources = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    resources.push("resources");
+    for entry in std::fs::read_dir(resources).expect("failed to read resources") {
+        let path = entry.expect("failed to read resource").path();
+        if path.extension().map_or(true, |extension| extension != "lp") {
+            continue;
+        }
+        let input = parse_file(&path).expect("failed to read file from resources");
+        let tree = lp_parser_rs::syntax::cst::SyntaxTree::parse(&input);
+        assert_eq!(tree.to_string(), input, "{} did not round trip", path.display());
+
+        let tokens = lp_parser_rs::syntax::tokenize(&input);
+        assert!(tokens.windows(2).all(|pair| pair[0].span.end == pair[1].span.start));
+        assert!(tokens.iter().all(|token| input[token.span.clone()] == *token.text));
+    }
+}
+
 #[ignore]
 #[test]
 /// A test used my contributors for assisting with issues